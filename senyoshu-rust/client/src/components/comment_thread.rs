@@ -0,0 +1,216 @@
+use dioxus::core_macro::rsx;
+use dioxus::prelude::*;
+use tracing::error;
+
+use senyoshu_common::types::api::comment::{
+    Comment, CommentQuery, CommentTarget, CREATE_COMMENT_API, DELETE_COMMENT_API,
+    EDIT_COMMENT_API, LIST_COMMENTS_API, MAX_COMMENT_LIMIT, NewComment,
+};
+
+use crate::components::button::Button;
+use crate::global::BUSYING;
+use crate::storage::account::ACCOUNT;
+use crate::text::TEXT;
+
+#[derive(Props, Clone, Debug, PartialEq, Eq)]
+pub struct CommentThreadProps {
+    pub target: CommentTarget,
+}
+
+/// 词条或修改请求下的讨论，回复按缩进显示在被回复的评论下面
+pub fn CommentThread(props: CommentThreadProps) -> Element {
+    let target = props.target;
+    //已经展开的页数，重新加载时保持
+    let mut pages = use_signal(|| 1usize);
+    let mut resource = use_resource(move || async move { list_pages(target, pages()).await });
+    let mut input = use_signal(String::new);
+    let mut replying = use_signal(|| None::<i64>);
+    let mut editing = use_signal(|| None::<i64>);
+
+    let account_info = ACCOUNT.snap();
+    let (comments, has_more) = resource.value().read().to_owned().flatten().unwrap_or_default();
+
+    let submit = move |_| {
+        let content = input.peek().to_owned();
+        let parent = *replying.peek();
+        let edit_id = *editing.peek();
+        *BUSYING.write() = true;
+        spawn(async move {
+            let rv = match edit_id {
+                Some(id) => EDIT_COMMENT_API.call(&(id, content)).await.map(|_| ()),
+                None => {
+                    let new_comment = NewComment { target, parent, content };
+                    CREATE_COMMENT_API.call(&new_comment).await.map(|_| ())
+                }
+            };
+            match rv {
+                Ok(()) => {
+                    input.set(String::new());
+                    replying.set(None);
+                    editing.set(None);
+                    resource.restart();
+                }
+                Err(err) => {
+                    error!("评论失败: {err}");
+                }
+            }
+            *BUSYING.write() = false;
+        });
+    };
+    let delete = move |id: i64| {
+        *BUSYING.write() = true;
+        spawn(async move {
+            if let Err(err) = DELETE_COMMENT_API.call(&id).await {
+                error!("删除评论失败: {err}");
+            }
+            resource.restart();
+            *BUSYING.write() = false;
+        });
+    };
+
+    let list = flatten(&comments, None, 0).into_iter().map(|(depth, comment)| {
+        let id = comment.id;
+        let create_time = comment.create_time.format("%Y-%m-%d %H:%M").to_string();
+        let indent = depth * 16;
+        let edited = comment.edit_time.map(|_| {
+            let edited = TEXT.read().comment_thread_edited;
+            rsx! { span { "{edited}" } }
+        });
+        let content = if comment.deleted {
+            TEXT.read().comment_thread_deleted.to_string()
+        } else {
+            comment.content.to_owned()
+        };
+        let is_author = account_info
+            .as_ref()
+            .is_some_and(|it| it.user_info.uid == comment.author);
+        let is_maintainer = account_info
+            .as_ref()
+            .is_some_and(|it| it.user_info.content_maintainer && !it.user_info.restrict_user);
+        let can_post = account_info.is_some() && !comment.deleted;
+        let can_edit = is_author && !comment.deleted;
+        let can_delete = (is_author || is_maintainer) && !comment.deleted;
+        let edit_content = comment.content.to_owned();
+        let author_name = comment.author_name.to_owned();
+        rsx! {
+            div { key: "comment:{id}", style: "margin-left:{indent}px;padding:4px 0",
+                div { style: "display:flex;flex-direction:row;gap:8px;color:gray",
+                    span { "{author_name}" }
+                    span { "{create_time}" }
+                    {edited}
+                }
+                div { style: "white-space:pre-wrap", "{content}" }
+                div { style: "display:flex;flex-direction:row;gap:8px",
+                    if can_post {
+                        Button {
+                            onclick: move |_| {
+                                editing.set(None);
+                                replying.set(Some(id));
+                            },
+                            {TEXT.read().comment_thread_reply}
+                        }
+                    }
+                    if can_edit {
+                        Button {
+                            onclick: move |_| {
+                                replying.set(None);
+                                editing.set(Some(id));
+                                input.set(edit_content.to_owned());
+                            },
+                            {TEXT.read().comment_thread_edit}
+                        }
+                    }
+                    if can_delete {
+                        Button {
+                            disabled: *BUSYING.read(),
+                            onclick: move |_| delete(id),
+                            {TEXT.read().comment_thread_delete}
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    //回复或编辑时显示对应的评论，可以取消回到发表新评论
+    let replying_hint = replying().or(editing()).map(|id| {
+        let action = if editing().is_some() {
+            TEXT.read().comment_thread_edit
+        } else {
+            TEXT.read().comment_thread_reply
+        };
+        rsx! {
+            div { style: "display:flex;flex-direction:row;gap:8px",
+                span { "{action} #{id}" }
+                Button {
+                    onclick: move |_| {
+                        replying.set(None);
+                        editing.set(None);
+                        input.set(String::new());
+                    },
+                    {TEXT.read().comment_thread_cancel}
+                }
+            }
+        }
+    });
+
+    rsx! {
+        div { style: "margin:16px",
+            h3 { {TEXT.read().comment_thread_title} }
+            {list}
+            if has_more {
+                Button {
+                    onclick: move |_| *pages.write() += 1,
+                    {TEXT.read().comment_thread_more}
+                }
+            }
+            if account_info.is_some() {
+                {replying_hint}
+                textarea {
+                    style: "width:100%",
+                    rows: 3,
+                    placeholder: TEXT.read().comment_thread_placeholder,
+                    value: "{input}",
+                    oninput: move |evt| input.set(evt.value())
+                }
+                Button {
+                    disabled: *BUSYING.read() || input.read().trim().is_empty(),
+                    onclick: submit,
+                    {TEXT.read().comment_thread_submit}
+                }
+            }
+        }
+    }
+}
+
+/// 依次读取前 `pages` 页，同时返回是否还有下一页
+async fn list_pages(target: CommentTarget, pages: usize) -> Option<(Vec<Comment>, bool)> {
+    let mut comments: Vec<Comment> = Vec::new();
+    for _ in 0..pages {
+        let query = CommentQuery {
+            target,
+            after: comments.last().map(|it| it.id),
+            limit: MAX_COMMENT_LIMIT,
+        };
+        let page = LIST_COMMENTS_API.call(&query).await.ok()?;
+        let full = page.len() as u64 == MAX_COMMENT_LIMIT;
+        comments.extend(page);
+        if !full {
+            return Some((comments, false));
+        }
+    }
+    Some((comments, true))
+}
+
+/// 按深度优先的顺序展开成列表，同一层保持发表顺序
+fn flatten(comments: &[Comment], parent: Option<i64>, depth: usize) -> Vec<(usize, &Comment)> {
+    comments
+        .iter()
+        .filter(|it| it.parent == parent)
+        .flat_map(|it| {
+            let mut list = Vec::from([(depth, it)]);
+            list.extend(flatten(comments, Some(it.id), depth + 1));
+            list
+        })
+        .collect()
+}
@@ -1,4 +1,5 @@
 pub mod button;
+pub mod comment_thread;
 pub mod dialog;
 pub mod editor;
 pub mod lazy_list;
@@ -11,6 +11,12 @@ pub(crate) const CN_TEXT: Text = Text {
     login_page_password: "密码",
     login_page_register: "注册(register)",
     login_page_sign_in: "登入",
+    login_page_wait: "尝试次数过多，请稍后再试",
+    login_page_forgot_passwd: "忘记密码",
+    login_page_reset_mail_sent: "如果该账号绑定了邮箱，重置密码的邮件已经发出",
+    reset_passwd_page_new_passwd: "新密码",
+    reset_passwd_page_submit: "重置密码",
+    reset_passwd_page_success: "密码已重置，请重新登录",
     home_page_sign_out: "登出",
 
     voice_page_action_try_listen: "试听",
@@ -48,10 +54,74 @@ pub(crate) const CN_TEXT: Text = Text {
     home_page_to_voices_page: "语音管理",
     home_page_to_knowledge_page: "学习管理",
     home_page_to_management_page: "内容管理",
+    home_page_to_admin_page: "用户管理",
     home_page_to_setting_page: "设置管理",
     home_page_to_about_page: "关于我们",
 
     setting_page_menu_show_refresh_app: "显示刷新APP菜单按钮",
+    setting_page_sessions: "登录的设备",
+    setting_page_sessions_current: "当前设备",
+    setting_page_sessions_revoke: "退出",
+    setting_page_sessions_revoke_others: "退出其他设备",
+    setting_page_e_mail: "邮箱",
+    setting_page_e_mail_send_code: "发送验证码",
+    setting_page_e_mail_code: "验证码",
+    setting_page_e_mail_verify: "绑定",
+    setting_page_account_data: "个人数据",
+    setting_page_export: "导出全部数据",
+    setting_page_delete_account: "删除账号",
+    setting_page_delete_account_passwd: "输入密码以删除账号",
     home_page_connect_to_japan_internet: "接入日本互联网",
     management_page_to_deduplicate_page: "词汇去重",
+    admin_page_search: "搜索",
+    admin_page_post_permission: "可以提交修改",
+    admin_page_content_maintainer: "审核者",
+    admin_page_admin: "管理员",
+    admin_page_vip: "会员",
+    admin_page_vip_revoke: "取消会员",
+    admin_page_restrict: "限制",
+    admin_page_restrict_reason: "限制理由",
+    admin_page_unrestrict: "解除限制",
+    admin_page_surf_servers: "代理服务器",
+    admin_page_surf_enabled: "启用",
+    admin_page_surf_rotate: "更换密码",
+    admin_page_surf_add: "添加",
+
+    management_page_to_moderation_log_page: "审核记录",
+    moderation_log_page_all: "全部",
+    moderation_log_page_pass: "通过",
+    moderation_log_page_cancel: "驳回",
+    moderation_log_page_withdraw: "撤回",
+    moderation_log_page_update_many: "批量修改",
+    moderation_log_page_delete: "删除",
+    moderation_log_page_more: "加载更多",
+
+    home_page_to_my_submissions_page: "我的提交",
+    inspection_page_comment: "审核意见（可选）",
+    my_submissions_page_pending: "待审核",
+    my_submissions_page_pass: "已通过",
+    my_submissions_page_cancel: "已驳回",
+    my_submissions_page_withdraw: "已撤回",
+    my_submissions_page_withdraw_action: "撤回",
+    my_submissions_page_comment: "审核意见",
+    comment_thread_title: "讨论",
+    comment_thread_placeholder: "发表评论",
+    comment_thread_submit: "发送",
+    comment_thread_reply: "回复",
+    comment_thread_edit: "编辑",
+    comment_thread_delete: "删除",
+    comment_thread_cancel: "取消",
+    comment_thread_deleted: "该评论已删除",
+    comment_thread_edited: "已编辑",
+    comment_thread_more: "加载更多",
+
+    error_not_auth: "未登录，或用户名、密码错误",
+    error_permission_denied: "没有权限",
+    error_validation_failed: "输入不合法",
+    error_conflict: "冲突，可能已被他人修改",
+    error_not_found: "未找到",
+    error_rate_limited: "请求过于频繁，请稍后再试",
+    error_database: "服务器错误，请稍后再试",
+    error_mail_unavailable: "邮件发送失败，请稍后再试",
+    error_network: "网络错误",
 };
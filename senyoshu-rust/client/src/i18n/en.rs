@@ -12,6 +12,12 @@ pub(crate) const EN_TEXT: Text = Text {
     login_page_password: "password",
     login_page_register: "register",
     login_page_sign_in: "sign in",
+    login_page_wait: "too many attempts, please wait",
+    login_page_forgot_passwd: "forgot password",
+    login_page_reset_mail_sent: "if the account has an e-mail, a reset mail has been sent",
+    reset_passwd_page_new_passwd: "new password",
+    reset_passwd_page_submit: "reset password",
+    reset_passwd_page_success: "password has been reset, please sign in again",
     home_page_sign_out: "sign out",
 
     voice_page_action_try_listen: "listen",
@@ -51,10 +57,74 @@ pub(crate) const EN_TEXT: Text = Text {
     home_page_to_voices_page: "voices",
     home_page_to_knowledge_page: "knowledge",
     home_page_to_management_page: "content management",
+    home_page_to_admin_page: "user management",
     home_page_to_setting_page: "setting",
     home_page_to_about_page: "about",
 
     setting_page_menu_show_refresh_app: "show refresh app button in menu",
+    setting_page_sessions: "signed-in devices",
+    setting_page_sessions_current: "this device",
+    setting_page_sessions_revoke: "sign out",
+    setting_page_sessions_revoke_others: "sign out other devices",
+    setting_page_e_mail: "e-mail",
+    setting_page_e_mail_send_code: "send code",
+    setting_page_e_mail_code: "code",
+    setting_page_e_mail_verify: "bind",
+    setting_page_account_data: "personal data",
+    setting_page_export: "export all data",
+    setting_page_delete_account: "delete account",
+    setting_page_delete_account_passwd: "enter password to delete account",
     home_page_connect_to_japan_internet: "connect to japan internet",
     management_page_to_deduplicate_page: "word deduplicate",
+    admin_page_search: "search",
+    admin_page_post_permission: "post permission",
+    admin_page_content_maintainer: "content maintainer",
+    admin_page_admin: "administrator",
+    admin_page_vip: "VIP",
+    admin_page_vip_revoke: "revoke VIP",
+    admin_page_restrict: "restrict",
+    admin_page_restrict_reason: "restrict reason",
+    admin_page_unrestrict: "unrestrict",
+    admin_page_surf_servers: "surf servers",
+    admin_page_surf_enabled: "enabled",
+    admin_page_surf_rotate: "rotate passwords",
+    admin_page_surf_add: "add",
+
+    management_page_to_moderation_log_page: "moderation log",
+    moderation_log_page_all: "all",
+    moderation_log_page_pass: "pass",
+    moderation_log_page_cancel: "cancel",
+    moderation_log_page_withdraw: "withdraw",
+    moderation_log_page_update_many: "bulk update",
+    moderation_log_page_delete: "delete",
+    moderation_log_page_more: "load more",
+
+    home_page_to_my_submissions_page: "my submissions",
+    inspection_page_comment: "review comment (optional)",
+    my_submissions_page_pending: "pending",
+    my_submissions_page_pass: "passed",
+    my_submissions_page_cancel: "cancelled",
+    my_submissions_page_withdraw: "withdrawn",
+    my_submissions_page_withdraw_action: "withdraw",
+    my_submissions_page_comment: "review comment",
+    comment_thread_title: "discussion",
+    comment_thread_placeholder: "write a comment",
+    comment_thread_submit: "send",
+    comment_thread_reply: "reply",
+    comment_thread_edit: "edit",
+    comment_thread_delete: "delete",
+    comment_thread_cancel: "cancel",
+    comment_thread_deleted: "this comment was deleted",
+    comment_thread_edited: "edited",
+    comment_thread_more: "load more",
+
+    error_not_auth: "not logged in, or the username or password is wrong",
+    error_permission_denied: "permission denied",
+    error_validation_failed: "invalid input",
+    error_conflict: "conflict, it may have been changed by others",
+    error_not_found: "not found",
+    error_rate_limited: "too many requests, please try again later",
+    error_database: "server error, please try again later",
+    error_mail_unavailable: "failed to send mail, please try again later",
+    error_network: "network error",
 };
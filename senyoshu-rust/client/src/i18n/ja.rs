@@ -11,6 +11,12 @@ pub(crate) const JA_TEXT: Text = Text {
     login_page_password: "login_page_password",
     login_page_register: "登録(register)",
     login_page_sign_in: "ログイン",
+    login_page_wait: "login_page_wait",
+    login_page_forgot_passwd: "login_page_forgot_passwd",
+    login_page_reset_mail_sent: "login_page_reset_mail_sent",
+    reset_passwd_page_new_passwd: "reset_passwd_page_new_passwd",
+    reset_passwd_page_submit: "reset_passwd_page_submit",
+    reset_passwd_page_success: "reset_passwd_page_success",
     home_page_sign_out: "ログアウト",
 
     voice_page_action_try_listen: "voice_page_action_try_listen",
@@ -49,10 +55,74 @@ pub(crate) const JA_TEXT: Text = Text {
     home_page_to_voices_page: "home_page_to_voices_page",
     home_page_to_knowledge_page: "home_page_to_knowledge_page",
     home_page_to_management_page: "home_page_to_management_page",
+    home_page_to_admin_page: "home_page_to_admin_page",
     home_page_to_setting_page: "home_page_to_setting_page",
     home_page_to_about_page: "home_page_to_about_page",
 
     setting_page_menu_show_refresh_app: "setting_page_menu_show_refresh_app",
+    setting_page_sessions: "setting_page_sessions",
+    setting_page_sessions_current: "setting_page_sessions_current",
+    setting_page_sessions_revoke: "setting_page_sessions_revoke",
+    setting_page_sessions_revoke_others: "setting_page_sessions_revoke_others",
+    setting_page_e_mail: "setting_page_e_mail",
+    setting_page_e_mail_send_code: "setting_page_e_mail_send_code",
+    setting_page_e_mail_code: "setting_page_e_mail_code",
+    setting_page_e_mail_verify: "setting_page_e_mail_verify",
+    setting_page_account_data: "setting_page_account_data",
+    setting_page_export: "setting_page_export",
+    setting_page_delete_account: "setting_page_delete_account",
+    setting_page_delete_account_passwd: "setting_page_delete_account_passwd",
     home_page_connect_to_japan_internet: "home_page_connect_to_japan_internet",
     management_page_to_deduplicate_page: "management_page_to_deduplicate_page",
+    admin_page_search: "admin_page_search",
+    admin_page_post_permission: "admin_page_post_permission",
+    admin_page_content_maintainer: "admin_page_content_maintainer",
+    admin_page_admin: "admin_page_admin",
+    admin_page_vip: "admin_page_vip",
+    admin_page_vip_revoke: "admin_page_vip_revoke",
+    admin_page_restrict: "admin_page_restrict",
+    admin_page_restrict_reason: "admin_page_restrict_reason",
+    admin_page_unrestrict: "admin_page_unrestrict",
+    admin_page_surf_servers: "admin_page_surf_servers",
+    admin_page_surf_enabled: "admin_page_surf_enabled",
+    admin_page_surf_rotate: "admin_page_surf_rotate",
+    admin_page_surf_add: "admin_page_surf_add",
+
+    management_page_to_moderation_log_page: "management_page_to_moderation_log_page",
+    moderation_log_page_all: "moderation_log_page_all",
+    moderation_log_page_pass: "moderation_log_page_pass",
+    moderation_log_page_cancel: "moderation_log_page_cancel",
+    moderation_log_page_withdraw: "moderation_log_page_withdraw",
+    moderation_log_page_update_many: "moderation_log_page_update_many",
+    moderation_log_page_delete: "moderation_log_page_delete",
+    moderation_log_page_more: "moderation_log_page_more",
+
+    home_page_to_my_submissions_page: "home_page_to_my_submissions_page",
+    inspection_page_comment: "inspection_page_comment",
+    my_submissions_page_pending: "my_submissions_page_pending",
+    my_submissions_page_pass: "my_submissions_page_pass",
+    my_submissions_page_cancel: "my_submissions_page_cancel",
+    my_submissions_page_withdraw: "my_submissions_page_withdraw",
+    my_submissions_page_withdraw_action: "my_submissions_page_withdraw_action",
+    my_submissions_page_comment: "my_submissions_page_comment",
+    comment_thread_title: "comment_thread_title",
+    comment_thread_placeholder: "comment_thread_placeholder",
+    comment_thread_submit: "comment_thread_submit",
+    comment_thread_reply: "comment_thread_reply",
+    comment_thread_edit: "comment_thread_edit",
+    comment_thread_delete: "comment_thread_delete",
+    comment_thread_cancel: "comment_thread_cancel",
+    comment_thread_deleted: "comment_thread_deleted",
+    comment_thread_edited: "comment_thread_edited",
+    comment_thread_more: "comment_thread_more",
+
+    error_not_auth: "error_not_auth",
+    error_permission_denied: "error_permission_denied",
+    error_validation_failed: "error_validation_failed",
+    error_conflict: "error_conflict",
+    error_not_found: "error_not_found",
+    error_rate_limited: "error_rate_limited",
+    error_database: "error_database",
+    error_mail_unavailable: "error_mail_unavailable",
+    error_network: "error_network",
 };
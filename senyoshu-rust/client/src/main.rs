@@ -9,11 +9,15 @@ use dioxus::prelude::*;
 use dioxus_router::prelude::Router;
 use tracing::{debug, Level};
 
+use senyoshu_common::types::api::set_token_provider;
+
 use window::add_window_size_change_listener;
 
 use crate::router::AppRoute;
 use crate::singleton::confirm_box::ConfirmBox;
 use crate::singleton::top_navigation::TOP_NAVIGATION;
+use crate::storage::account::ACCOUNT;
+use crate::storage::push::connect_push;
 use crate::storage::update;
 use crate::task::TASK_DEQUE;
 
@@ -58,6 +62,9 @@ fn main() {
         ");
     }
 
+    //需要鉴权的接口从当前登录的账号中读取 token
+    set_token_provider(|| ACCOUNT.peek().map(|it| it.token));
+
     launch(App);
 }
 
@@ -71,6 +78,7 @@ pub fn App() -> Element {
     let _ = use_coroutine(|_rx: UnboundedReceiver<()>| async {
         add_window_size_change_listener();
         update().await;
+        connect_push();
         loop {
             TASK_DEQUE.exec();
             sleep(Duration::from_millis(10)).await;
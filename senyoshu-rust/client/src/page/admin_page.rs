@@ -0,0 +1,275 @@
+use std::future::Future;
+
+use dioxus::prelude::*;
+use tracing::error;
+
+use senyoshu_common::types::api::admin::{
+    AdminUserInfo, GET_SURF_SERVER_CONFIGS_API, MAX_USER_QUERY_LIMIT, Role,
+    ROTATE_SURF_CREDENTIALS_API, SAVE_SURF_SERVER_API, SEARCH_USERS_API, SET_RESTRICT_API,
+    SET_USER_ROLE_API, SET_VIP_API, SurfServerConfig, UserQuery, VipChange,
+};
+use senyoshu_common::types::api::ApiResult;
+
+use crate::components::button::Button;
+use crate::global::BUSYING;
+use crate::singleton::confirm_box::confirm;
+use crate::singleton::top_navigation::TOP_NAVIGATION;
+use crate::storage::account::ACCOUNT;
+use crate::text::TEXT;
+
+/// 每次延长的会员天数
+const VIP_EXTEND_DAYS: u32 = 30;
+
+pub fn AdminPage() -> Element {
+    TOP_NAVIGATION.reset();
+
+    let mut keyword = use_signal(String::new);
+    let mut users = use_signal(Vec::<AdminUserInfo>::new);
+
+    //不是管理员的不显示，服务端同样会拒绝
+    if !ACCOUNT.snap()?.user_info.admin {
+        return None;
+    }
+
+    let search = move |_| {
+        *BUSYING.write() = true;
+        let query = UserQuery {
+            keyword: keyword.peek().to_owned(),
+            offset: 0,
+            limit: MAX_USER_QUERY_LIMIT,
+        };
+        spawn(async move {
+            match SEARCH_USERS_API.call(&query).await {
+                Ok(result) => users.set(result),
+                Err(err) => error!("搜索用户失败: {err}"),
+            }
+            *BUSYING.write() = false;
+        });
+    };
+
+    let list = users.read().to_owned().into_iter().map(|user| {
+        rsx! { UserRow { key: "{user.uid}", user } }
+    });
+
+    rsx! {
+        div { style: "margin:16px",
+            input {
+                value: "{keyword}",
+                oninput: move |evt| keyword.set(evt.value())
+            }
+            Button { disabled: *BUSYING.read(), onclick: search, {TEXT.read().admin_page_search} }
+        }
+        {list}
+        SurfServerList {}
+    }
+}
+
+/// 代理服务器的列表和添加
+#[component]
+fn SurfServerList() -> Element {
+    let mut resource = use_resource(|| async move { GET_SURF_SERVER_CONFIGS_API.call(&()).await.ok() });
+    let mut draft = use_signal(|| SurfServerConfig {
+        method: String::from("aes-256-gcm"),
+        enabled: true,
+        ..Default::default()
+    });
+
+    let save = move |config: SurfServerConfig| {
+        *BUSYING.write() = true;
+        spawn(async move {
+            if let Err(err) = SAVE_SURF_SERVER_API.call(&config).await {
+                error!("保存代理服务器失败: {err}");
+                confirm(Vec::from([TEXT.peek().error(&err)])).await;
+            }
+            resource.restart();
+            *BUSYING.write() = false;
+        });
+    };
+
+    let busying = *BUSYING.read();
+    let servers = resource.value().read().to_owned().flatten().unwrap_or_default();
+    let list = servers.into_iter().map(|server| {
+        let id = server.id.unwrap_or_default();
+        let capacity = if server.capacity == 0 { String::from("-") } else { server.capacity.to_string() };
+        rsx! {
+            div { key: "surf:{id}",
+                span { "{server.name} [{server.region}] {server.server}:{server.server_port} {server.active_users}/{capacity} " }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: server.enabled,
+                        disabled: busying,
+                        onclick: {
+                            let server = server.to_owned();
+                            move |_| {
+                                save(SurfServerConfig {
+                                    enabled: !server.enabled,
+                                    ..server.to_owned()
+                                })
+                            }
+                        }
+                    }
+                    {TEXT.read().admin_page_surf_enabled}
+                }
+                Button {
+                    disabled: busying,
+                    onclick: move |_| {
+                        spawn(async move {
+                            if let Err(err) = ROTATE_SURF_CREDENTIALS_API.call(&id).await {
+                                error!("更换代理服务器密码失败: {err}");
+                            }
+                            resource.restart();
+                        });
+                    },
+                    {TEXT.read().admin_page_surf_rotate}
+                }
+            }
+        }
+    });
+
+    let text_input = move |placeholder: &'static str, value: String, update: fn(&mut SurfServerConfig, String)| {
+        rsx! {
+            input {
+                placeholder,
+                value,
+                oninput: move |evt| update(&mut draft.write(), evt.value())
+            }
+        }
+    };
+    let current = draft.read().to_owned();
+
+    rsx! {
+        fieldset { style: "margin:16px",
+            legend { {TEXT.read().admin_page_surf_servers} }
+            {list}
+            div {
+                {text_input("name", current.name, |it, value| it.name = value)}
+                {text_input("region", current.region, |it, value| it.region = value)}
+                {text_input("server", current.server, |it, value| it.server = value)}
+                {text_input("port", current.server_port.to_string(), |it, value| it.server_port = value.parse().unwrap_or_default())}
+                {text_input("method", current.method, |it, value| it.method = value)}
+                {text_input("capacity", current.capacity.to_string(), |it, value| it.capacity = value.parse().unwrap_or_default())}
+                Button {
+                    disabled: busying,
+                    onclick: move |_| save(draft.peek().to_owned()),
+                    {TEXT.read().admin_page_surf_add}
+                }
+            }
+        }
+    }
+}
+
+/// 提交修改，成功后显示服务端返回的最新信息
+fn update_user(
+    mut user: Signal<AdminUserInfo>,
+    request: impl Future<Output=ApiResult<AdminUserInfo>> + 'static,
+) {
+    *BUSYING.write() = true;
+    spawn(async move {
+        match request.await {
+            Ok(info) => user.set(info),
+            Err(err) => {
+                error!("修改用户失败: {err}");
+                confirm(Vec::from([TEXT.peek().error(&err)])).await;
+            }
+        }
+        *BUSYING.write() = false;
+    });
+}
+
+#[component]
+fn UserRow(user: AdminUserInfo) -> Element {
+    let user = use_signal(|| user);
+    let mut reason = use_signal(String::new);
+
+    let info = user.read().to_owned();
+    let uid = info.uid;
+    let busying = *BUSYING.read();
+
+    let role_checkbox = move |role: Role, checked: bool, label: &'static str| {
+        rsx! {
+            label {
+                input {
+                    r#type: "checkbox",
+                    checked,
+                    disabled: busying,
+                    onclick: move |_| {
+                        update_user(user, async move {
+                            SET_USER_ROLE_API.call(&(uid, role, !checked)).await
+                        });
+                    }
+                }
+                {label}
+            }
+        }
+    };
+
+    let vip = info
+        .vip
+        .map(|vip| vip.format("%Y-%m-%d").to_string())
+        .unwrap_or(String::from("-"));
+
+    let restrict = if info.restrict_user {
+        let restrict_reason = info.restrict_reason.unwrap_or_default();
+        rsx! {
+            span { "{TEXT.read().admin_page_restrict_reason}: {restrict_reason} " }
+            Button {
+                disabled: busying,
+                onclick: move |_| {
+                    update_user(user, async move { SET_RESTRICT_API.call(&(uid, None)).await });
+                },
+                {TEXT.read().admin_page_unrestrict}
+            }
+        }
+    } else {
+        rsx! {
+            input {
+                placeholder: TEXT.read().admin_page_restrict_reason,
+                value: "{reason}",
+                oninput: move |evt| reason.set(evt.value())
+            }
+            Button {
+                disabled: busying || reason.read().trim().is_empty(),
+                onclick: move |_| {
+                    let restrict_reason = reason.peek().trim().to_string();
+                    reason.set(String::new());
+                    update_user(user, async move {
+                        SET_RESTRICT_API.call(&(uid, Some(restrict_reason))).await
+                    });
+                },
+                {TEXT.read().admin_page_restrict}
+            }
+        }
+    };
+
+    rsx! {
+        fieldset { style: "margin:16px",
+            legend { "{info.username} (uid: {uid})" }
+            div {
+                {role_checkbox(Role::PostPermission, info.post_permission, TEXT.read().admin_page_post_permission)}
+                {role_checkbox(Role::ContentMaintainer, info.content_maintainer, TEXT.read().admin_page_content_maintainer)}
+                {role_checkbox(Role::Admin, info.admin, TEXT.read().admin_page_admin)}
+            }
+            div {
+                span { "{TEXT.read().admin_page_vip}: {vip} " }
+                Button {
+                    disabled: busying,
+                    onclick: move |_| {
+                        update_user(user, async move {
+                            SET_VIP_API.call(&(uid, VipChange::ExtendDays(VIP_EXTEND_DAYS))).await
+                        });
+                    },
+                    "+{VIP_EXTEND_DAYS}d"
+                }
+                Button {
+                    disabled: busying || info.vip.is_none(),
+                    onclick: move |_| {
+                        update_user(user, async move { SET_VIP_API.call(&(uid, VipChange::Revoke)).await });
+                    },
+                    {TEXT.read().admin_page_vip_revoke}
+                }
+            }
+            div { {restrict} }
+        }
+    }
+}
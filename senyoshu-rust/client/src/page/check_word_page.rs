@@ -7,18 +7,14 @@ use senyoshu_common::types::api::dic::GET_CHANGE_REQUEST_API;
 
 use crate::components::word_node::WordNode;
 use crate::router::AppRoute;
-use crate::storage::account::ACCOUNT;
 
 pub fn CheckWordPage() -> Element {
     let mut change_word_requests = use_signal(|| Vec::<WordHistoryEntry>::new());
 
-    let _ = use_coroutine(|_rx: UnboundedReceiver<()>| {
+    let _ = use_coroutine(|_rx: UnboundedReceiver<()>| async move {
         //没有登录的根本不会来到这个页面
-        let token = { ACCOUNT.snap().unwrap().token };
-        async move {
-            if let Ok(word_history_entry) = GET_CHANGE_REQUEST_API.call(&token).await {
-                change_word_requests.set(word_history_entry);
-            }
+        if let Ok(word_history_entry) = GET_CHANGE_REQUEST_API.call(&()).await {
+            change_word_requests.set(word_history_entry);
         }
     });
 
@@ -37,7 +37,7 @@ pub fn CreateWordPage() -> Element {
             content_maintainer: true,
             ..
         },
-        token,
+        ..
     }) = ACCOUNT.peek()
     {
         let post_disabled =
@@ -52,16 +52,14 @@ pub fn CreateWordPage() -> Element {
             label: "提出",
             onclick: EventHandler::new(move |_| {
                 *BUSYING.write() = true;
-                let token = token.to_owned();
                 let word_define = WORD_DEFINE_DRAFT.peek().to_owned();
                 spawn(async move {
                     if confirm(vec![String::from("是否确认要创建单词？")]).await {
                         debug!("正在创建单词");
                         let mut success = false;
                         for _ in 0..3 {
-                            let param = (token.to_owned(), word_define.to_owned());
-                            let result = CREATE_WORD_API.call(&param).await;
-                            if let Ok(Some(wid)) = result {
+                            let result = CREATE_WORD_API.call(&word_define).await;
+                            if let Ok(wid) = result {
                                 WORD_DEFINE_DRAFT.reset();
                                 *word_define_signal.write() = WORD_DEFINE_DRAFT.peek().to_owned();
                                 success = true;
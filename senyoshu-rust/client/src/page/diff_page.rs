@@ -17,7 +17,6 @@ use crate::components::button::Button;
 use crate::components::editor::editor::RenderEditor;
 use crate::singleton::confirm_box::confirm;
 use crate::singleton::top_navigation::{MenuItem, TOP_NAVIGATION};
-use crate::storage::account::ACCOUNT;
 use crate::storage::dictionary::{Dic, DIC};
 use crate::window::is_widescreen;
 
@@ -31,8 +30,6 @@ pub fn DiffPage(props: DiffPageProps) -> Element {
     let dic = DIC.read();
     let nav = use_navigator();
 
-    let token = ACCOUNT.snap().unwrap().token;
-
     let wid = props.wid;
     let wid2 = props.wid2;
 
@@ -47,17 +44,16 @@ pub fn DiffPage(props: DiffPageProps) -> Element {
         img: None,
         label: "提交左侧并删除右侧",
         onclick: EventHandler::new(move |_| {
-            let token = token.to_owned();
             spawn(async move {
                 if confirm(Vec::from([String::from("您确定要合并词汇？")])).await {
                     for i in 0..3 {
                         let result = UPDATE_MANY_API
-                            .call(&(
-                                token.to_owned(),
-                                HashMap::from([(wid.0.into(), word_define.read().to_owned())]),
-                            ))
+                            .call(&HashMap::from([(
+                                wid.0.into(),
+                                word_define.read().to_owned(),
+                            )]))
                             .await;
-                        if result.unwrap_or(false) {
+                        if result.is_ok() {
                             break;
                         }
                         if i == 2 {
@@ -66,9 +62,9 @@ pub fn DiffPage(props: DiffPageProps) -> Element {
                     }
                     for i in 0..3 {
                         let result = DELETE_WORD_API
-                            .call(&(token.to_owned(), (wid2.0.into())))
+                            .call(&(wid2.0, Some(format!("合并到 {wid}"))))
                             .await;
-                        if result.unwrap_or(false) {
+                        if result.is_ok() {
                             break;
                         }
                         if i == 2 {
@@ -17,7 +17,6 @@ use crate::router::AppRoute;
 use crate::singleton::confirm_box::confirm;
 use crate::singleton::top_navigation::MenuItem;
 use crate::singleton::top_navigation::TOP_NAVIGATION;
-use crate::storage::account::ACCOUNT;
 use crate::storage::dictionary::DIC;
 use crate::storage::use_storage::GlobalSignalStorage;
 use crate::text::TEXT;
@@ -41,9 +40,6 @@ pub struct Draft {
 pub fn EditorPage(props: EditorPageProps) -> Element {
     let nav = use_navigator();
 
-    let account_info = ACCOUNT.snap().unwrap();
-    let token = account_info.token;
-
     let dic = DIC.read();
     let wid = props.wid;
     let word_define = use_signal(|| dic.get(&wid).unwrap().to_owned());
@@ -62,35 +58,36 @@ pub fn EditorPage(props: EditorPageProps) -> Element {
         items.push(MenuItem {
             img: None,
             label: { TEXT.read().editor_page_action_submit },
-            onclick: {
-                let token = token.to_owned();
-                EventHandler::new(move |_| {
-                    *BUSYING.write() = true;
-                    let token = token.to_owned();
-                    spawn(async move {
-                        if confirm(Vec::from([String::from("是否确定要提交更改？")])).await
-                        {
-                            let word_define = word_define.peek().to_owned();
-                            let result = POST_WORD_API
-                                .call(&(
-                                    token.to_owned(),
-                                    WordEntry {
-                                        id: wid,
-                                        word_define,
-                                    },
-                                ))
-                                .await;
-                            if let Ok(true) = result {
+            onclick: EventHandler::new(move |_| {
+                *BUSYING.write() = true;
+                spawn(async move {
+                    if confirm(Vec::from([String::from("是否确定要提交更改？")])).await
+                    {
+                        let word_define = word_define.peek().to_owned();
+                        let result = POST_WORD_API
+                            .call(&WordEntry {
+                                id: wid,
+                                word_define,
+                            })
+                            .await;
+                        match result {
+                            Ok(()) => {
                                 nav.push(AppRoute::WordPage { wid });
-                            } else {
-                                error!("提交失败");
                             }
-                        };
+                            Err(err) => {
+                                error!("提交失败: {err}");
+                                confirm(Vec::from([
+                                    String::from("提交失败"),
+                                    TEXT.peek().error(&err),
+                                ]))
+                                .await;
+                            }
+                        }
+                    };
 
-                        *BUSYING.write() = false;
-                    });
-                })
-            },
+                    *BUSYING.write() = false;
+                });
+            }),
             disabled: post_disabled,
         });
         items.push(MenuItem {
@@ -98,19 +95,25 @@ pub fn EditorPage(props: EditorPageProps) -> Element {
             label: { TEXT.read().editor_page_action_submit_then_pass },
             onclick: EventHandler::new(move |_| {
                 *BUSYING.write() = true;
-                let token = token.to_owned();
                 spawn(async move {
                     if confirm(Vec::from([String::from("是否确定要提交更改并确认通过？")])).await
                     {
-                        let token = token.to_owned();
                         let word_define = word_define.peek().to_owned();
                         let result = UPDATE_MANY_API
-                            .call(&(token, HashMap::from([(wid, word_define)])))
+                            .call(&HashMap::from([(wid, word_define)]))
                             .await;
-                        if let Ok(true) = result {
-                            nav.push(AppRoute::WordPage { wid });
-                        } else {
-                            error!("提交失败");
+                        match result {
+                            Ok(()) => {
+                                nav.push(AppRoute::WordPage { wid });
+                            }
+                            Err(err) => {
+                                error!("提交失败: {err}");
+                                confirm(Vec::from([
+                                    String::from("提交失败"),
+                                    TEXT.peek().error(&err),
+                                ]))
+                                .await;
+                            }
                         }
                     };
 
@@ -36,9 +36,12 @@ pub fn HomePage() -> Element {
     }])]);
 
     let mut is_content_maintainer = false;
+    let mut is_admin = false;
+    let is_login = account_info.is_some();
 
     let login_info = if let Some(AccountInfo { user_info, token }) = account_info {
         is_content_maintainer = user_info.content_maintainer;
+        is_admin = user_info.admin;
 
         let last_sync = WorkBook::get_last_sync_time()
             .map(|date| {
@@ -124,6 +127,14 @@ pub fn HomePage() -> Element {
                     img { style: "margin-left: 4px", src: FORWARD_12_12 }
                 }
             }
+            if is_login {
+                div { style: "margin:16px",
+                    Link { to: AppRoute::MySubmissionsPage {},
+                        {TEXT.read().home_page_to_my_submissions_page},
+                        img { style: "margin-left: 4px", src: FORWARD_12_12 }
+                    }
+                }
+            }
             if is_content_maintainer {
                 div { style: "margin:16px",
                     Link { to: AppRoute::ManagementPage {},
@@ -132,6 +143,14 @@ pub fn HomePage() -> Element {
                     }
                 }
             }
+            if is_admin {
+                div { style: "margin:16px",
+                    Link { to: AppRoute::AdminPage {},
+                        {TEXT.read().home_page_to_admin_page},
+                        img { style: "margin-left: 4px", src: FORWARD_12_12 }
+                    }
+                }
+            }
 
             div { style: "margin:16px",
                 Link { to: AppRoute::SettingPage {},
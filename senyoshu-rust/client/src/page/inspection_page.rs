@@ -4,16 +4,18 @@ use dioxus_router::hooks::use_navigator;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error};
 
+use senyoshu_common::types::api::comment::CommentTarget;
 use senyoshu_common::types::api::dic::{GET_WORD_BY_PID_API, SET_ADOPTED_API};
 use senyoshu_common::types::integer::Integer;
 use senyoshu_common::types::state::State;
 
 use crate::components::button::Button;
+use crate::components::comment_thread::CommentThread;
 use crate::components::viewer::ViewerNode;
 use crate::global::BUSYING;
 use crate::singleton::top_navigation::{MenuItem, TOP_NAVIGATION};
-use crate::storage::account::ACCOUNT;
 use crate::storage::dictionary::{Dic, DIC};
+use crate::text::TEXT;
 use crate::window::is_widescreen;
 
 #[derive(Props, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -26,7 +28,7 @@ pub fn InspectionPage(props: InspectionPageProps) -> Element {
     let nav = use_navigator();
 
     let future =
-        use_resource(move || async move { GET_WORD_BY_PID_API.call(&props.pid.0).await.ok()? });
+        use_resource(move || async move { GET_WORD_BY_PID_API.call(&props.pid.0).await.ok() });
     let _ = use_coroutine({
         |_rx: UnboundedReceiver<()>| async {
             Dic::update().await;
@@ -34,22 +36,24 @@ pub fn InspectionPage(props: InspectionPageProps) -> Element {
     });
 
     let mut show_new = use_signal(|| true);
+    //通过或驳回时一起提交，作者可以在自己的提交记录中看到
+    let mut comment = use_signal(String::new);
 
     if let Some(new_word_entry) = future()? {
         let accept = move |_| {
             *BUSYING.write() = true;
             let pid = props.pid.0;
-            //没有登录的根本不会来到这个页面
-            let token = { ACCOUNT.snap().unwrap().token };
             spawn(async move {
-                let rv = SET_ADOPTED_API
-                    .call(&(token.to_owned(), pid, State::Pass))
-                    .await;
-                if let Ok(true) = rv {
-                    debug!("提交成功");
-                    nav.go_back();
-                } else {
-                    error!("提交失败");
+                let comment = Some(comment.peek().to_owned());
+                let rv = SET_ADOPTED_API.call(&(pid, State::Pass, comment)).await;
+                match rv {
+                    Ok(()) => {
+                        debug!("提交成功");
+                        nav.go_back();
+                    }
+                    Err(err) => {
+                        error!("提交失败: {err}");
+                    }
                 }
                 *BUSYING.write() = false;
             });
@@ -57,17 +61,17 @@ pub fn InspectionPage(props: InspectionPageProps) -> Element {
         let cancel = move |_| {
             *BUSYING.write() = true;
             let pid = props.pid.0;
-            //没有登录的根本不会来到这个页面
-            let token = { ACCOUNT.snap().unwrap().token };
             spawn(async move {
-                let rv = SET_ADOPTED_API
-                    .call(&(token.to_owned(), pid, State::Cancel))
-                    .await;
-                if let Ok(true) = rv {
-                    debug!("取消成功");
-                    nav.go_back();
-                } else {
-                    debug!("取消失败");
+                let comment = Some(comment.peek().to_owned());
+                let rv = SET_ADOPTED_API.call(&(pid, State::Cancel, comment)).await;
+                match rv {
+                    Ok(()) => {
+                        debug!("取消成功");
+                        nav.go_back();
+                    }
+                    Err(err) => {
+                        debug!("取消失败: {err}");
+                    }
                 }
                 *BUSYING.write() = false;
             });
@@ -136,6 +140,16 @@ pub fn InspectionPage(props: InspectionPageProps) -> Element {
         TOP_NAVIGATION.set_menu_items(items);
         rsx! {
             {viewer_node}
+            div { style: "margin:16px",
+                textarea {
+                    style: "width:100%",
+                    rows: 3,
+                    placeholder: TEXT.read().inspection_page_comment,
+                    value: "{comment}",
+                    oninput: move |evt| comment.set(evt.value())
+                }
+            }
+            CommentThread { target: CommentTarget::ChangeRequest(props.pid.0) }
         }
     } else {
         rsx! { "pid not found or access denied" }
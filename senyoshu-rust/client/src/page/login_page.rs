@@ -7,7 +7,8 @@ use dioxus::core_macro::rsx;
 use dioxus::prelude::*;
 use dioxus_router::hooks::use_navigator;
 
-use senyoshu_common::types::api::account::REGISTER_API;
+use senyoshu_common::types::api::account::{REGISTER_API, REQUEST_PASSWD_RESET_API};
+use senyoshu_common::types::error::Error;
 use senyoshu_common::util::passwd_hasher::{get_passwd_hash, is_legal_username};
 
 use crate::components::button::Button;
@@ -21,6 +22,18 @@ pub const MAIL_REGEX_STR: &str = ".+@.+\\..+";
 // .map(|it| !it.is_match())
 // .unwrap_or(true)
 
+/// 被限流时开始倒计时，结束前不能再次提交
+fn count_down(mut wait_secs: Signal<u64>, secs: u64) {
+    wait_secs.set(secs);
+    spawn(async move {
+        while *wait_secs.peek() > 0 {
+            sleep(Duration::from_secs(1)).await;
+            let left = wait_secs.peek().saturating_sub(1);
+            wait_secs.set(left);
+        }
+    });
+}
+
 pub fn LoginPage() -> Element {
     let nav = use_navigator();
     let mut forwarded = use_signal(|| false);
@@ -28,7 +41,8 @@ pub fn LoginPage() -> Element {
     let mut username = use_signal(|| String::new());
     let mut passwd = use_signal(|| String::new());
     let mut passwd2 = use_signal(|| String::new());
-    let mut note = use_signal(|| "");
+    let mut note = use_signal(|| String::new());
+    let wait_secs = use_signal(|| 0u64);
 
     if ACCOUNT.snap().is_some() {
         if *forwarded.read() == false {
@@ -54,18 +68,25 @@ pub fn LoginPage() -> Element {
         let is_legal_username = is_legal_username(username.peek().as_str());
         let is_passwd_empty = passwd.peek().is_empty();
         if is_legal_username == false {
-            note.set("note: username is error");
+            note.set("note: username is error".to_string());
             return;
         } else if is_passwd_empty {
-            note.set("note: password should not be empty");
+            note.set("note: password should not be empty".to_string());
             return;
         } else {
             *BUSYING.write() = true;
             let username = username.peek().to_string();
             let passwd = passwd.peek().to_string();
             spawn(async move {
-                if ACCOUNT.login(username, passwd).await == false {
-                    note.set("note: password may be is error .");
+                match ACCOUNT.login(username, passwd).await {
+                    Ok(()) => {}
+                    Err(Error::RateLimited { retry_after_secs }) => {
+                        note.set(String::new());
+                        count_down(wait_secs, retry_after_secs);
+                    }
+                    Err(err) => {
+                        note.set(format!("note: {}", TEXT.peek().error(&err)));
+                    }
                 }
                 *BUSYING.write() = false;
             });
@@ -79,17 +100,17 @@ pub fn LoginPage() -> Element {
         let two_passwd_match = String::eq(passwd.peek().deref(), passwd2.peek().deref());
 
         if is_legal_username == false {
-            note.set("note: username is error");
+            note.set("note: username is error".to_string());
             return;
         } else if is_passwd_empty {
-            note.set("note: password should not be empty");
+            note.set("note: password should not be empty".to_string());
             return;
         } else if is_register_mode == false {
             register_mode.set(true);
-            note.set("note: please repeat the password");
+            note.set("note: please repeat the password".to_string());
             return;
         } else if two_passwd_match == false {
-            note.set("note: tow password input should be same");
+            note.set("note: tow password input should be same".to_string());
             return;
         } else {
             *BUSYING.write() = true;
@@ -100,13 +121,23 @@ pub fn LoginPage() -> Element {
                     .call(&(username.to_string(), get_passwd_hash(&passwd)))
                     .await
                 {
-                    Ok(true) => {
-                        if ACCOUNT.login(username, passwd).await == false {
-                            note.set("error: register success but login failed");
+                    Ok(()) => {
+                        if let Err(err) = ACCOUNT.login(username, passwd).await {
+                            note.set(format!(
+                                "error: register success but login failed, {}",
+                                TEXT.peek().error(&err)
+                            ));
                         }
                     }
-                    _ => {
-                        note.set("note: failed , maybe the username has been registered");
+                    Err(Error::RateLimited { retry_after_secs }) => {
+                        note.set(String::new());
+                        count_down(wait_secs, retry_after_secs);
+                    }
+                    Err(Error::Conflict) => {
+                        note.set("note: failed , the username has been registered".to_string());
+                    }
+                    Err(err) => {
+                        note.set(format!("note: failed , {}", TEXT.peek().error(&err)));
                     }
                 }
                 *BUSYING.write() = false;
@@ -114,6 +145,27 @@ pub fn LoginPage() -> Element {
         }
     };
 
+    //用户名一栏也可以填写邮箱
+    let forgot_passwd_onclick = move |_| {
+        let name = username.peek().trim().to_string();
+        if name.is_empty() {
+            note.set("note: username is error".to_string());
+            return;
+        }
+        *BUSYING.write() = true;
+        spawn(async move {
+            match REQUEST_PASSWD_RESET_API.call(&name).await {
+                Ok(()) => note.set(TEXT.peek().login_page_reset_mail_sent.to_string()),
+                Err(Error::RateLimited { retry_after_secs }) => {
+                    note.set(String::new());
+                    count_down(wait_secs, retry_after_secs);
+                }
+                Err(err) => note.set(format!("note: failed , {}", TEXT.peek().error(&err))),
+            }
+            *BUSYING.write() = false;
+        });
+    };
+
     let passwd_repeat = if *register_mode.read() {
         rsx! {
             div {
@@ -133,6 +185,8 @@ pub fn LoginPage() -> Element {
         None
     };
 
+    let disabled = *BUSYING.read() || *wait_secs.read() > 0;
+
     rsx! {
         div {
             div {
@@ -161,11 +215,15 @@ pub fn LoginPage() -> Element {
             {passwd_repeat},
 
             div {
-                Button { disabled: *BUSYING.read(), onclick: login_onclick, {TEXT.read().login_page_sign_in} }
-                Button { disabled: *BUSYING.read(), onclick: register_onclick, {TEXT.read().login_page_register} }
+                Button { disabled: disabled, onclick: login_onclick, {TEXT.read().login_page_sign_in} }
+                Button { disabled: disabled, onclick: register_onclick, {TEXT.read().login_page_register} }
+                Button { disabled: disabled, onclick: forgot_passwd_onclick, {TEXT.read().login_page_forgot_passwd} }
             }
 
             div { {note} }
+            if *wait_secs.read() > 0 {
+                div { "{TEXT.read().login_page_wait} ({wait_secs}s)" }
+            }
         }
     }
 }
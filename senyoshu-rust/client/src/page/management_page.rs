@@ -12,7 +12,6 @@ use smallvec::SmallVec;
 use tracing::debug;
 
 use senyoshu_common::glossary::jo_yo_kan_ji::YO_MI_MAP;
-use senyoshu_common::types::api::account::Token;
 use senyoshu_common::types::api::dic::{CREATE_WORD_API, UPDATE_MANY_API};
 use senyoshu_common::types::word::parts_of_speech::{
     Compound, PartsOfSpeech, VerbClass, VerbConjugation,
@@ -31,7 +30,7 @@ use crate::storage::account::ACCOUNT;
 use crate::storage::dictionary::{Dic, DicModel, DIC};
 use crate::text::TEXT;
 
-async fn update_many_api(dic: &mut DicModel, token: Token) -> bool {
+async fn update_many_api(dic: &mut DicModel) -> bool {
     let mut maps_vec = Vec::new();
     for (idx, (wid, wd)) in dic.iter().enumerate() {
         if idx % 500 == 0 {
@@ -42,10 +41,7 @@ async fn update_many_api(dic: &mut DicModel, token: Token) -> bool {
     }
     let mut result = true;
     for maps in maps_vec.into_iter() {
-        let this_result = UPDATE_MANY_API
-            .call(&(token.to_owned(), maps.to_owned()))
-            .await
-            .unwrap_or(false);
+        let this_result = UPDATE_MANY_API.call(&maps).await.is_ok();
 
         if this_result {
             for key in maps.keys() {
@@ -71,7 +67,7 @@ pub fn ManagementPage() -> Element {
     let mut post_many = use_signal(|| Vec::<WordDefine>::new());
     let post_many_disabled = post_many.read().is_empty() || *BUSYING.read();
 
-    let account_info = ACCOUNT.snap()?;
+    ACCOUNT.snap()?;
 
     rsx! {
         div { style: "margin:16px",
@@ -86,6 +82,12 @@ pub fn ManagementPage() -> Element {
                 img { style: "margin-right: 4px", src: FORWARD_12_12 }
             }
         }
+        div { style: "margin:16px",
+            Link { to: AppRoute::ModerationLogPage {},
+                {TEXT.read().management_page_to_moderation_log_page},
+                img { style: "margin-right: 4px", src: FORWARD_12_12 }
+            }
+        }
         div { style: "margin:16px",
             Link { to: AppRoute::DeduplicatePage {},
                 {TEXT.read().management_page_to_deduplicate_page},
@@ -112,25 +114,21 @@ pub fn ManagementPage() -> Element {
         div {
             Button {
                 disabled: *BUSYING.read(),
-                onclick: {
-                    let token = account_info.token.to_owned();
-                    move |_| {
-                        *BUSYING.write() = true;
-                        let token = token.to_owned();
-                        spawn(async move {
-                            if Dic::update().await {
-                                let mut update_many_map = deal_words();
-                                if update_many_map.len() == 0 {
-                                    debug!("custom update nothing");
-                                } else if update_many_api(&mut update_many_map, token).await {
-                                    debug!("custom update success");
-                                } else {
-                                    debug!("custom update failed");
-                                };
-                            }
-                            *BUSYING.write() = false;
-                        });
-                    }
+                onclick: move |_| {
+                    *BUSYING.write() = true;
+                    spawn(async move {
+                        if Dic::update().await {
+                            let mut update_many_map = deal_words();
+                            if update_many_map.len() == 0 {
+                                debug!("custom update nothing");
+                            } else if update_many_api(&mut update_many_map).await {
+                                debug!("custom update success");
+                            } else {
+                                debug!("custom update failed");
+                            };
+                        }
+                        *BUSYING.write() = false;
+                    });
                 },
                 "自定义更新"
             }
@@ -138,22 +136,18 @@ pub fn ManagementPage() -> Element {
         div {
             Button {
                 disabled: update_many_disabled,
-                onclick: {
-                    let token = account_info.token.to_owned();
-                    move |_| {
-                        *BUSYING.write() = true;
-                        let token = token.to_owned();
-                        spawn(async move {
-                            let mut update_many_map = { update_many.read().to_owned() };
-                            if update_many_api(&mut update_many_map, token).await {
-                                debug!("update all success");
-                            } else {
-                                debug!("update some failed");
-                            };
-                            update_many.set(update_many_map);
-                            *BUSYING.write() = false;
-                        });
-                    }
+                onclick: move |_| {
+                    *BUSYING.write() = true;
+                    spawn(async move {
+                        let mut update_many_map = { update_many.read().to_owned() };
+                        if update_many_api(&mut update_many_map).await {
+                            debug!("update all success");
+                        } else {
+                            debug!("update some failed");
+                        };
+                        update_many.set(update_many_map);
+                        *BUSYING.write() = false;
+                    });
                 },
                 "批量更新"
             }
@@ -190,29 +184,24 @@ pub fn ManagementPage() -> Element {
         div {
             Button {
                 disabled: post_many_disabled,
-                onclick: {
-                    let token = account_info.token.to_owned();
-                    move |_| {
-                        *BUSYING.write() = true;
-                        let token = token.to_owned();
-                        spawn(async move {
-                            while let Some(word_define) = {
+                onclick: move |_| {
+                    *BUSYING.write() = true;
+                    spawn(async move {
+                        while let Some(word_define) = {
+                            let mut post_many_write = post_many.write();
+                            let wd = post_many_write.pop();
+                            drop(post_many_write);
+                            wd
+                        } {
+                            let result = CREATE_WORD_API.call(&word_define).await;
+                            if let Err(_) = result {
                                 let mut post_many_write = post_many.write();
-                                let wd = post_many_write.pop();
+                                post_many_write.push(word_define);
                                 drop(post_many_write);
-                                wd
-                            } {
-                                let param = (token.to_owned(), word_define.to_owned());
-                                let result = CREATE_WORD_API.call(&param).await;
-                                if let Ok(Some(wid)) = result {} else {
-                                    let mut post_many_write = post_many.write();
-                                    post_many_write.push(word_define);
-                                    drop(post_many_write);
-                                }
                             }
-                            *BUSYING.write() = false;
-                        });
-                    }
+                        }
+                        *BUSYING.write() = false;
+                    });
                 },
                 "批量上传"
             }
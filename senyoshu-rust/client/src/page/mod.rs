@@ -1,4 +1,5 @@
 pub(super) mod about_page;
+pub(super) mod admin_page;
 pub(super) mod check_word_page;
 pub(super) mod collection_page;
 pub(super) mod create_word_page;
@@ -14,6 +15,9 @@ pub(super) mod learn;
 pub(super) mod login_page;
 pub(super) mod maintain;
 pub(super) mod management_page;
+pub(super) mod moderation_log_page;
+pub(super) mod my_submissions_page;
+pub(super) mod reset_passwd_page;
 pub(super) mod setting_page;
 pub(super) mod voices_page;
 pub(super) mod word_page;
@@ -0,0 +1,125 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::Link;
+use tracing::error;
+
+use senyoshu_common::types::api::dic::{
+    GET_MODERATION_LOG_API, MAX_MODERATION_LOG_LIMIT, ModerationLogEntry, ModerationLogQuery,
+};
+use senyoshu_common::types::moderation::ModerationAction;
+
+use crate::components::button::Button;
+use crate::global::BUSYING;
+use crate::router::AppRoute;
+use crate::singleton::top_navigation::TOP_NAVIGATION;
+use crate::storage::account::ACCOUNT;
+use crate::text::TEXT;
+
+/// 最近的审核记录，每次加载一页，服务端只允许审核者查看
+pub fn ModerationLogPage() -> Element {
+    TOP_NAVIGATION.reset();
+
+    let mut logs = use_signal(Vec::<ModerationLogEntry>::new);
+    let mut action = use_signal(|| None::<ModerationAction>);
+    let mut has_more = use_signal(|| true);
+
+    let mut load = move |reset: bool| {
+        *BUSYING.write() = true;
+        let query = ModerationLogQuery {
+            before: if reset { None } else { logs.peek().last().map(|it| it.id) },
+            action: *action.peek(),
+            ..Default::default()
+        };
+        spawn(async move {
+            match GET_MODERATION_LOG_API.call(&query).await {
+                Ok(page) => {
+                    has_more.set(page.len() as u64 == MAX_MODERATION_LOG_LIMIT);
+                    if reset {
+                        logs.set(page);
+                    } else {
+                        logs.write().extend(page);
+                    }
+                }
+                Err(err) => error!("获取审核记录失败: {err}"),
+            }
+            *BUSYING.write() = false;
+        });
+    };
+    let _ = use_coroutine(move |_rx: UnboundedReceiver<()>| async move {
+        load(true);
+    });
+
+    if !ACCOUNT.snap()?.user_info.content_maintainer {
+        return None;
+    }
+
+    let filters = [
+        None,
+        Some(ModerationAction::Pass),
+        Some(ModerationAction::Cancel),
+        Some(ModerationAction::Withdraw),
+        Some(ModerationAction::UpdateMany),
+        Some(ModerationAction::Delete),
+    ]
+        .into_iter()
+        .map(|it| {
+            rsx! {
+                label {
+                    input {
+                        r#type: "radio",
+                        checked: *action.read() == it,
+                        onclick: move |_| {
+                            action.set(it);
+                            load(true);
+                        }
+                    }
+                    {action_text(it)}
+                }
+            }
+        });
+
+    let list = logs.read().to_owned().into_iter().map(|log| {
+        let time = log.create_time.format("%Y-%m-%d %H:%M").to_string();
+        let target = match (log.pid, log.wid) {
+            (Some(pid), _) => rsx! {
+                Link { to: AppRoute::InspectionPage { pid: pid.into() }, "#{pid}" }
+            },
+            (None, Some(wid)) => rsx! {
+                Link { to: AppRoute::WordPage { wid }, "{wid}" }
+            },
+            (None, None) => None,
+        };
+        rsx! {
+            div { key: "moderation:{log.id}", style: "display:flex;flex-direction:row;gap:8px",
+                span { "{time}" }
+                span { "{log.actor_name}" }
+                span { {action_text(Some(log.action))} }
+                span { {target} }
+                span { {log.comment.unwrap_or_default()} }
+            }
+        }
+    });
+
+    rsx! {
+        div { style: "margin:16px", {filters} }
+        div { style: "margin:16px", {list} }
+        div { style: "margin:16px",
+            Button {
+                disabled: *BUSYING.read() || !*has_more.read(),
+                onclick: move |_| load(false),
+                {TEXT.read().moderation_log_page_more}
+            }
+        }
+    }
+}
+
+fn action_text(action: Option<ModerationAction>) -> &'static str {
+    let text = TEXT.read();
+    match action {
+        None => text.moderation_log_page_all,
+        Some(ModerationAction::Pass) => text.moderation_log_page_pass,
+        Some(ModerationAction::Cancel) => text.moderation_log_page_cancel,
+        Some(ModerationAction::Withdraw) => text.moderation_log_page_withdraw,
+        Some(ModerationAction::UpdateMany) => text.moderation_log_page_update_many,
+        Some(ModerationAction::Delete) => text.moderation_log_page_delete,
+    }
+}
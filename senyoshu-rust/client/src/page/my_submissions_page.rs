@@ -0,0 +1,78 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::Link;
+use tracing::error;
+
+use senyoshu_common::types::api::dic::{GET_MY_SUBMISSIONS_API, SET_ADOPTED_API};
+use senyoshu_common::types::state::State;
+
+use crate::components::button::Button;
+use crate::components::word_node::WordNode;
+use crate::global::BUSYING;
+use crate::router::AppRoute;
+use crate::singleton::top_navigation::TOP_NAVIGATION;
+use crate::storage::account::ACCOUNT;
+use crate::text::TEXT;
+
+/// 自己提交的修改请求，以及审核的结果和意见
+pub fn MySubmissionsPage() -> Element {
+    TOP_NAVIGATION.reset();
+
+    let mut resource = use_resource(|| async move { GET_MY_SUBMISSIONS_API.call(&()).await.ok() });
+
+    ACCOUNT.snap()?;
+
+    let withdraw = move |pid: i64| {
+        *BUSYING.write() = true;
+        spawn(async move {
+            if let Err(err) = SET_ADOPTED_API.call(&(pid, State::Withdraw, None)).await {
+                error!("撤回失败: {err}");
+            }
+            resource.restart();
+            *BUSYING.write() = false;
+        });
+    };
+
+    let submissions = resource.value().read().to_owned().flatten().unwrap_or_default();
+    let list = submissions.into_iter().map(|post| {
+        let post_date = post.post_date.format("%Y-%m-%d %H:%M").to_string();
+        let review_date = post
+            .review_date
+            .map(|it| it.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let state = match post.state {
+            State::Pending => TEXT.read().my_submissions_page_pending,
+            State::Pass => TEXT.read().my_submissions_page_pass,
+            State::Cancel => TEXT.read().my_submissions_page_cancel,
+            State::Withdraw => TEXT.read().my_submissions_page_withdraw,
+        };
+        let comment = post.review_comment.map(|comment| {
+            let label = TEXT.read().my_submissions_page_comment;
+            rsx! { div { "{label}: {comment}" } }
+        });
+        let pid = post.pid;
+        rsx! {
+            div { key: "submission:{pid}", style: "margin:16px",
+                Link { to: AppRoute::InspectionPage { pid: pid.into() },
+                    WordNode { word: post.word }
+                }
+                div { style: "display:flex;flex-direction:row;gap:8px",
+                    span { "{post_date}" }
+                    span { "{state}" }
+                    span { "{review_date}" }
+                    if post.state == State::Pending {
+                        Button {
+                            disabled: *BUSYING.read(),
+                            onclick: move |_| withdraw(pid),
+                            {TEXT.read().my_submissions_page_withdraw_action}
+                        }
+                    }
+                }
+                {comment}
+            }
+        }
+    });
+
+    rsx! {
+        {list}
+    }
+}
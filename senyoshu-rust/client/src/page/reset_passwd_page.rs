@@ -0,0 +1,82 @@
+use std::ops::Deref;
+
+use dioxus::prelude::*;
+
+use senyoshu_common::types::api::account::RESET_PASSWD_API;
+use senyoshu_common::util::passwd_hasher::get_passwd_hash;
+
+use crate::components::button::Button;
+use crate::global::BUSYING;
+use crate::router::AppRoute;
+use crate::text::TEXT;
+
+/// 重置密码邮件中的链接打开这个页面
+#[component]
+pub fn ResetPasswdPage(token: String) -> Element {
+    let mut passwd = use_signal(|| String::new());
+    let mut passwd2 = use_signal(|| String::new());
+    let mut note = use_signal(|| String::new());
+    let mut success = use_signal(|| false);
+
+    if *success.read() {
+        return rsx! {
+            div { {TEXT.read().reset_passwd_page_success} }
+            Link { to: AppRoute::LoginPage {}, {TEXT.read().login_page_sign_in} }
+        };
+    }
+
+    let width = "width:120px;display:inline-block;text-align:right;";
+
+    let onclick = move |_| {
+        if passwd.peek().is_empty() {
+            note.set("note: password should not be empty".to_string());
+            return;
+        } else if String::eq(passwd.peek().deref(), passwd2.peek().deref()) == false {
+            note.set("note: tow password input should be same".to_string());
+            return;
+        }
+        *BUSYING.write() = true;
+        let token = token.to_owned();
+        let passwd_hash = get_passwd_hash(passwd.peek().as_str());
+        spawn(async move {
+            match RESET_PASSWD_API.call(&(token, passwd_hash)).await {
+                Ok(()) => success.set(true),
+                Err(err) => note.set(format!("note: failed , {}", TEXT.peek().error(&err))),
+            }
+            *BUSYING.write() = false;
+        });
+    };
+
+    rsx! {
+        div {
+            div {
+                span { style: width,
+                    {TEXT.read().reset_passwd_page_new_passwd},
+                    ":"
+                }
+                input {
+                    r#type: "password",
+                    onchange: move |evt| {
+                        passwd.set(evt.value().to_string());
+                    }
+                }
+            }
+            div {
+                span { style: width,
+                    {TEXT.read().login_page_password},
+                    ":"
+                }
+                input {
+                    r#type: "password",
+                    onchange: move |evt| {
+                        passwd2.set(evt.value().to_string());
+                    }
+                }
+            }
+            div {
+                Button { disabled: *BUSYING.read(), onclick: onclick, {TEXT.read().reset_passwd_page_submit} }
+            }
+            div { {note} }
+        }
+    }
+}
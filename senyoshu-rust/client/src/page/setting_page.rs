@@ -1,10 +1,27 @@
 use dioxus::prelude::*;
 
+use senyoshu_common::types::api::account::{
+    DELETE_ACCOUNT_API, EXPORT_ACCOUNT_API, GET_SESSIONS_API, REQUEST_EMAIL_CODE_API,
+    REVOKE_OTHER_SESSIONS_API, REVOKE_SESSION_API, Token, VERIFY_EMAIL_API,
+};
+use senyoshu_common::util::passwd_hasher::get_passwd_hash;
+
+use crate::file::download;
+use crate::storage::account::{AccountInfo, ACCOUNT};
 use crate::storage::setting::{Language, SETTING};
 use crate::text::TEXT;
 
 pub fn SettingPage() -> Element {
     let setting = SETTING.read();
+    let e_mail_setting = ACCOUNT.snap().map(|AccountInfo { user_info, token }| {
+        rsx! { EmailSetting { token, e_mail: user_info.e_mail } }
+    });
+    let session_list = ACCOUNT
+        .snap()
+        .map(|AccountInfo { token, .. }| rsx! { SessionList { token } });
+    let account_data = ACCOUNT
+        .snap()
+        .map(|AccountInfo { token, .. }| rsx! { AccountData { token } });
 
     rsx! {
         fieldset {
@@ -71,5 +88,205 @@ pub fn SettingPage() -> Element {
                 {TEXT.read().setting_page_menu_show_refresh_app}
             }
         }
+        {e_mail_setting}
+        {session_list}
+        {account_data}
+    }
+}
+
+/// 导出个人数据和删除账号
+#[component]
+fn AccountData(token: Token) -> Element {
+    let mut passwd = use_signal(|| String::new());
+    let mut note = use_signal(|| String::new());
+
+    let export = {
+        let token = token.to_owned();
+        move |_| {
+            let token = token.to_owned();
+            spawn(async move {
+                match EXPORT_ACCOUNT_API.call_with_token(&token, &()).await {
+                    Ok(export) => {
+                        let file_name = format!("senyoshu-{}.json", export.profile.username);
+                        download(serde_json::to_string(&export).unwrap(), file_name);
+                    }
+                    Err(err) => note.set(TEXT.peek().error(&err)),
+                }
+            });
+        }
+    };
+
+    let delete = move |_| {
+        let token = token.to_owned();
+        let passwd_hash = get_passwd_hash(passwd.peek().as_str());
+        spawn(async move {
+            match DELETE_ACCOUNT_API.call_with_token(&token, &passwd_hash).await {
+                Ok(()) => ACCOUNT.login_out(),
+                Err(err) => note.set(TEXT.peek().error(&err)),
+            }
+        });
+    };
+
+    rsx! {
+        fieldset {
+            legend { {TEXT.read().setting_page_account_data} }
+            div {
+                input {
+                    r#type: "button",
+                    value: TEXT.read().setting_page_export,
+                    onclick: export
+                }
+            }
+            div {
+                input {
+                    r#type: "password",
+                    placeholder: TEXT.read().setting_page_delete_account_passwd,
+                    onchange: move |evt| passwd.set(evt.value())
+                }
+                input {
+                    r#type: "button",
+                    disabled: passwd.read().is_empty(),
+                    value: TEXT.read().setting_page_delete_account,
+                    onclick: delete
+                }
+            }
+            div { {note} }
+        }
+    }
+}
+
+/// 发送验证码到新的邮箱，验证通过后绑定
+#[component]
+fn EmailSetting(token: Token, e_mail: Option<String>) -> Element {
+    let mut new_e_mail = use_signal(|| e_mail.to_owned().unwrap_or_default());
+    let mut code = use_signal(|| String::new());
+    let mut note = use_signal(|| String::new());
+
+    let send_code = {
+        let token = token.to_owned();
+        move |_| {
+            let token = token.to_owned();
+            let e_mail = new_e_mail.peek().trim().to_string();
+            spawn(async move {
+                match REQUEST_EMAIL_CODE_API.call_with_token(&token, &e_mail).await {
+                    Ok(()) => note.set(String::new()),
+                    Err(err) => note.set(TEXT.peek().error(&err)),
+                }
+            });
+        }
+    };
+
+    let verify = move |_| {
+        let token = token.to_owned();
+        let e_mail = new_e_mail.peek().trim().to_string();
+        let code_value = code.peek().trim().to_string();
+        spawn(async move {
+            match VERIFY_EMAIL_API.call_with_token(&token, &(e_mail, code_value)).await {
+                Ok(()) => {
+                    code.set(String::new());
+                    note.set(String::new());
+                    ACCOUNT.refresh().await;
+                }
+                Err(err) => note.set(TEXT.peek().error(&err)),
+            }
+        });
+    };
+
+    rsx! {
+        fieldset {
+            legend { {TEXT.read().setting_page_e_mail} }
+            if let Some(e_mail) = e_mail {
+                div { "{e_mail}" }
+            }
+            div {
+                input {
+                    r#type: "email",
+                    value: "{new_e_mail}",
+                    onchange: move |evt| new_e_mail.set(evt.value())
+                }
+                input {
+                    r#type: "button",
+                    value: TEXT.read().setting_page_e_mail_send_code,
+                    onclick: send_code
+                }
+            }
+            div {
+                input {
+                    placeholder: TEXT.read().setting_page_e_mail_code,
+                    value: "{code}",
+                    onchange: move |evt| code.set(evt.value())
+                }
+                input {
+                    r#type: "button",
+                    value: TEXT.read().setting_page_e_mail_verify,
+                    onclick: verify
+                }
+            }
+            div { {note} }
+        }
+    }
+}
+
+#[component]
+fn SessionList(token: Token) -> Element {
+    let mut resource = use_resource({
+        let token = token.to_owned();
+        move || {
+            let token = token.to_owned();
+            async move { GET_SESSIONS_API.call_with_token(&token, &()).await.ok() }
+        }
+    });
+
+    let sessions = resource.value().read().to_owned().flatten()?;
+    let list = sessions.into_iter().map(|session| {
+        let last_used = session
+            .last_used
+            .unwrap_or(session.login_time)
+            .format("%Y-%m-%d %H:%M")
+            .to_string();
+        rsx! {
+            div { key: "session:{session.id}",
+                span { "{session.device} {last_used} " }
+                if session.current {
+                    span { {TEXT.read().setting_page_sessions_current} }
+                } else {
+                    input {
+                        r#type: "button",
+                        value: TEXT.read().setting_page_sessions_revoke,
+                        onclick: {
+                            let token = token.to_owned();
+                            let id = session.id.to_owned();
+                            move |_| {
+                                let token = token.to_owned();
+                                let id = id.to_owned();
+                                spawn(async move {
+                                    let _ = REVOKE_SESSION_API.call_with_token(&token, &id).await;
+                                    resource.restart();
+                                });
+                            }
+                        }
+                    }
+                }
+                div { style: "font-size:0.75rem;color:gray", "{session.user_agent}" }
+            }
+        }
+    });
+
+    rsx! {
+        fieldset {
+            legend { {TEXT.read().setting_page_sessions} }
+            {list}
+            input {
+                r#type: "button",
+                value: TEXT.read().setting_page_sessions_revoke_others,
+                onclick: move |_| {
+                    let token = token.to_owned();
+                    spawn(async move {
+                        let _ = REVOKE_OTHER_SESSIONS_API.call_with_token(&token, &()).await;
+                        resource.restart();
+                    });
+                }
+            }
+        }
     }
 }
@@ -4,9 +4,11 @@ use dioxus_router::prelude::use_navigator;
 use serde::{Deserialize, Serialize};
 use tracing::debug;
 
+use senyoshu_common::types::api::comment::CommentTarget;
 use senyoshu_common::types::api::dic::DELETE_WORD_API;
 use senyoshu_common::types::word::wid::WordIdentity;
 
+use crate::components::comment_thread::CommentThread;
 use crate::components::viewer::ViewerNode;
 use crate::router::AppRoute;
 use crate::singleton::confirm_box::confirm;
@@ -30,7 +32,7 @@ pub fn WordPage(props: WordPageProps) -> Element {
     let account_info = ACCOUNT.snap();
     if let Some(word_define) = word_define_opt {
         let items = account_info
-            .map(|AccountInfo { token, user_info }| {
+            .map(|AccountInfo { user_info, .. }| {
                 let mut items = Vec::with_capacity(2);
                 items.push(Vec::from([MenuItem {
                     label: TEXT.read().word_page_action_edite,
@@ -41,7 +43,6 @@ pub fn WordPage(props: WordPageProps) -> Element {
                 }]));
                 if user_info.content_maintainer {
                     let delete_button_onclick = move |_| {
-                        let token = token.to_owned();
                         spawn(async move {
                             if confirm(Vec::from([
                                 String::from("您确定要删除该词汇？"),
@@ -49,10 +50,13 @@ pub fn WordPage(props: WordPageProps) -> Element {
                             ]))
                             .await
                             {
-                                if let Ok(true) = DELETE_WORD_API.call(&(token, *wid)).await {
-                                    nav.go_back();
-                                } else {
-                                    debug!("删除失败");
+                                match DELETE_WORD_API.call(&(*wid, None)).await {
+                                    Ok(()) => {
+                                        nav.go_back();
+                                    }
+                                    Err(err) => {
+                                        debug!("删除失败: {err}");
+                                    }
                                 }
                             }
                         });
@@ -71,6 +75,7 @@ pub fn WordPage(props: WordPageProps) -> Element {
 
         return rsx! {
             ViewerNode { word_define }
+            CommentThread { target: CommentTarget::Word(wid) }
         };
     }
 
@@ -8,6 +8,7 @@ use senyoshu_common::types::kanji_alias::Kanji;
 use senyoshu_common::types::word::wid::WordIdentity;
 
 use crate::page::about_page::AboutPage;
+use crate::page::admin_page::AdminPage;
 use crate::page::check_word_page::CheckWordPage;
 use crate::page::collection_page::CollectionPage;
 use crate::page::create_word_page::CreateWordPage;
@@ -24,6 +25,9 @@ use crate::page::login_page::LoginPage;
 use crate::page::maintain::deduplicate_page::DeduplicatePage;
 use crate::page::maintain::segment_page::SegmentPage;
 use crate::page::management_page::ManagementPage;
+use crate::page::moderation_log_page::ModerationLogPage;
+use crate::page::my_submissions_page::MySubmissionsPage;
+use crate::page::reset_passwd_page::ResetPasswdPage;
 use crate::page::setting_page::SettingPage;
 use crate::page::voices_page::VoicesPage;
 use crate::page::word_page::WordPage;
@@ -53,6 +57,8 @@ pub enum AppRoute {
     },
     #[route("/login")]
     LoginPage {},
+    #[route("/reset_passwd?:token")]
+    ResetPasswdPage { token: String },
     #[route("/kanji_list/:name")]
     KanjiListPage { name: String },
     #[route("/kanji?:kanji")]
@@ -79,6 +85,12 @@ pub enum AppRoute {
     SettingPage {},
     #[route("/management")]
     ManagementPage {},
+    #[route("/admin")]
+    AdminPage {},
+    #[route("/moderation_log")]
+    ModerationLogPage {},
+    #[route("/my_submissions")]
+    MySubmissionsPage {},
     #[route("/deduplicate")]
     DeduplicatePage {},
     #[route("/segment")]
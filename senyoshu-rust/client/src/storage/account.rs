@@ -1,12 +1,15 @@
 use std::ops::Deref;
 
+use dioxus::prelude::spawn;
 use serde::{Deserialize, Serialize};
 
 use senyoshu_common::types::api::account::{
-    Token, UserInfo, UserState, LOGIN_API, UPDATE_USER_STATE_API,
+    Token, UserInfo, UserState, LOGIN_API, REVOKE_SESSION_API, UPDATE_USER_STATE_API,
 };
+use senyoshu_common::types::error::Error;
 use senyoshu_common::util::passwd_hasher::get_passwd_hash;
 
+use crate::storage::push::connect_push;
 use crate::storage::use_storage::GlobalSignalStorage;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -21,6 +24,9 @@ pub static ACCOUNT: Account = Account(GlobalSignalStorage::local(ACCOUNT_LOCAL_S
 
 const ACCOUNT_LOCAL_STORAGE: &str = "account";
 
+/// 登录时提交的设备名称，显示在设置页的登录设备列表中
+const DEVICE: &str = if cfg!(feature = "android") { "Android" } else { "Web" };
+
 impl Account {
     pub fn peek(&self) -> Option<AccountInfo> {
         (*self.0.peek().deref()).to_owned()
@@ -30,40 +36,43 @@ impl Account {
         self.0.read().to_owned()
     }
 
-    pub async fn login(&'static self, username: String, passwd: String) -> bool {
-        let rv = async {
-            let token = LOGIN_API
-                .call(&(username, get_passwd_hash(&passwd)))
-                .await
-                .ok()??;
-            let user_state = UPDATE_USER_STATE_API.call(&token).await.ok()?;
-            match user_state {
-                UserState::TokenRevoked => {
-                    self.0.reset();
-                    None
-                }
-                UserState::Valid(user_info) => {
-                    let account_newest = AccountInfo { user_info, token };
-                    *self.0.write() = Some(account_newest);
-                    Some(())
-                }
-                UserState::Err => None,
+    pub async fn login(&'static self, username: String, passwd: String) -> Result<(), Error> {
+        let token = LOGIN_API
+            .call(&(username, get_passwd_hash(&passwd), DEVICE.to_string()))
+            .await?;
+        let user_state = UPDATE_USER_STATE_API.call_with_token(&token, &()).await?;
+        match user_state {
+            UserState::TokenRevoked => {
+                self.0.reset();
+                Err(Error::NotAuth)
+            }
+            UserState::Valid(user_info) => {
+                let account_newest = AccountInfo { user_info, token };
+                *self.0.write() = Some(account_newest);
+                connect_push();
+                Ok(())
             }
         }
-        .await
-        .is_some();
-        rv
     }
 
     pub fn login_out(&self) {
+        if let Some(AccountInfo { user_info, token }) = self.peek() {
+            //撤销失败时 session 也会在服务端过期
+            if let Some(session) = user_info.sessions.into_iter().find(|it| it.current) {
+                spawn(async move {
+                    let _ = REVOKE_SESSION_API.call_with_token(&token, &session.id).await;
+                });
+            }
+        }
         self.0.reset();
+        connect_push();
     }
 
     pub async fn refresh(&'static self) -> bool {
         async {
             let account_info_old = { self.0.peek().as_ref()?.to_owned() };
             let user_state = UPDATE_USER_STATE_API
-                .call(&account_info_old.token)
+                .call_with_token(&account_info_old.token, &())
                 .await
                 .ok()?;
 
@@ -83,7 +92,6 @@ impl Account {
 
                     Some(())
                 }
-                UserState::Err => None,
             }
         }
         .await
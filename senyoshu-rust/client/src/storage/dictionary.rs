@@ -1,15 +1,21 @@
+use std::cell::Cell;
 use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
-use chrono::Local;
+use async_std::task::sleep;
+use chrono::{DateTime, FixedOffset};
 use derive_more::Deref;
-use dioxus::prelude::{GlobalSignal, Readable, ReadableRef, Signal};
+use dioxus::prelude::{spawn, GlobalSignal, Readable, ReadableRef, Signal};
 use itertools::Itertools;
 use serde::Serialize;
 use smallvec::{smallvec, SmallVec};
 use tracing::{debug, error};
 
 use senyoshu_common::types::api::api::WordQuery;
-use senyoshu_common::types::api::dic::SYNC_DIC_API;
+use senyoshu_common::types::api::dic::{
+    DIC_SNAPSHOT_VERSION, get_dic_snapshot, get_dic_snapshot_manifest, SYNC_DIC_API,
+    SyncDicRequest,
+};
 use senyoshu_common::types::kanji_alias::Kanji;
 use senyoshu_common::types::kanji_detail::{KanjiReference, WordRef};
 use senyoshu_common::types::word::wid::WordIdentity;
@@ -57,6 +63,27 @@ pub struct CharIndex {
 }
 
 pub const DIC_LOCAL_STORAGE: &str = "dic";
+/// 推送的修改合并到内存后等待这么久再保存到本地，期间的修改一起保存
+const PERSIST_DELAY: Duration = Duration::from_secs(5);
+
+thread_local! {
+    /// 内存中有尚未保存到本地的修改
+    static PERSIST_PENDING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// 词条中每个字出现的次数，异体字统一为标准字
+fn char_counts(wd: &WordDefine) -> HashMap<char, usize> {
+    wd.word
+        .elements
+        .iter()
+        .map(|e| e.txt.chars())
+        .flatten()
+        .map(|c| (alias_to_standard(c), ()))
+        .into_group_map()
+        .into_iter()
+        .map(|(c, count)| (c, count.len()))
+        .collect()
+}
 
 impl Dic {
     pub fn get() -> DicModel {
@@ -87,17 +114,9 @@ impl Dic {
                 kana_map.insert(kana, smallvec![*wid]);
             }
 
-            for (c, count) in wd
-                .word
-                .elements
-                .iter()
-                .map(|e| e.txt.chars())
-                .flatten()
-                .map(|c| (alias_to_standard(c), ()))
-                .into_group_map()
-            {
+            for (c, count) in char_counts(wd) {
                 let ci = CharIndex {
-                    frequency: count.len(),
+                    frequency: count,
                     word_id: *wid,
                 };
                 if let Some(ci_vec) = char_map.get_mut(&c) {
@@ -120,31 +139,170 @@ impl Dic {
         Self::from(Self::get())
     }
 
-    //todo: 使用cache api
+    /// 没有同步过时先下载快照，失败时从头同步
+    async fn bootstrap() -> Option<(DicModel, DateTime<FixedOffset>)> {
+        let manifest = get_dic_snapshot_manifest().await.ok()??;
+        if manifest.version != DIC_SNAPSHOT_VERSION {
+            return None;
+        }
+        match get_dic_snapshot(&manifest).await {
+            Ok(dic) => Some((dic, manifest.watermark)),
+            Err(err) => {
+                error!("dic snapshot download fail: {err:?}");
+                None
+            }
+        }
+    }
+
+    /// 分页同步，每页都保存到本地，中断后下次从中断的位置继续
     pub async fn update() -> bool {
-        let last_update = { LAST_UPDATED.peek().dic.to_owned() };
-        let updated_words = SYNC_DIC_API.call(&last_update).await;
-        if let Ok(words) = updated_words {
-            if words.len() > 0 {
-                let mut dic = Self::get();
-                for (k, v) in words.into_iter() {
-                    if let Some(word_define) = v {
-                        dic.insert(k, word_define);
-                    } else {
-                        dic.remove(&k);
-                    }
+        let (mut since, cursor) = {
+            let last_updated = LAST_UPDATED.peek();
+            (last_updated.dic.to_owned(), last_updated.dic_cursor.to_owned())
+        };
+        //先保存推送的修改，下面从本地读取
+        Self::flush();
+        let mut dic = Self::get();
+        let mut changed = false;
+
+        if since.is_none() && cursor.is_none() {
+            if let Some((snapshot, watermark)) = Self::bootstrap().await {
+                dic = snapshot;
+                Self::set(&dic);
+                LAST_UPDATED.write().dic = Some(watermark);
+                since = Some(watermark);
+                changed = true;
+            }
+        }
+        let mut request = SyncDicRequest { since, cursor, limit: 0 };
+
+        let finished = loop {
+            let page = match SYNC_DIC_API.call_compressed(&request).await {
+                Ok(page) => page,
+                Err(err) => {
+                    error!("dic update fail: {err:?}");
+                    break false;
                 }
+            };
+            if !page.words.is_empty() {
+                Self::merge(&mut dic, page.words);
                 Self::set(&dic);
-                let dic_new = Dic::from(dic);
-                let mut dic_ref = DIC.0.write();
-                *dic_ref = dic_new;
+                changed = true;
             }
-            LAST_UPDATED.write().dic = Some(Local::now().into());
+
+            match page.next {
+                Some(next) => {
+                    LAST_UPDATED.write().dic_cursor = Some(next.to_owned());
+                    request.cursor = Some(next);
+                }
+                None => {
+                    let mut last_updated = LAST_UPDATED.write();
+                    last_updated.dic = Some(page.watermark);
+                    last_updated.dic_cursor = None;
+                    break true;
+                }
+            }
+        };
+
+        if changed {
+            let dic_new = Dic::from(dic);
+            let mut dic_ref = DIC.0.write();
+            *dic_ref = dic_new;
+        }
+        if finished {
             debug!("dic update finish");
-            true
-        } else {
-            error!("dic update fail");
-            false
+        }
+        finished
+    }
+
+    /// 合并推送的修改，只更新变化的词条的索引，稍后再保存到本地
+    pub fn apply(words: Vec<(WordIdentity, Option<WordDefine>)>) {
+        {
+            let mut dic = DIC.0.write();
+            for (wid, word_define) in words.into_iter() {
+                if let Some(old) = dic.dic.remove(&wid) {
+                    dic.remove_index(wid, &old);
+                }
+                if let Some(word_define) = word_define {
+                    dic.add_index(wid, &word_define);
+                    dic.dic.insert(wid, word_define);
+                }
+            }
+        }
+        Self::persist_later();
+    }
+
+    fn persist_later() {
+        if PERSIST_PENDING.replace(true) {
+            return;
+        }
+        spawn(async {
+            sleep(PERSIST_DELAY).await;
+            Self::flush();
+        });
+    }
+
+    /// 立即保存内存中尚未保存的修改
+    fn flush() {
+        if PERSIST_PENDING.replace(false) {
+            Self::set(&DIC.peek().dic);
+        }
+    }
+
+    fn add_index(&mut self, wid: WordIdentity, wd: &WordDefine) {
+        for (map, key) in [
+            (&mut self.txt_map, wd.word.get_txt()),
+            (&mut self.kana_map, wd.word.get_katakana()),
+        ] {
+            if let Some(small_vec) = map.get_mut(&key) {
+                small_vec.push(wid)
+            } else {
+                map.insert(key, smallvec![wid]);
+            }
+        }
+        for (c, count) in char_counts(wd) {
+            let ci = CharIndex {
+                frequency: count,
+                word_id: wid,
+            };
+            if let Some(ci_vec) = self.char_map.get_mut(&c) {
+                ci_vec.push(ci)
+            } else {
+                self.char_map.insert(c, Vec::from([ci]));
+            }
+        }
+    }
+
+    fn remove_index(&mut self, wid: WordIdentity, wd: &WordDefine) {
+        for (map, key) in [
+            (&mut self.txt_map, wd.word.get_txt()),
+            (&mut self.kana_map, wd.word.get_katakana()),
+        ] {
+            if let Some(small_vec) = map.get_mut(&key) {
+                small_vec.retain(|it| *it != wid);
+                if small_vec.is_empty() {
+                    map.remove(&key);
+                }
+            }
+        }
+        for c in char_counts(wd).into_keys() {
+            if let Some(ci_vec) = self.char_map.get_mut(&c) {
+                ci_vec.retain(|it| it.word_id != wid);
+                if ci_vec.is_empty() {
+                    self.char_map.remove(&c);
+                }
+            }
+        }
+    }
+
+    /// `None` 为已删除
+    fn merge(dic: &mut DicModel, words: Vec<(WordIdentity, Option<WordDefine>)>) {
+        for (k, v) in words.into_iter() {
+            if let Some(word_define) = v {
+                dic.insert(k, word_define);
+            } else {
+                dic.remove(&k);
+            }
         }
     }
 
@@ -2,6 +2,9 @@ use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error};
 
+use senyoshu_common::types::api::dic::SyncCursor;
+use senyoshu_common::types::api::learn::RecordCursor;
+
 use crate::storage::account::ACCOUNT;
 use crate::storage::dictionary::Dic;
 use crate::storage::use_storage::GlobalSignalStorage;
@@ -11,6 +14,7 @@ pub mod account;
 pub mod backend;
 pub mod dictionary;
 pub mod permanent_storage;
+pub mod push;
 pub mod setting;
 pub mod use_storage;
 pub mod voice_setting;
@@ -19,13 +23,22 @@ pub mod workbook;
 pub static LAST_UPDATED: GlobalSignalStorage<LastUpdated> =
     GlobalSignalStorage::local("last_updated", || LastUpdated {
         dic: None,
+        dic_cursor: None,
         workbook: None,
+        workbook_cursor: None,
     });
 
 #[derive(Default, Serialize, Deserialize)]
 pub struct LastUpdated {
+    /// 上次同步完成时服务端的高水位
     pub dic: Option<DateTime<FixedOffset>>,
+    /// 同步中断时下一页的位置，下次从这里继续
+    #[serde(default)]
+    pub dic_cursor: Option<SyncCursor>,
+    /// 与 `dic` 相同，是服务端的高水位
     pub workbook: Option<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub workbook_cursor: Option<RecordCursor>,
 }
 
 pub async fn update() {
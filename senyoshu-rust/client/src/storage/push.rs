@@ -0,0 +1,117 @@
+use std::cell::RefCell;
+use std::time::Duration;
+
+use async_std::task::sleep;
+use dioxus::prelude::spawn;
+use js_sys::wasm_bindgen::closure::Closure;
+use js_sys::wasm_bindgen::JsCast;
+use tracing::{debug, error};
+use web_sys::{CloseEvent, MessageEvent, WebSocket};
+
+use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::api::get_host;
+use senyoshu_common::types::api::push::{push_url, PUSH_CLOSE_NOT_AUTH, PushEvent};
+
+use crate::storage::account::ACCOUNT;
+use crate::storage::dictionary::Dic;
+use crate::storage::update;
+use crate::storage::workbook::WorkBook;
+use crate::task::TASK_DEQUE;
+
+/// 连接断开后等待这么久再重连
+const RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+thread_local! {
+    /// 当前的连接和连接时使用的 token
+    static SOCKET: RefCell<Option<(WebSocket, Token)>> = const { RefCell::new(None) };
+}
+
+/// 让推送的连接与当前登录的账号一致：未连接时连接，账号变化时重新连接，退出登录后断开
+pub fn connect_push() {
+    let token = ACCOUNT.peek().map(|it| it.token);
+    SOCKET.with_borrow_mut(|current| {
+        if let Some((socket, current_token)) = current.as_ref() {
+            let is_alive = matches!(socket.ready_state(), WebSocket::CONNECTING | WebSocket::OPEN);
+            if is_alive && Some(current_token) == token.as_ref() {
+                return;
+            }
+        }
+        if let Some((socket, _)) = current.take() {
+            socket.set_onclose(None);
+            let _ = socket.close();
+        }
+        if let Some(token) = token {
+            *current = open(&token).map(|socket| (socket, token));
+        }
+    });
+}
+
+fn open(token: &Token) -> Option<WebSocket> {
+    let socket = match WebSocket::new(push_url(get_host().as_str()).as_str()) {
+        Ok(socket) => socket,
+        Err(err) => {
+            error!("push connect fail: {err:?}");
+            reconnect_later();
+            return None;
+        }
+    };
+
+    let credential = token.credential();
+    let on_open: Closure<dyn Fn()> = Closure::new({
+        let socket = socket.to_owned();
+        move || {
+            let _ = socket.send_with_str(credential.as_str());
+        }
+    });
+    socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+    on_open.forget();
+
+    //js世界的回调不能访问 global_signal，通过任务队列处理
+    let on_message: Closure<dyn Fn(MessageEvent)> = Closure::new(|event: MessageEvent| {
+        let Some(text) = event.data().as_string() else {
+            return;
+        };
+        match serde_json::from_str::<PushEvent>(text.as_str()) {
+            Ok(event) => TASK_DEQUE.add(move || handle(event)),
+            Err(err) => error!("push event decode fail: {err}"),
+        }
+    });
+    socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    on_message.forget();
+
+    let on_close: Closure<dyn Fn(CloseEvent)> = Closure::new(|event: CloseEvent| {
+        if event.code() == PUSH_CLOSE_NOT_AUTH {
+            debug!("push closed: not auth");
+        } else {
+            debug!("push closed: {}", event.code());
+            reconnect_later();
+        }
+    });
+    socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+    on_close.forget();
+
+    Some(socket)
+}
+
+fn reconnect_later() {
+    wasm_bindgen_futures::spawn_local(async {
+        sleep(RECONNECT_DELAY).await;
+        TASK_DEQUE.add(connect_push);
+    });
+}
+
+fn handle(event: PushEvent) {
+    match event {
+        PushEvent::Learn(records) => {
+            debug!("push learn record: {}", records.len());
+            WorkBook::with_mut(|work_book| work_book.merge_remote(records));
+        }
+        PushEvent::Dic(words) => {
+            debug!("push dic: {}", words.len());
+            Dic::apply(words);
+        }
+        PushEvent::Resync => {
+            spawn(update());
+        }
+    }
+}
@@ -1,14 +1,14 @@
 use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
 
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Utc};
 use gloo::storage::{LocalStorage, Storage};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error};
 
 use senyoshu_common::types::api::account::Token;
-use senyoshu_common::types::api::learn::{GET_RECORD_API, POST_LEARN_RECORD_API};
+use senyoshu_common::types::api::learn::{GET_RECORD_API, GetRecordRequest, POST_LEARN_RECORD_API};
 use senyoshu_common::types::learn::knowledge::Knowledge;
 use senyoshu_common::types::learn::learn_knowledge_history::{
     LearnKnowledgeHistory, OperateRecord,
@@ -106,7 +106,8 @@ impl WorkBook {
         let work_book = Self::get();
         let count = work_book.to_be_push.len();
         if count > 0 {
-            const GROUP_LEN: usize = 500;
+            //服务端批量合并，每组的大小只受请求体大小的限制
+            const GROUP_LEN: usize = 1000;
             let mut groups: Vec<HashMap<Knowledge, LearnKnowledgeHistory>> =
                 Vec::with_capacity((work_book.to_be_push.len() / GROUP_LEN) + 1);
             groups.push(Default::default());
@@ -131,9 +132,9 @@ impl WorkBook {
                 }
 
                 if POST_LEARN_RECORD_API
-                    .call(&(token.to_owned(), LearnHistoryMap::new(group.to_owned())))
+                    .call_with_token(&token, &LearnHistoryMap::new(group.to_owned()))
                     .await
-                    .unwrap_or(false)
+                    .is_ok()
                 {
                     //todo:这里不是原子操作
                     WorkBook::with_mut(|work_book: &mut WorkBook| {
@@ -160,45 +161,73 @@ impl WorkBook {
         }
     }
 
+    /// 分页下载，每页都保存到本地，直到追上第一页时服务端确定的高水位
     async fn update(token: Token) -> bool {
-        let result = async {
-            let last_update = { LAST_UPDATED.peek().workbook.to_owned() };
-            let remote_history = GET_RECORD_API.call(&(token, last_update)).await.ok()??;
-            let remote_history_count = remote_history.len();
-            if remote_history_count > 0 {
-                WorkBook::with_mut(|work_book| {
-                    for (remote_know, remote_know_history) in remote_history.into_iter() {
-                        work_book.append_record(
-                            remote_know.to_owned(),
-                            remote_know_history.history.to_owned(),
-                        );
-                        let new_freeze_time = remote_know_history.freeze_time.to_owned();
-                        let mut local_know_history = work_book
-                            .history
-                            .entry(remote_know)
-                            .or_insert(remote_know_history);
-                        if let Some(new_freeze_time) = new_freeze_time {
-                            if let Some(old_freeze_time) = local_know_history.freeze_time {
-                                if new_freeze_time.timestamp_micros()
-                                    > old_freeze_time.timestamp_micros()
-                                {
-                                    local_know_history.freeze_time = Some(new_freeze_time);
-                                }
-                            } else {
-                                local_know_history.freeze_time = Some(new_freeze_time);
-                            }
-                        }
-                    }
-                });
+        let mut request = {
+            let last_updated = LAST_UPDATED.peek();
+            GetRecordRequest {
+                since: last_updated.workbook.to_owned(),
+                cursor: last_updated.workbook_cursor.to_owned(),
+                ..Default::default()
+            }
+        };
+        let mut remote_history_count = 0;
+
+        loop {
+            let page = match GET_RECORD_API.call_with_token(&token, &request).await {
+                Ok(page) => page,
+                Err(err) => {
+                    error!("update_learn_record fail: {err:?}");
+                    return false;
+                }
+            };
+            remote_history_count += page.records.len();
+            if !page.records.is_empty() {
+                WorkBook::with_mut(|work_book| work_book.merge_remote(page.records));
+            }
+
+            match page.next {
+                Some(next) => {
+                    LAST_UPDATED.write().workbook_cursor = Some(next.to_owned());
+                    request.cursor = Some(next);
+                }
+                None => {
+                    let mut last_updated = LAST_UPDATED.write();
+                    last_updated.workbook = Some(page.watermark);
+                    last_updated.workbook_cursor = None;
+                    break;
+                }
             }
-            let now = Local::now();
-            LAST_UPDATED.write().workbook = Some(now.into());
-            debug!("update_learn_record:{remote_history_count}");
-            Some(())
         }
-        .await
-        .is_some();
+        debug!("update_learn_record:{remote_history_count}");
+        true
+    }
 
-        result
+    /// 合并服务端的记录，服务端已经有的记录不需要再上传
+    pub fn merge_remote(&mut self, remote_history: Vec<(Knowledge, LearnKnowledgeHistory)>) {
+        for (remote_know, remote_know_history) in remote_history.into_iter() {
+            let is_pending = self.to_be_push.contains(&remote_know);
+            self.append_record(
+                remote_know.to_owned(),
+                remote_know_history.history.to_owned(),
+            );
+            if !is_pending {
+                self.to_be_push.remove(&remote_know);
+            }
+            let new_freeze_time = remote_know_history.freeze_time.to_owned();
+            let local_know_history = self
+                .history
+                .entry(remote_know)
+                .or_insert(remote_know_history);
+            if let Some(new_freeze_time) = new_freeze_time {
+                if let Some(old_freeze_time) = local_know_history.freeze_time {
+                    if new_freeze_time.timestamp_micros() > old_freeze_time.timestamp_micros() {
+                        local_know_history.freeze_time = Some(new_freeze_time);
+                    }
+                } else {
+                    local_know_history.freeze_time = Some(new_freeze_time);
+                }
+            }
+        }
     }
 }
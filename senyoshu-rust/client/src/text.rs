@@ -1,5 +1,7 @@
 use dioxus::prelude::{GlobalMemo, Signal};
 
+use senyoshu_common::types::error::Error;
+
 use crate::i18n::cn::CN_TEXT;
 use crate::i18n::en::EN_TEXT;
 use crate::storage::setting::{Language, SETTING};
@@ -33,6 +35,13 @@ pub struct Text {
     pub login_page_password: &'static str,
     pub login_page_register: &'static str,
     pub login_page_sign_in: &'static str,
+    pub login_page_wait: &'static str,
+    pub login_page_forgot_passwd: &'static str,
+    pub login_page_reset_mail_sent: &'static str,
+
+    pub reset_passwd_page_new_passwd: &'static str,
+    pub reset_passwd_page_submit: &'static str,
+    pub reset_passwd_page_success: &'static str,
 
     pub voice_page_action_try_listen: &'static str,
     pub voice_page_action_save_setting: &'static str,
@@ -75,6 +84,7 @@ pub struct Text {
     pub home_page_to_voices_page: &'static str,
     pub home_page_to_knowledge_page: &'static str,
     pub home_page_to_management_page: &'static str,
+    pub home_page_to_admin_page: &'static str,
     pub home_page_to_setting_page: &'static str,
     pub home_page_to_about_page: &'static str,
     pub home_page_sign_out: &'static str,
@@ -83,5 +93,87 @@ pub struct Text {
     pub management_page_download_dic: &'static str,
     pub management_page_to_deduplicate_page: &'static str,
 
+    pub admin_page_search: &'static str,
+    pub admin_page_post_permission: &'static str,
+    pub admin_page_content_maintainer: &'static str,
+    pub admin_page_admin: &'static str,
+    pub admin_page_vip: &'static str,
+    pub admin_page_vip_revoke: &'static str,
+    pub admin_page_restrict: &'static str,
+    pub admin_page_restrict_reason: &'static str,
+    pub admin_page_unrestrict: &'static str,
+    pub admin_page_surf_servers: &'static str,
+    pub admin_page_surf_enabled: &'static str,
+    pub admin_page_surf_rotate: &'static str,
+    pub admin_page_surf_add: &'static str,
+
+    pub management_page_to_moderation_log_page: &'static str,
+    pub moderation_log_page_all: &'static str,
+    pub moderation_log_page_pass: &'static str,
+    pub moderation_log_page_cancel: &'static str,
+    pub moderation_log_page_withdraw: &'static str,
+    pub moderation_log_page_update_many: &'static str,
+    pub moderation_log_page_delete: &'static str,
+    pub moderation_log_page_more: &'static str,
+
+    pub home_page_to_my_submissions_page: &'static str,
+    pub inspection_page_comment: &'static str,
+    pub my_submissions_page_pending: &'static str,
+    pub my_submissions_page_pass: &'static str,
+    pub my_submissions_page_cancel: &'static str,
+    pub my_submissions_page_withdraw: &'static str,
+    pub my_submissions_page_withdraw_action: &'static str,
+    pub my_submissions_page_comment: &'static str,
+    pub comment_thread_title: &'static str,
+    pub comment_thread_placeholder: &'static str,
+    pub comment_thread_submit: &'static str,
+    pub comment_thread_reply: &'static str,
+    pub comment_thread_edit: &'static str,
+    pub comment_thread_delete: &'static str,
+    pub comment_thread_cancel: &'static str,
+    pub comment_thread_deleted: &'static str,
+    pub comment_thread_edited: &'static str,
+    pub comment_thread_more: &'static str,
+
     pub setting_page_menu_show_refresh_app: &'static str,
+    pub setting_page_sessions: &'static str,
+    pub setting_page_sessions_current: &'static str,
+    pub setting_page_sessions_revoke: &'static str,
+    pub setting_page_sessions_revoke_others: &'static str,
+    pub setting_page_e_mail: &'static str,
+    pub setting_page_e_mail_send_code: &'static str,
+    pub setting_page_e_mail_code: &'static str,
+    pub setting_page_e_mail_verify: &'static str,
+    pub setting_page_account_data: &'static str,
+    pub setting_page_export: &'static str,
+    pub setting_page_delete_account: &'static str,
+    pub setting_page_delete_account_passwd: &'static str,
+
+    pub error_not_auth: &'static str,
+    pub error_permission_denied: &'static str,
+    pub error_validation_failed: &'static str,
+    pub error_conflict: &'static str,
+    pub error_not_found: &'static str,
+    pub error_rate_limited: &'static str,
+    pub error_database: &'static str,
+    pub error_mail_unavailable: &'static str,
+    pub error_network: &'static str,
+}
+
+impl Text {
+    pub fn error(&self, err: &Error) -> String {
+        match err {
+            Error::NotAuth => self.error_not_auth.to_string(),
+            Error::PermissionDenied => self.error_permission_denied.to_string(),
+            Error::ValidationFailed(reason) => format!("{} ({reason})", self.error_validation_failed),
+            Error::Conflict => self.error_conflict.to_string(),
+            Error::NotFound | Error::WordIsNotExist => self.error_not_found.to_string(),
+            Error::RateLimited { retry_after_secs } => {
+                format!("{} ({retry_after_secs}s)", self.error_rate_limited)
+            }
+            Error::DatabaseErr => self.error_database.to_string(),
+            Error::MailUnavailable => self.error_mail_unavailable.to_string(),
+            Error::Network(_) => self.error_network.to_string(),
+        }
+    }
 }
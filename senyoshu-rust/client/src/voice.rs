@@ -1,3 +1,5 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -9,31 +11,57 @@ use js_sys::Uint8Array;
 use tracing::debug;
 use web_sys::AudioBuffer;
 
+use senyoshu_common::types::api::sound::get_sound;
 use senyoshu_common::types::word::tones::Tone;
 
 use crate::storage::setting::Setting;
 use crate::storage::voice_setting::TextToSpeech;
 
-// pub async fn play_sound(sid: i64) -> Option<()> {
-//     //todo:包装一下,解决缓存
-//     // let sound_u8vec = get_sound_api(sid).await.ok()?;
-//     // play_sound_u8v(sound_u8vec).await;
-//     Some(())
-// }
+/// 缓存的音频数量上限，超过时清空
+const MAX_CACHED_SOUNDS: usize = 256;
 
-pub const PLAYING_SOUND: AtomicBool = AtomicBool::new(false);
+thread_local! {
+    /// 服务器上的音频，`None` 表示服务器上没有，避免重复请求
+    static SOUND_CACHE: RefCell<HashMap<(String, u8), Option<Vec<u8>>>> = RefCell::new(HashMap::new());
+}
+
+/// 获取服务器上审核通过的音频，网络错误时不缓存，下次重试
+async fn server_sound(kana: &str, tone: u8) -> Option<Vec<u8>> {
+    let key = (kana.to_string(), tone);
+    if let Some(cached) = SOUND_CACHE.with_borrow(|cache| cache.get(&key).cloned()) {
+        return cached;
+    }
 
-pub async fn play(kana: String, _tone: Option<Tone>) -> Option<()> {
-    //todo:调用服务器
+    let sound = get_sound(kana, tone).await.ok()?;
+    SOUND_CACHE.with_borrow_mut(|cache| {
+        if cache.len() >= MAX_CACHED_SOUNDS {
+            cache.clear();
+        }
+        cache.insert(key, sound.to_owned());
+    });
+    sound
+}
 
+pub const PLAYING_SOUND: AtomicBool = AtomicBool::new(false);
+
+pub async fn play(kana: String, tone: Option<Tone>) -> Option<()> {
     debug!("try to play: {kana}");
 
     let silent_mode = Setting::get().silent_mode;
     if silent_mode {
         sleep(Duration::from_millis(1000)).await;
-    } else {
-        TextToSpeech::speak(kana).await?;
+        return Some(());
+    }
+
+    //优先播放服务器上的音频，没有或者播放失败时使用语音合成
+    if let Some(tone) = tone.and_then(|tone| u8::try_from(tone.index()).ok()) {
+        if let Some(sound) = server_sound(kana.as_str(), tone).await {
+            if play_sound_u8v(sound).await.is_some() {
+                return Some(());
+            }
+        }
     }
+    TextToSpeech::speak(kana).await?;
 
     Some(())
 }
@@ -1,46 +1,87 @@
+use std::fmt::{Debug, Formatter};
+
 use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
 
-use crate::types::api::API;
-use crate::types::api::session::Session;
+use crate::types::api::{API, AuthAPI};
+use crate::types::api::comment::Comment;
+use crate::types::api::session::SessionInfo;
+use crate::types::learn::knowledge::Knowledge;
+use crate::types::learn::learn_knowledge_history::LearnKnowledgeHistory;
+use crate::types::state::State;
+use crate::types::word::wid::WordIdentity;
+use crate::types::word::word_entry::WordDefine;
+use crate::util::redact::Redacted;
 
 pub const GET_OTHER_USER_INFO_API: API<
     (
         /* uid */ Option<i64>,
         /* username */ Option<String>,
     ),
-    Option<OtherUserInfo>,
+    OtherUserInfo,
 > = API::new("get_other_user_info");
-pub const UPDATE_USER_STATE_API: API<Token, UserState> = API::new("get_user_info");
+pub const UPDATE_USER_STATE_API: AuthAPI<(), UserState> = AuthAPI::new("get_user_info");
 pub const LOGIN_API: API<
     (
         /* username */ String,
         /* new_passwd_hash */ String,
+        /* device */ String,
     ),
-    Option<Token>,
+    Token,
 > = API::new("login");
 pub const REGISTER_API: API<
     (
         /* username */ String,
         /* new_passwd_hash */ String,
     ),
-    bool,
+    (),
 > = API::new("register");
+/// 旧密码错误时返回 `NotAuth`，与登录共用失败锁定，修改成功后所有设备都需要重新登录
 pub const UPDATE_PASSWD_API: API<
     (
         /* username */ String,
         /* new_passwd_hash */ String,
         /* old_passwd_hash */ String,
     ),
-    bool,
+    (),
 > = API::new("update_passwd");
+pub const GET_SESSIONS_API: AuthAPI<(), Vec<SessionInfo>> = AuthAPI::new("get_sessions");
+/// 撤销自己的某个 session，撤销当前 session 相当于退出登录
+pub const REVOKE_SESSION_API: AuthAPI</* session id */ String, ()> =
+    AuthAPI::new("revoke_session");
+/// 撤销除当前 session 以外的所有 session
+pub const REVOKE_OTHER_SESSIONS_API: AuthAPI<(), ()> = AuthAPI::new("revoke_other_sessions");
+/// 向邮箱发送验证码，验证通过后绑定到当前账号
+pub const REQUEST_EMAIL_CODE_API: AuthAPI</* e_mail */ String, ()> =
+    AuthAPI::new("request_email_code");
+pub const VERIFY_EMAIL_API: AuthAPI<(/* e_mail */ String, /* code */ String), ()> =
+    AuthAPI::new("verify_email");
+/// 向已绑定的邮箱发送重置密码的 token，账号不存在或没有绑定邮箱时同样返回成功
+pub const REQUEST_PASSWD_RESET_API: API</* username or e_mail */ String, ()> =
+    API::new("request_passwd_reset");
+/// 使用邮件中的 token 重置密码，成功后所有 session 都会失效
+pub const RESET_PASSWD_API: API<
+    (
+        /* token */ String,
+        /* new_passwd_hash */ String,
+    ),
+    (),
+> = API::new("reset_passwd");
+/// 导出全部个人数据，请求头为 `Accept: application/cbor` 时响应体是 CBOR 编码的 [`AccountExport`]
+pub const EXPORT_ACCOUNT_API: AuthAPI<(), AccountExport> = AuthAPI::new("export_account");
+/// 删除账号和学习记录，提交过的词条修改会保留但不再关联到账号
+pub const DELETE_ACCOUNT_API: AuthAPI</* passwd_hash */ String, ()> = AuthAPI::new("delete_account");
+
+/// [`AccountExport`] 的格式版本，结构不兼容地变化时增加
+pub const ACCOUNT_EXPORT_VERSION: u32 = 1;
+/// 账号删除之后，提交过的词条修改、审核记录和评论的作者改为这个 uid
+pub const DELETED_AUTHOR: i64 = 0;
 
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub enum UserState {
     TokenRevoked,
     Valid(UserInfo),
-    Err,
 }
 
 
@@ -52,17 +93,46 @@ pub struct UserInfo {
     pub e_mail: Option<String>,
     pub post_permission: bool,
     pub restrict_user: bool,
+    /// 被限制时管理员填写的理由
+    #[serde(default)]
+    pub restrict_reason: Option<String>,
     pub content_maintainer: bool,
+    #[serde(default)]
+    pub admin: bool,
     pub vip: Option<DateTime<FixedOffset>>,
-    pub sessions: Vec<Session>,
+    pub sessions: Vec<SessionInfo>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Token {
     pub uid: i64,
     pub token: String,
 }
 
+impl Debug for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Token")
+            .field("uid", &self.uid)
+            .field("token", &Redacted)
+            .finish()
+    }
+}
+
+impl Token {
+    /// `Authorization: Bearer` 之后的部分，格式为 `<uid>.<token>`
+    pub fn credential(&self) -> String {
+        format!("{}.{}", self.uid, self.token)
+    }
+
+    pub fn from_credential(credential: &str) -> Option<Self> {
+        let (uid, token) = credential.split_once('.')?;
+        Some(Token {
+            uid: uid.parse().ok()?,
+            token: token.to_string(),
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
 pub struct OtherUserInfo {
     pub uid: i64,
@@ -73,3 +143,27 @@ pub struct OtherUserInfo {
     pub content_maintainer: bool,
     pub vip: Option<DateTime<FixedOffset>>,
 }
+
+/// 导出的个人数据
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AccountExport {
+    pub version: u32,
+    pub export_time: DateTime<FixedOffset>,
+    pub profile: UserInfo,
+    pub learn: Vec<(Knowledge, LearnKnowledgeHistory)>,
+    pub contributions: Vec<Contribution>,
+    /// 没有删除的评论，旧的导出中没有这一项
+    #[serde(default)]
+    pub comments: Vec<Comment>,
+}
+
+/// 用户提交过的一次词条修改
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Contribution {
+    pub pid: i64,
+    pub wid: WordIdentity,
+    pub post_date: DateTime<FixedOffset>,
+    pub update_date: DateTime<FixedOffset>,
+    pub state: State,
+    pub word_define: WordDefine,
+}
@@ -0,0 +1,113 @@
+use std::fmt::{Debug, Formatter};
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use crate::types::api::AuthAPI;
+use crate::util::redact::Redacted;
+
+/// 以下接口都只有管理员可以调用
+pub const SEARCH_USERS_API: AuthAPI<UserQuery, Vec<AdminUserInfo>> =
+    AuthAPI::new("admin_search_users");
+pub const SET_USER_ROLE_API: AuthAPI<
+    (
+        /* uid */ i64,
+        Role,
+        /* enable */ bool,
+    ),
+    AdminUserInfo,
+> = AuthAPI::new("admin_set_user_role");
+pub const SET_VIP_API: AuthAPI<(/* uid */ i64, VipChange), AdminUserInfo> =
+    AuthAPI::new("admin_set_vip");
+/// 理由为 `Some` 时限制该用户，为 `None` 时解除限制
+pub const SET_RESTRICT_API: AuthAPI<(/* uid */ i64, /* reason */ Option<String>), AdminUserInfo> =
+    AuthAPI::new("admin_set_restrict");
+/// 所有代理服务器，包括已停用的
+pub const GET_SURF_SERVER_CONFIGS_API: AuthAPI<(), Vec<SurfServerConfig>> =
+    AuthAPI::new("admin_get_surf_servers");
+/// `id` 为 `None` 时添加，否则修改，返回服务器的 id
+pub const SAVE_SURF_SERVER_API: AuthAPI<SurfServerConfig, /* id */ i64> =
+    AuthAPI::new("admin_save_surf_server");
+/// 作废某台服务器上所有用户的密码，用户下次获取服务器列表时生成新的，返回作废的数量
+pub const ROTATE_SURF_CREDENTIALS_API: AuthAPI</* id */ i64, u64> =
+    AuthAPI::new("admin_rotate_surf_credentials");
+/// 某台服务器上当前有效的密码，供代理服务器同步
+pub const GET_SURF_CREDENTIALS_API: AuthAPI</* id */ i64, Vec<SurfCredential>> =
+    AuthAPI::new("admin_get_surf_credentials");
+
+/// 搜索用户时每页的最大数量
+pub const MAX_USER_QUERY_LIMIT: u64 = 100;
+/// 限制理由的最大长度
+pub const MAX_RESTRICT_REASON_LEN: usize = 256;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct UserQuery {
+    /// 用户名包含该关键字，或者 uid 与之相等；为空时列出所有用户
+    pub keyword: String,
+    pub offset: u64,
+    pub limit: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    PostPermission,
+    ContentMaintainer,
+    Admin,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum VipChange {
+    /// 设置到期时间
+    Until(DateTime<FixedOffset>),
+    /// 从当前到期时间（已过期则从现在）起延长若干天
+    ExtendDays(u32),
+    Revoke,
+}
+
+/// 管理页面中显示的用户信息
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct AdminUserInfo {
+    pub uid: i64,
+    pub register_date: DateTime<FixedOffset>,
+    pub username: String,
+    pub e_mail: Option<String>,
+    pub post_permission: bool,
+    pub restrict_user: bool,
+    pub restrict_reason: Option<String>,
+    pub content_maintainer: bool,
+    pub admin: bool,
+    pub vip: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct SurfServerConfig {
+    pub id: Option<i64>,
+    pub name: String,
+    pub region: String,
+    pub server: String,
+    pub server_port: u16,
+    pub method: String,
+    /// 最多同时分配给多少个用户，0 表示不限制
+    pub capacity: u32,
+    pub enabled: bool,
+    /// 当前持有有效密码的用户数，保存时忽略
+    #[serde(default)]
+    pub active_users: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SurfCredential {
+    pub uid: i64,
+    pub password: String,
+    pub expire_time: DateTime<FixedOffset>,
+}
+
+impl Debug for SurfCredential {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SurfCredential")
+            .field("uid", &self.uid)
+            .field("password", &Redacted)
+            .field("expire_time", &self.expire_time)
+            .finish()
+    }
+}
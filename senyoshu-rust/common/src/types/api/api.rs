@@ -1,12 +1,15 @@
-use std::fmt::{Display, Formatter};
+use std::fmt::{Debug, Display, Formatter};
 use std::str::FromStr;
 
 use chrono::FixedOffset;
 use serde::{Deserialize, Serialize};
 use crate::types::api::account::{Token, UserState};
-use crate::types::api::API;
+use crate::types::api::AuthAPI;
+use crate::types::state::State;
 
+use crate::types::word::wid::WordIdentity;
 use crate::types::word::word::Word;
+use crate::util::redact::Redacted;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WordHistoryEntry {
@@ -22,9 +25,13 @@ pub struct PostList {
     pub pid: i64,
     pub post_date: chrono::DateTime<FixedOffset>,
     pub author: i64,
+    pub wid: WordIdentity,
     pub word: Word,
+    pub state: State,
+    /// 作者撤回和审核者直接修改的没有审核者
     pub reviewer: Option<i64>,
     pub review_date: Option<chrono::DateTime<FixedOffset>>,
+    pub review_comment: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
@@ -47,13 +54,34 @@ impl FromStr for WordQuery {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+/// 会员可以使用的代理服务器，每个用户在每台服务器上的密码不同
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct SurfServer {
     pub name:String,
     pub server: String,
     pub server_port: u16,
     pub password: String,
     pub method: String,
+    #[serde(default)]
+    pub region: String,
+    /// 密码的到期时间，与会员的到期时间相同
+    #[serde(default)]
+    pub expire_time: Option<chrono::DateTime<FixedOffset>>,
+}
+
+impl Debug for SurfServer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SurfServer")
+            .field("name", &self.name)
+            .field("server", &self.server)
+            .field("server_port", &self.server_port)
+            .field("password", &Redacted)
+            .field("method", &self.method)
+            .field("region", &self.region)
+            .field("expire_time", &self.expire_time)
+            .finish()
+    }
 }
 
-pub const GET_SURF_SERVERS_API: API<(), Vec<SurfServer>> = API::new("get_surf_servers");
\ No newline at end of file
+/// 需要登录且会员没有过期，否则返回 `Error::PermissionDenied`
+pub const GET_SURF_SERVERS_API: AuthAPI<(), Vec<SurfServer>> = AuthAPI::new("get_surf_servers");
\ No newline at end of file
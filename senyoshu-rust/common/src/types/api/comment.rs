@@ -0,0 +1,60 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use crate::types::api::{API, AuthAPI};
+use crate::types::word::wid::WordIdentity;
+
+/// 某个词条或修改请求下的评论，按发表时间排序分页，客户端根据 `parent` 组织成树
+pub const LIST_COMMENTS_API: API<CommentQuery, Vec<Comment>> = API::new("list_comments");
+/// 每页的最大数量，也是 `limit` 为 0 时的数量
+pub const MAX_COMMENT_LIMIT: u64 = 200;
+/// 需要提交修改的权限，被限制的用户不能发表和编辑
+pub const CREATE_COMMENT_API: AuthAPI<NewComment, Comment> = AuthAPI::new("create_comment");
+/// 只有作者可以编辑
+pub const EDIT_COMMENT_API: AuthAPI<(/* id */ i64, /* content */ String), Comment> =
+    AuthAPI::new("edit_comment");
+/// 作者或审核者可以删除，删除后只清空内容，有回复的评论仍保留在讨论中的位置
+pub const DELETE_COMMENT_API: AuthAPI</* id */ i64, ()> = AuthAPI::new("delete_comment");
+
+/// 评论内容的最大长度
+pub const MAX_COMMENT_LEN: usize = 2000;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CommentTarget {
+    Word(WordIdentity),
+    /// 修改请求的 pid
+    ChangeRequest(i64),
+}
+
+/// 回复总是比被回复的评论晚，按顺序翻页时被回复的评论总在之前的页中
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommentQuery {
+    pub target: CommentTarget,
+    /// 只返回 id 大于它的评论，翻页时传入上一页最后一条的 id
+    pub after: Option<i64>,
+    pub limit: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct NewComment {
+    pub target: CommentTarget,
+    /// 回复的评论，需要属于同一个 `target`
+    pub parent: Option<i64>,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Comment {
+    pub id: i64,
+    pub target: CommentTarget,
+    pub parent: Option<i64>,
+    pub author: i64,
+    /// 已注销的账号为空
+    pub author_name: String,
+    /// 已删除的评论为空
+    pub content: String,
+    pub deleted: bool,
+    pub create_time: DateTime<FixedOffset>,
+    /// 最后一次编辑的时间
+    pub edit_time: Option<DateTime<FixedOffset>>,
+}
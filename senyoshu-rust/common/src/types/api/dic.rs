@@ -1,32 +1,161 @@
 use std::collections::HashMap;
 
+use blake2::{Blake2b512, Digest};
 use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
 
-use crate::types::api::account::Token;
-use crate::types::api::API;
-use crate::types::api::api::WordHistoryEntry;
+use crate::types::api::{API, ApiResult, AuthAPI, get_host};
+use crate::types::api::api::{PostList, WordHistoryEntry};
+use crate::types::error::Error;
+use crate::types::moderation::ModerationAction;
 use crate::types::state::State;
 use crate::types::word::wid::WordIdentity;
 use crate::types::word::word_entry::{WordDefine, WordEntry};
+use crate::util::cbor_lz4;
 
-pub const CREATE_WORD_API: API<(Token, WordDefine), Option<WordIdentity>> = API::new("create_word");
-pub const DELETE_WORD_API: API<(Token, /* wid */ i64), bool> = API::new("delete_word");
-pub const SYNC_DIC_API: API<
-    Option<DateTime<FixedOffset>>,
-    HashMap<WordIdentity, Option<WordDefine>>,
-> = API::new("sync_dic");
+pub const CREATE_WORD_API: AuthAPI<WordDefine, WordIdentity> = AuthAPI::new("create_word");
+/// 删除的原因记录在审核日志中，长度限制同审核意见
+pub const DELETE_WORD_API: AuthAPI<(/* wid */ i64, /* comment */ Option<String>), ()> =
+    AuthAPI::new("delete_word");
+/// 按 `(update_date, wid)` 分页同步词典，可以通过 [`API::call_compressed`] 请求压缩的响应
+pub const SYNC_DIC_API: API<SyncDicRequest, SyncDicPage> = API::new("sync_dic");
+/// 每页的最大数量，也是 `limit` 为 0 时的数量
+pub const MAX_SYNC_DIC_LIMIT: u64 = 5000;
 
-pub const GET_CHANGE_REQUEST_API: API<Token, Vec<WordHistoryEntry>> =
-    API::new("get_change_request");
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct SyncDicRequest {
+    /// 上次同步完成时的 [`SyncDicPage::watermark`]，为空时全量同步，不返回已删除的词
+    pub since: Option<DateTime<FixedOffset>>,
+    /// 上一页返回的 [`SyncDicPage::next`]，第一页为空，中断后可以从这里继续
+    pub cursor: Option<SyncCursor>,
+    pub limit: u64,
+}
 
-pub const GET_WORD_BY_PID_API: API</* pid */ i64, Option<WordEntry>> = API::new("get_word_by_pid");
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SyncCursor {
+    pub update_date: DateTime<FixedOffset>,
+    pub wid: WordIdentity,
+    /// 第一页时服务端确定的高水位，之后的页都不会超过它
+    pub watermark: DateTime<FixedOffset>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SyncDicPage {
+    /// `None` 为已删除
+    pub words: Vec<(WordIdentity, Option<WordDefine>)>,
+    /// 没有下一页时为空
+    pub next: Option<SyncCursor>,
+    /// 所有页都同步完成后保存，作为下次同步的 `since`
+    pub watermark: DateTime<FixedOffset>,
+}
+
+/// 服务端定期生成的词典快照，新的客户端先下载快照，再用 [`SYNC_DIC_API`] 同步之后的修改
+///
+/// 快照的文件名包含内容的哈希，可以长期缓存，清单则每次都需要确认
+pub const DIC_SNAPSHOT_PATH: &str = "/snapshot";
+pub const DIC_SNAPSHOT_MANIFEST: &str = "dic-manifest.json";
+/// 快照格式变化时增加，客户端忽略不认识的版本
+pub const DIC_SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DicSnapshotManifest {
+    pub version: u32,
+    /// 快照的文件名，位于 [`DIC_SNAPSHOT_PATH`] 下
+    pub file: String,
+    /// 见 [`snapshot_hash`]
+    pub hash: String,
+    /// 快照包含这个时间之前的所有修改，作为之后同步的 `since`
+    pub watermark: DateTime<FixedOffset>,
+    pub words: u64,
+    pub size: u64,
+    pub create_time: DateTime<FixedOffset>,
+}
+
+/// 快照文件内容的 Blake2b，取前 16 字节
+pub fn snapshot_hash(bytes: &[u8]) -> String {
+    let mut hasher = Blake2b512::new();
+    hasher.update(bytes);
+    hex::encode(&hasher.finalize()[..16])
+}
+
+/// 服务端没有生成快照时返回 `None`
+pub async fn get_dic_snapshot_manifest() -> ApiResult<Option<DicSnapshotManifest>> {
+    get_dic_snapshot_manifest_with_host(get_host().as_str()).await
+}
+
+pub async fn get_dic_snapshot_manifest_with_host(host: &str) -> ApiResult<Option<DicSnapshotManifest>> {
+    let url = format!("{}{DIC_SNAPSHOT_PATH}/{DIC_SNAPSHOT_MANIFEST}", host.trim_end_matches("/"));
+    let response = reqwest::get(url).await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    Ok(Some(response.error_for_status()?.json().await?))
+}
+
+/// 下载并校验快照，内容与本地保存的词典格式相同
+pub async fn get_dic_snapshot(manifest: &DicSnapshotManifest) -> ApiResult<HashMap<WordIdentity, WordDefine>> {
+    get_dic_snapshot_with_host(get_host().as_str(), manifest).await
+}
+
+pub async fn get_dic_snapshot_with_host(
+    host: &str,
+    manifest: &DicSnapshotManifest,
+) -> ApiResult<HashMap<WordIdentity, WordDefine>> {
+    let url = format!("{}{DIC_SNAPSHOT_PATH}/{}", host.trim_end_matches("/"), manifest.file);
+    let bytes = reqwest::get(url).await?.error_for_status()?.bytes().await?;
+    if snapshot_hash(bytes.as_ref()) != manifest.hash {
+        return Err(Error::Network(String::from("dictionary snapshot hash mismatch")));
+    }
+    cbor_lz4::decode(bytes.as_ref()).map_err(Error::Network)
+}
+
+pub const GET_CHANGE_REQUEST_API: AuthAPI<(), Vec<WordHistoryEntry>> =
+    AuthAPI::new("get_change_request");
+
+pub const GET_WORD_BY_PID_API: API</* pid */ i64, WordEntry> = API::new("get_word_by_pid");
 
 pub const GET_WORD_HISTORY_API: API</* wid */ i64, Vec<WordHistoryEntry>> =
     API::new("get_word_history");
 
-pub const POST_WORD_API: API<(Token, WordEntry), bool> = API::new("post_word");
+pub const POST_WORD_API: AuthAPI<WordEntry, ()> = AuthAPI::new("post_word");
+
+/// 通过或驳回时可以附上审核意见，作者可以在 [`GET_MY_SUBMISSIONS_API`] 中看到
+pub const SET_ADOPTED_API: AuthAPI<(/* pid */ i64, State, /* comment */ Option<String>), ()> =
+    AuthAPI::new("set_adopted");
+/// 审核意见的最大长度
+pub const MAX_REVIEW_COMMENT_LEN: usize = 1000;
+
+/// 当前用户提交的修改请求，最新的在前
+pub const GET_MY_SUBMISSIONS_API: AuthAPI<(), Vec<PostList>> = AuthAPI::new("get_my_submissions");
+
+
+pub const UPDATE_MANY_API: AuthAPI<HashMap<WordIdentity, WordDefine>, ()> = AuthAPI::new("update_many");
 
-pub const SET_ADOPTED_API: API<(Token, /* pid */ i64, State), bool> = API::new("set_adopted");
+/// 只有审核者可以查看，按时间倒序
+pub const GET_MODERATION_LOG_API: AuthAPI<ModerationLogQuery, Vec<ModerationLogEntry>> =
+    AuthAPI::new("get_moderation_log");
+/// 每页的最大数量，也是 `limit` 为 0 时的数量
+pub const MAX_MODERATION_LOG_LIMIT: u64 = 200;
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct ModerationLogQuery {
+    /// 只返回 id 小于它的记录，翻页时传入上一页最后一条的 id
+    pub before: Option<i64>,
+    pub actor: Option<i64>,
+    pub action: Option<ModerationAction>,
+    pub wid: Option<WordIdentity>,
+    pub limit: u64,
+}
 
-pub const UPDATE_MANY_API: API<(Token, HashMap<WordIdentity, WordDefine>), bool> = API::new("update_many");
\ No newline at end of file
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ModerationLogEntry {
+    pub id: i64,
+    pub actor: i64,
+    pub actor_name: String,
+    pub action: ModerationAction,
+    /// 修改请求的 pid，删除词条时没有
+    pub pid: Option<i64>,
+    pub wid: Option<WordIdentity>,
+    pub comment: Option<String>,
+    pub create_time: DateTime<FixedOffset>,
+}
\ No newline at end of file
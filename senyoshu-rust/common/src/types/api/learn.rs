@@ -2,17 +2,45 @@ use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
 
 use crate::types::api::account::Token;
-use crate::types::api::API;
-use crate::types::learn::knowledge::Knowledge;
+use crate::types::api::AuthAPI;
+use crate::types::learn::knowledge::{Knowledge, KnowledgeType};
 use crate::types::learn::learn_knowledge_history::LearnKnowledgeHistory;
 use crate::types::learn::LearnHistoryMap;
 
-pub const POST_LEARN_RECORD_API: API<(Token, LearnHistoryMap), bool> =
-    API::new("post_learn_record");
-pub const GET_RECORD_API: API<
-    (Token, Option<DateTime<FixedOffset>>),
-    Option<Vec<(Knowledge, LearnKnowledgeHistory)>>,
-> = API::new("get_record");
+pub const POST_LEARN_RECORD_API: AuthAPI<LearnHistoryMap, ()> =
+    AuthAPI::new("post_learn_record");
+pub const GET_RECORD_API: AuthAPI<GetRecordRequest, GetRecordPage> = AuthAPI::new("get_record");
+/// 每页的最大数量，也是 `limit` 为 0 时的数量
+pub const MAX_GET_RECORD_LIMIT: u64 = 5000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct GetRecordRequest {
+    /// 上次同步完成时的 [`GetRecordPage::watermark`]，为空时下载全部记录
+    pub since: Option<DateTime<FixedOffset>>,
+    /// 上一页返回的 [`GetRecordPage::next`]，第一页为空，中断后可以从这里继续
+    pub cursor: Option<RecordCursor>,
+    /// 只下载这些类型的记录，为空时不过滤
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub knowledge_types: Vec<KnowledgeType>,
+    pub limit: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RecordCursor {
+    pub update_time: DateTime<FixedOffset>,
+    pub knowledge: Knowledge,
+    /// 第一页时服务端确定的高水位，之后的页都不会超过它
+    pub watermark: DateTime<FixedOffset>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GetRecordPage {
+    pub records: Vec<(Knowledge, LearnKnowledgeHistory)>,
+    /// 没有下一页时为空
+    pub next: Option<RecordCursor>,
+    /// 所有页都下载完成后保存，作为下次的 `since`
+    pub watermark: DateTime<FixedOffset>,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct PostLearnRecordApi {
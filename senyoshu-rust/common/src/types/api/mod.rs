@@ -1,13 +1,27 @@
 use std::marker::PhantomData;
+use std::sync::OnceLock;
 
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
+use crate::types::api::account::Token;
+use crate::types::error::Error;
+use crate::util::cbor_lz4;
+
 pub mod account;
+pub mod admin;
 pub mod api;
+pub mod comment;
 pub mod dic;
 pub mod learn;
+pub mod push;
 pub mod session;
+pub mod sound;
+
+pub type ApiResult<RES> = Result<RES, Error>;
+
+/// 数据量较大的响应可以通过 `Accept` 请求这种格式，见 [`crate::util::cbor_lz4`]
+pub const CBOR_LZ4: &str = "application/x-cbor-lz4";
 
 pub struct API<REQ: Serialize + DeserializeOwned, RES: Serialize + DeserializeOwned> {
     name: &'static str,
@@ -28,22 +42,119 @@ impl<REQ: Serialize + DeserializeOwned, RES: Serialize + DeserializeOwned> API<R
         }
     }
 
-    pub async fn call(&self, body: &REQ) -> Result<RES, reqwest::Error> {
-        let client = reqwest::Client::new();
-        let host = get_host();
-        let host = host.trim_end_matches("/");
-        let url = format!("{host}/api/{}", self.name);
+    /// 服务端的错误通过响应体中的 `Err` 返回，传输层的错误则转换为 [`Error::Network`]
+    pub async fn call(&self, body: &REQ) -> ApiResult<RES> {
+        self.call_with_host(get_host().as_str(), body).await
+    }
+
+    /// 与 [`API::call`] 相同，但请求指定的服务器，例如测试中启动的本地服务
+    pub async fn call_with_host(&self, host: &str, body: &REQ) -> ApiResult<RES> {
+        send(self.name, host, None, body).await
+    }
 
-        client
-            .post(url)
-            .json(body)
-            .send()
-            .await?
-            .json::<RES>()
-            .await
+    /// 与 [`API::call`] 相同，但响应使用 [`CBOR_LZ4`] 编码，只有部分 API 支持
+    pub async fn call_compressed(&self, body: &REQ) -> ApiResult<RES> {
+        self.call_compressed_with_host(get_host().as_str(), body).await
+    }
+
+    pub async fn call_compressed_with_host(&self, host: &str, body: &REQ) -> ApiResult<RES> {
+        send_compressed(self.name, host, body).await
     }
 }
 
+/// 需要登录的 API，token 通过 `Authorization: Bearer <uid>.<token>` 请求头传递
+pub struct AuthAPI<REQ: Serialize + DeserializeOwned, RES: Serialize + DeserializeOwned> {
+    name: &'static str,
+    request: PhantomData<REQ>,
+    response: PhantomData<RES>,
+}
+
+impl<REQ: Serialize + DeserializeOwned, RES: Serialize + DeserializeOwned> AuthAPI<REQ, RES> {
+    pub fn path(&self) -> String {
+        format!("/api/{}", self.name)
+    }
+
+    const fn new(path: &'static str) -> AuthAPI<REQ, RES> {
+        AuthAPI {
+            name: path,
+            request: PhantomData,
+            response: PhantomData,
+        }
+    }
+
+    /// 使用 [`set_token_provider`] 提供的 token，未登录时直接返回 [`Error::NotAuth`]
+    pub async fn call(&self, body: &REQ) -> ApiResult<RES> {
+        let token = TOKEN_PROVIDER
+            .get()
+            .and_then(|provider| provider())
+            .ok_or(Error::NotAuth)?;
+        self.call_with_token(&token, body).await
+    }
+
+    pub async fn call_with_token(&self, token: &Token, body: &REQ) -> ApiResult<RES> {
+        self.call_with_host(get_host().as_str(), token, body).await
+    }
+
+    pub async fn call_with_host(&self, host: &str, token: &Token, body: &REQ) -> ApiResult<RES> {
+        send(self.name, host, Some(token), body).await
+    }
+}
+
+static TOKEN_PROVIDER: OnceLock<fn() -> Option<Token>> = OnceLock::new();
+
+/// 设置 [`AuthAPI::call`] 获取 token 的方式，客户端启动时从保存的账号中读取
+pub fn set_token_provider(provider: fn() -> Option<Token>) {
+    let _ = TOKEN_PROVIDER.set(provider);
+}
+
+async fn send<REQ: Serialize, RES: DeserializeOwned>(
+    name: &str,
+    host: &str,
+    token: Option<&Token>,
+    body: &REQ,
+) -> ApiResult<RES> {
+    let client = reqwest::Client::new();
+    let host = host.trim_end_matches("/");
+    let url = format!("{host}/api/{name}");
+
+    let mut request = client.post(url).json(body);
+    if let Some(token) = token {
+        request = request.bearer_auth(token.credential());
+    }
+    request
+        .send()
+        .await?
+        .json::<ApiResult<RES>>()
+        .await?
+}
+
+/// 服务端出错时仍然返回 json
+async fn send_compressed<REQ: Serialize, RES: DeserializeOwned>(
+    name: &str,
+    host: &str,
+    body: &REQ,
+) -> ApiResult<RES> {
+    let client = reqwest::Client::new();
+    let host = host.trim_end_matches("/");
+    let url = format!("{host}/api/{name}");
+
+    let response = client
+        .post(url)
+        .header(reqwest::header::ACCEPT, CBOR_LZ4)
+        .json(body)
+        .send()
+        .await?;
+    let is_compressed = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes() == CBOR_LZ4.as_bytes());
+    if is_compressed {
+        let bytes = response.bytes().await?;
+        cbor_lz4::decode::<RES>(bytes.as_ref()).map_err(Error::Network)
+    } else {
+        response.json::<ApiResult<RES>>().await?
+    }
+}
 
 pub fn get_host() -> String {
     if cfg!(target_family = "wasm") && cfg!(feature = "android") == false {
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::learn::knowledge::Knowledge;
+use crate::types::learn::learn_knowledge_history::LearnKnowledgeHistory;
+use crate::types::word::wid::WordIdentity;
+use crate::types::word::word_entry::WordDefine;
+
+/// 推送通知的 WebSocket，连接后客户端先发送 [`crate::types::api::account::Token::credential`]
+///
+/// 服务端之后发送 json 编码的 [`PushEvent`]，第一条总是 [`PushEvent::Resync`]，
+/// 鉴权失败或 token 失效时以 [`PUSH_CLOSE_NOT_AUTH`] 关闭连接
+pub const PUSH_PATH: &str = "/api/push";
+/// 客户端收到这个关闭码时不再重连
+pub const PUSH_CLOSE_NOT_AUTH: u16 = 4401;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum PushEvent {
+    /// 同一个用户在其他设备上传的记录，已经与服务端保存的记录合并
+    Learn(Vec<(Knowledge, LearnKnowledgeHistory)>),
+    /// 词典中被修改的词，`None` 为已删除
+    Dic(Vec<(WordIdentity, Option<WordDefine>)>),
+    /// 连接成功或推送有遗漏，客户端需要重新同步
+    Resync,
+}
+
+/// 由 API 的地址得到 WebSocket 的地址，`http` 对应 `ws`，`https` 对应 `wss`
+pub fn push_url(host: &str) -> String {
+    let host = host.trim_end_matches('/');
+    let host = match host.split_once("://") {
+        Some(("https", rest)) => format!("wss://{rest}"),
+        Some((_, rest)) => format!("ws://{rest}"),
+        None => format!("ws://{host}"),
+    };
+    format!("{host}{PUSH_PATH}")
+}
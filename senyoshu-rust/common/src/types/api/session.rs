@@ -1,15 +1,67 @@
 use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter};
 
+use blake2::{Blake2b512, Digest};
 use chrono::FixedOffset;
 use sea_orm::FromJsonQueryResult;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+use crate::util::redact::Redacted;
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Session {
     pub token: String,
     pub login_time: chrono::DateTime<FixedOffset>,
     pub active: bool,
+    /// 客户端提供的设备名称
+    #[serde(default)]
+    pub device: String,
+    #[serde(default)]
+    pub user_agent: String,
+    #[serde(default)]
+    pub last_used: Option<chrono::DateTime<FixedOffset>>,
+    /// 旧的 session 没有这个字段，由服务端按 `login_time` 计算
+    #[serde(default)]
+    pub expire_time: Option<chrono::DateTime<FixedOffset>>,
+}
+
+impl Debug for Session {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Session")
+            .field("token", &Redacted)
+            .field("login_time", &self.login_time)
+            .field("active", &self.active)
+            .field("device", &self.device)
+            .field("user_agent", &self.user_agent)
+            .field("last_used", &self.last_used)
+            .field("expire_time", &self.expire_time)
+            .finish()
+    }
+}
+
+impl Session {
+    /// 用于展示和撤销 session，不会泄露 token
+    pub fn id(&self) -> String {
+        let mut hasher = Blake2b512::new();
+        hasher.update(b"senyoshu-session-id-");
+        hasher.update(self.token.as_bytes());
+        let result = hasher.finalize();
+        hex::encode(&result[..8])
+    }
 }
 
 #[derive(FromJsonQueryResult, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct SessionVec(pub VecDeque<Session>);
+
+/// 返回给客户端的 session，不包含 token
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub id: String,
+    pub device: String,
+    pub user_agent: String,
+    pub login_time: chrono::DateTime<FixedOffset>,
+    pub last_used: Option<chrono::DateTime<FixedOffset>>,
+    pub expire_time: chrono::DateTime<FixedOffset>,
+    /// 是否是发出请求的 session
+    pub current: bool,
+}
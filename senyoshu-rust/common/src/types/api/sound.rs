@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::api::{ApiResult, AuthAPI, get_host};
+use crate::types::sound::SoundMeta;
+
+/// 以下接口都只有审核者可以调用
+///
+/// 上传会覆盖相同读音、声调和说话人的音频，覆盖后需要重新审核
+pub const UPLOAD_SOUND_API: AuthAPI<SoundUpload, SoundMeta> = AuthAPI::new("upload_sound");
+/// 作者不能审核自己上传的音频
+pub const REVIEW_SOUND_API: AuthAPI<(SoundKey, /* pass */ bool), SoundMeta> =
+    AuthAPI::new("review_sound");
+pub const SEARCH_SOUNDS_API: AuthAPI<SoundQuery, Vec<SoundMeta>> = AuthAPI::new("search_sounds");
+
+/// 音频通过 GET 请求获取，不经过 json，路径见 [`SoundKey::path`]
+pub const SOUND_PATH: &str = "/api/sound";
+/// 单个音频的最大字节数
+pub const MAX_SOUND_SIZE: usize = 256 * 1024;
+/// 搜索音频时每页的最大数量
+pub const MAX_SOUND_QUERY_LIMIT: u64 = 100;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SoundKey {
+    pub katakana: String,
+    pub tone: u8,
+    pub role: String,
+}
+
+impl SoundKey {
+    /// 审核通过之前只有审核者可以获取
+    pub fn path(&self) -> String {
+        format!("{}/{}", sound_path(self.katakana.as_str(), self.tone), encode(self.role.as_str()))
+    }
+}
+
+/// 不指定说话人时返回任意一个审核通过的音频
+pub fn sound_path(katakana: &str, tone: u8) -> String {
+    format!("{SOUND_PATH}/{}/{tone}", encode(katakana))
+}
+
+fn encode(segment: &str) -> String {
+    form_urlencoded::byte_serialize(segment.as_bytes()).collect()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SoundUpload {
+    pub key: SoundKey,
+    /// 例如 `audio/mpeg`
+    pub content_type: String,
+    pub sound: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct SoundQuery {
+    /// 为空时不按读音过滤
+    pub katakana: Option<String>,
+    pub unchecked_only: bool,
+    pub offset: u64,
+    pub limit: u64,
+}
+
+/// 获取审核通过的音频，没有时返回 `None`
+pub async fn get_sound(katakana: &str, tone: u8) -> ApiResult<Option<Vec<u8>>> {
+    get_sound_with_host(get_host().as_str(), sound_path(katakana, tone).as_str()).await
+}
+
+/// 与 [`get_sound`] 相同，但请求指定的服务器和路径
+pub async fn get_sound_with_host(host: &str, path: &str) -> ApiResult<Option<Vec<u8>>> {
+    let url = format!("{}{path}", host.trim_end_matches("/"));
+    let response = reqwest::get(url).await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    Ok(Some(response.error_for_status()?.bytes().await?.to_vec()))
+}
@@ -1,8 +1,74 @@
+use std::fmt::{Display, Formatter};
+
 use serde::{Deserialize, Serialize};
+use tracing::error;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub enum Error {
+    /// token 不存在或已失效
     NotAuth,
+    /// 已登录，但没有执行该操作的权限
+    PermissionDenied,
+    ValidationFailed(String),
+    Conflict,
+    NotFound,
+    RateLimited {
+        retry_after_secs: u64,
+    },
     WordIsNotExist,
     DatabaseErr,
+    /// 邮件发送失败
+    MailUnavailable,
+    /// 仅由客户端产生：请求未能到达服务器或响应无法解析
+    Network(String),
+}
+
+impl Error {
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Error::NotAuth => 401,
+            Error::PermissionDenied => 403,
+            Error::ValidationFailed(_) => 400,
+            Error::Conflict => 409,
+            Error::NotFound | Error::WordIsNotExist => 404,
+            Error::RateLimited { .. } => 429,
+            Error::DatabaseErr | Error::Network(_) => 500,
+            Error::MailUnavailable => 503,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NotAuth => write!(f, "not authenticated"),
+            Error::PermissionDenied => write!(f, "permission denied"),
+            Error::ValidationFailed(reason) => write!(f, "validation failed: {reason}"),
+            Error::Conflict => write!(f, "conflict"),
+            Error::NotFound => write!(f, "not found"),
+            Error::RateLimited { retry_after_secs } => {
+                write!(f, "rate limited, retry after {retry_after_secs}s")
+            }
+            Error::WordIsNotExist => write!(f, "word is not exist"),
+            Error::DatabaseErr => write!(f, "database error"),
+            Error::MailUnavailable => write!(f, "mail unavailable"),
+            Error::Network(reason) => write!(f, "network error: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<sea_orm::DbErr> for Error {
+    fn from(value: sea_orm::DbErr) -> Self {
+        //返回给客户端的错误不包含细节，在这里记录原因
+        error!("{value}");
+        Error::DatabaseErr
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(value: reqwest::Error) -> Self {
+        Error::Network(value.to_string())
+    }
 }
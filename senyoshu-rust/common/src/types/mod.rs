@@ -5,6 +5,7 @@ pub mod json_value_template;
 pub mod kanji_alias;
 pub mod kanji_detail;
 pub mod learn;
+pub mod moderation;
 pub mod sound;
 pub mod state;
 pub mod word;
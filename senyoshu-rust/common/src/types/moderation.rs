@@ -0,0 +1,18 @@
+use sea_orm::{DeriveActiveEnum, EnumIter};
+use serde::{Deserialize, Serialize};
+
+/// 审核日志记录的操作，只追加不修改
+#[derive(EnumIter, DeriveActiveEnum, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[sea_orm(rs_type = "i16", db_type = "SmallInteger")]
+pub enum ModerationAction {
+    /// 通过修改请求
+    Pass = 0,
+    /// 驳回修改请求
+    Cancel = 1,
+    /// 作者撤回自己的修改请求
+    Withdraw = 2,
+    /// 审核者批量修改词条
+    UpdateMany = 3,
+    /// 审核者删除词条
+    Delete = 4,
+}
@@ -1,12 +1,16 @@
 use chrono::FixedOffset;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone)]
+/// 某个读音、声调和说话人的音频，不包含音频本身
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct SoundMeta {
-    pub sid: i64,
     pub update_date: chrono::DateTime<FixedOffset>,
     pub author: i64,
     pub katakana: String,
     pub tone: u8,
+    /// 说话人，例如 `female`
+    pub role: String,
+    pub content_type: String,
+    /// `None` 为尚未审核
     pub checked: Option<bool>,
 }
@@ -22,6 +22,13 @@ pub static TONE_SIGNS: [char; 6] = ['⓪', '①', '②', '③', '④', '⑤'];
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
 pub struct Tone(usize);
 
+impl Tone {
+    /// 第几拍之后下降，0 为平板型
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
 
 // impl Into<char> for NumTone {
 //     fn into(self) -> char {
@@ -0,0 +1,24 @@
+use std::io::{Read, Write};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// CBOR 编码后再用 lz4 frame 压缩，与客户端本地存储的格式相同
+pub fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, String> {
+    let mut cbor = Vec::new();
+    ciborium::into_writer(value, &mut cbor).map_err(|err| err.to_string())?;
+
+    let mut compressed = Vec::new();
+    let mut compressor = lz4_flex::frame::FrameEncoder::new(&mut compressed);
+    compressor.write_all(cbor.as_slice()).map_err(|err| err.to_string())?;
+    compressor.finish().map_err(|err| err.to_string())?;
+    Ok(compressed)
+}
+
+pub fn decode<T: DeserializeOwned>(compressed: &[u8]) -> Result<T, String> {
+    let mut cbor = Vec::new();
+    lz4_flex::frame::FrameDecoder::new(compressed)
+        .read_to_end(&mut cbor)
+        .map_err(|err| err.to_string())?;
+    ciborium::from_reader(cbor.as_slice()).map_err(|err| err.to_string())
+}
@@ -1,7 +1,9 @@
 pub mod alias;
+pub mod cbor_lz4;
 pub mod iter_util;
 pub mod number;
 pub mod passwd_hasher;
+pub mod redact;
 pub mod string_util;
 pub mod time;
 pub mod seq_map;
@@ -22,3 +22,20 @@ pub fn is_legal_username(username: impl AsRef<str>) -> bool {
         .chars()
         .all(|c| (c >= 'a' && c <= 'z') || (c >= '0' && c <= '9'))
 }
+
+/// 只做基本的格式检查，是否可用由验证码确认
+pub fn is_legal_e_mail(e_mail: impl AsRef<str>) -> bool {
+    let e_mail = e_mail.as_ref();
+    if e_mail.len() > 254 || e_mail.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return false;
+    }
+    match e_mail.rsplit_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && domain.contains('.')
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,10 @@
+use std::fmt::{Debug, Formatter};
+
+/// 在手写的 `Debug` 实现中代替 token、密码等字段，避免被 `#[instrument]` 等写进日志
+pub struct Redacted;
+
+impl Debug for Redacted {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("***")
+    }
+}
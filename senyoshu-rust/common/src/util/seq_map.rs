@@ -1,7 +1,7 @@
 use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
-use std::mem::swap;
+use std::mem::{replace, swap};
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -57,6 +57,26 @@ impl<K, V> SeqMap<K, V>
             .binary_search_by(|(k1, _)| k1.borrow().cmp(key))
     }
 
+    /// 插入或替换，返回旧值
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.search(&key) {
+            Ok(idx) => Some(replace(&mut self.0[idx].1, value)),
+            Err(idx) => {
+                self.0.insert(idx, (key, value));
+                None
+            }
+        }
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+        where
+            K: Borrow<Q>,
+            Q: Ord + ?Sized,
+    {
+        let idx = self.search(key).ok()?;
+        Some(self.0.remove(idx).1)
+    }
+
     pub fn get_by_index(&self, idx: usize) -> Option<&V> {
         let item = self.0.as_slice().get(idx)?;
         Some(&item.1)
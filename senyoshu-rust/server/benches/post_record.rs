@@ -0,0 +1,64 @@
+//! 同步 10k 个知识点所需的时间，`cargo bench -p senyoshu-server --bench post_record`
+//!
+//! 与集成测试一样使用 sqlite 内存数据库，按客户端的分组大小上传
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use itertools::Itertools;
+
+use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::api::learn::POST_LEARN_RECORD_API;
+use senyoshu_common::types::learn::knowledge::{Knowledge, KnowledgeType};
+use senyoshu_common::types::learn::learn_knowledge_history::{LearnKnowledgeHistory, OperateRecord, OperateType};
+use senyoshu_common::types::learn::LearnHistoryMap;
+use senyoshu_common::util::time::UtcTimeStamp;
+
+use crate::common::{host, login};
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+const KNOWLEDGE_COUNT: usize = 10_000;
+const HISTORY_LEN: i64 = 20;
+/// 与客户端 `WorkBook::push` 的分组大小相同
+const GROUP_LEN: usize = 1000;
+
+fn history_map(offset: i64) -> HashMap<Knowledge, LearnKnowledgeHistory> {
+    (0..KNOWLEDGE_COUNT)
+        .map(|i| {
+            let knowledge = Knowledge {
+                knowledge_type: KnowledgeType::Kanji,
+                key: format!("k{i}"),
+            };
+            let history = (0..HISTORY_LEN)
+                .map(|n| OperateRecord {
+                    operate_type: OperateType::Remember,
+                    operate_time: UtcTimeStamp(offset + n * 1000),
+                })
+                .collect_vec();
+            (knowledge, LearnKnowledgeHistory { history, freeze_time: None })
+        })
+        .collect()
+}
+
+async fn sync(token: &Token, map: HashMap<Knowledge, LearnKnowledgeHistory>) -> Duration {
+    let start = Instant::now();
+    for group in map.into_iter().chunks(GROUP_LEN).into_iter() {
+        let group = LearnHistoryMap::new(group.collect());
+        POST_LEARN_RECORD_API.call_with_host(host(), token, &group).await.unwrap();
+    }
+    start.elapsed()
+}
+
+#[tokio::main]
+async fn main() {
+    let token = login("benchpostrecord").await;
+
+    //首次同步全部都是插入，再次同步时全部需要与已有的记录合并
+    let insert = sync(&token, history_map(0)).await;
+    let merge = sync(&token, history_map(HISTORY_LEN * 1000)).await;
+
+    println!("post_learn_record {KNOWLEDGE_COUNT} knowledges x {HISTORY_LEN} operates, {GROUP_LEN} per request");
+    println!("  first sync: {insert:?}");
+    println!("  merge sync: {merge:?}");
+}
@@ -0,0 +1,100 @@
+use axum::Json;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
+use sea_orm::prelude::Expr;
+use tracing::{info, instrument};
+
+use senyoshu_common::types::api::account::DELETED_AUTHOR;
+use senyoshu_common::types::error::Error;
+
+use crate::api::account::passwd::verify_passwd;
+use crate::api::ApiResponse;
+use crate::api::auth::AuthUser;
+use crate::database::{account, comment, learn, mail_token};
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::dic::{moderation_log, sounds, word_history};
+use crate::database::model::{data_model, learn_model};
+use crate::database::surf::surf_credential;
+
+pub async fn delete_account_api(user: AuthUser, Json(passwd_hash): Json<String>) -> ApiResponse<()> {
+    delete_account(user.user_info.uid, passwd_hash).await.into()
+}
+
+/// session 保存在账号中，随账号一起删除
+#[instrument(skip(passwd_hash))]
+pub(crate) async fn delete_account(uid: i64, passwd_hash: String) -> Result<(), Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
+
+    let account = account::Entity::find_by_id(uid)
+        .one(&transaction)
+        .await?
+        .ok_or(Error::NotAuth)?;
+    if !verify_passwd(&account, passwd_hash).await? {
+        return Err(Error::NotAuth);
+    }
+
+    learn::Entity::delete_many()
+        .filter(learn::Column::Uid.eq(uid))
+        .exec(&transaction)
+        .await?;
+    mail_token::Entity::delete_many()
+        .filter(mail_token::Column::Uid.eq(uid))
+        .exec(&transaction)
+        .await?;
+    surf_credential::Entity::delete_many()
+        .filter(surf_credential::Column::Uid.eq(uid))
+        .exec(&transaction)
+        .await?;
+
+    //词条的修改记录是词典历史的一部分，只去掉作者
+    word_history::Entity::update_many()
+        .filter(word_history::Column::Author.eq(uid))
+        .col_expr(word_history::Column::Author, Expr::value(DELETED_AUTHOR))
+        .exec(&transaction)
+        .await?;
+    learn_model::Entity::update_many()
+        .filter(learn_model::Column::Author.eq(uid))
+        .col_expr(learn_model::Column::Author, Expr::value(DELETED_AUTHOR))
+        .exec(&transaction)
+        .await?;
+    data_model::Entity::update_many()
+        .filter(data_model::Column::Author.eq(uid))
+        .col_expr(data_model::Column::Author, Expr::value(DELETED_AUTHOR))
+        .exec(&transaction)
+        .await?;
+    sounds::Entity::update_many()
+        .filter(sounds::Column::Author.eq(uid))
+        .col_expr(sounds::Column::Author, Expr::value(DELETED_AUTHOR))
+        .exec(&transaction)
+        .await?;
+
+    //审核意见是审核者写的内容，与审核者一起去掉
+    word_history::Entity::update_many()
+        .filter(word_history::Column::Reviewer.eq(uid))
+        .col_expr(word_history::Column::Reviewer, Expr::value(DELETED_AUTHOR))
+        .col_expr(word_history::Column::ReviewComment, Expr::value(None::<String>))
+        .exec(&transaction)
+        .await?;
+    //审核日志只追加，注销时只去掉操作者和附带的意见
+    moderation_log::Entity::update_many()
+        .filter(moderation_log::Column::Actor.eq(uid))
+        .col_expr(moderation_log::Column::Actor, Expr::value(DELETED_AUTHOR))
+        .col_expr(moderation_log::Column::Comment, Expr::value(None::<String>))
+        .exec(&transaction)
+        .await?;
+
+    //评论是自由填写的内容，按删除处理，保留位置以维持讨论的结构
+    comment::Entity::update_many()
+        .filter(comment::Column::Author.eq(uid))
+        .col_expr(comment::Column::Author, Expr::value(DELETED_AUTHOR))
+        .col_expr(comment::Column::Content, Expr::value(String::new()))
+        .col_expr(comment::Column::Deleted, Expr::value(true))
+        .exec(&transaction)
+        .await?;
+
+    account::Entity::delete_by_id(uid).exec(&transaction).await?;
+
+    transaction.commit().await?;
+    info!("account {} is deleted", account.username);
+    Ok(())
+}
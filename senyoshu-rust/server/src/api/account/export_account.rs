@@ -0,0 +1,81 @@
+use axum::Json;
+use axum::response::{IntoResponse, Response};
+use http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use http::HeaderMap;
+use itertools::Itertools;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use tracing::{error, instrument};
+
+use senyoshu_common::types::api::account::{ACCOUNT_EXPORT_VERSION, AccountExport, Contribution};
+use senyoshu_common::types::error::Error;
+
+use crate::api::account::session::now;
+use crate::api::{accepts, ApiResponse};
+use crate::api::auth::AuthUser;
+use crate::api::comment::to_comments;
+use crate::api::learn::get_record::get_all_records;
+use crate::database::comment;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::dic::word_history;
+
+const CBOR: &str = "application/cbor";
+
+/// 默认与其他 API 一样返回 json，请求 CBOR 时直接返回编码后的 [`AccountExport`]，出错时仍然是 json
+pub async fn export_account_api((user, headers): (AuthUser, HeaderMap), Json(()): Json<()>) -> Response {
+    match export_account(user).await {
+        Ok(export) if accepts(&headers, CBOR) => cbor_response(&export),
+        result => ApiResponse(result).into_response(),
+    }
+}
+
+fn cbor_response(export: &AccountExport) -> Response {
+    let mut bytes = Vec::new();
+    if let Err(err) = ciborium::into_writer(export, &mut bytes) {
+        error!("failed to encode export: {err}");
+        return ApiResponse::<()>(Err(Error::DatabaseErr)).into_response();
+    }
+    let file_name = format!("attachment; filename=\"senyoshu-{}.cbor\"", export.profile.username);
+    ([(CONTENT_TYPE, CBOR.to_string()), (CONTENT_DISPOSITION, file_name)], bytes).into_response()
+}
+
+#[instrument(skip_all, fields(uid = user.user_info.uid))]
+pub(crate) async fn export_account(user: AuthUser) -> Result<AccountExport, Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let uid = user.user_info.uid;
+
+    let learn = get_all_records(uid)
+        .await?
+        .into_iter()
+        .collect_vec();
+    let contributions = word_history::Entity::find()
+        .filter(word_history::Column::Author.eq(uid))
+        .order_by_asc(word_history::Column::Pid)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|it| Contribution {
+            pid: it.pid,
+            wid: it.wid,
+            post_date: it.post_date,
+            update_date: it.update_date,
+            state: it.state,
+            word_define: it.word_define,
+        })
+        .collect_vec();
+    let comments = comment::Entity::find()
+        .filter(comment::Column::Author.eq(uid))
+        .filter(comment::Column::Deleted.eq(false))
+        .order_by_asc(comment::Column::Id)
+        .all(db)
+        .await?;
+    let comments = to_comments(comments, db).await?;
+
+    Ok(AccountExport {
+        version: ACCOUNT_EXPORT_VERSION,
+        export_time: now(),
+        profile: user.user_info,
+        learn,
+        contributions,
+        comments,
+    })
+}
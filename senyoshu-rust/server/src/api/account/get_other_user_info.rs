@@ -2,24 +2,28 @@ use axum::Json;
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 
 use senyoshu_common::types::api::account::OtherUserInfo;
+use senyoshu_common::types::error::Error;
 
+use crate::api::ApiResponse;
 use crate::database::account;
 use crate::database::database::GLOBAL_DATABASE;
 
 pub async fn get_other_user_info_api(
     Json((uid, username)): Json<(Option<i64>, Option<String>)>,
-) -> Json<Option<OtherUserInfo>> {
+) -> ApiResponse<OtherUserInfo> {
     if uid.is_some() || username.is_some() {
-        Json(get_other_user_info(uid, username).await)
+        get_other_user_info(uid, username).await.into()
     } else {
-        Json(None)
+        ApiResponse(Err(Error::ValidationFailed(String::from(
+            "uid or username is required",
+        ))))
     }
 }
 
 pub async fn get_other_user_info(
     uid: Option<i64>,
     username: Option<String>,
-) -> Option<OtherUserInfo> {
+) -> Result<OtherUserInfo, Error> {
     let db = GLOBAL_DATABASE.get().unwrap();
     let mut select = account::Entity::find();
     if let Some(uid) = uid {
@@ -28,9 +32,9 @@ pub async fn get_other_user_info(
     if let Some(username) = username {
         select = select.filter(account::Column::Username.eq(username));
     }
-    let user_info = select.one(db).await.ok()??;
+    let user_info = select.one(db).await?.ok_or(Error::NotFound)?;
 
-    Some(OtherUserInfo {
+    Ok(OtherUserInfo {
         uid: user_info.uid,
         register_date: user_info.register_date,
         username: user_info.username,
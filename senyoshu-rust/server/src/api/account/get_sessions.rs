@@ -0,0 +1,10 @@
+use axum::Json;
+
+use senyoshu_common::types::api::session::SessionInfo;
+
+use crate::api::ApiResponse;
+use crate::api::auth::AuthUser;
+
+pub async fn get_sessions_api(user: AuthUser, Json(()): Json<()>) -> ApiResponse<Vec<SessionInfo>> {
+    ApiResponse(Ok(user.user_info.sessions))
+}
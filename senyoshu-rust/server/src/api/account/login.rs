@@ -1,51 +1,91 @@
 use axum::Json;
 use blake2::{Blake2b512, Digest};
-use chrono::Local;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
+use http::header::USER_AGENT;
+use http::HeaderMap;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect, TransactionTrait};
 use sea_orm::prelude::Expr;
-use tracing::{debug, instrument};
+use tracing::instrument;
 
 use senyoshu_common::types::api::account::Token;
-use senyoshu_common::types::api::session::{Session, SessionVec};
-use senyoshu_common::util::passwd_hasher::get_passwd_hash;
+use senyoshu_common::types::api::session::Session;
+use senyoshu_common::types::error::Error;
 
+use crate::api::account::passwd::{CURRENT_VERSION, hash_passwd, verify_dummy, verify_passwd};
+use crate::api::account::session::{MAX_SESSIONS, now, prune, save_sessions, SESSION_TTL};
+use crate::api::ApiResponse;
 use crate::database::account;
 use crate::database::database::GLOBAL_DATABASE;
 
+const MAX_DEVICE_LEN: usize = 64;
+const MAX_USER_AGENT_LEN: usize = 256;
+
 pub async fn login_api(
-    Json((username, password_hash)): Json<(String, String)>,
-) -> Json<Option<Token>> {
-    Json(login(username, password_hash).await)
+    headers: HeaderMap,
+    Json((username, password_hash, device)): Json<(String, String, String)>,
+) -> ApiResponse<Token> {
+    let user_agent = headers
+        .get(USER_AGENT)
+        .and_then(|it| it.to_str().ok())
+        .unwrap_or_default()
+        .chars()
+        .take(MAX_USER_AGENT_LEN)
+        .collect();
+    let device = device.chars().take(MAX_DEVICE_LEN).collect();
+    login(username, password_hash, device, user_agent).await.into()
 }
 
-#[instrument]
-pub async fn login(username: String, password_hash: String) -> Option<Token> {
+#[instrument(skip(password_hash))]
+pub async fn login(
+    username: String,
+    password_hash: String,
+    device: String,
+    user_agent: String,
+) -> Result<Token, Error> {
     let db = GLOBAL_DATABASE.get().unwrap();
-    let transaction = db.begin().await.ok()?;
-
-    let passwd_hash2 = get_passwd_hash(password_hash.as_str());
-    debug!("username:{}", username.as_str());
-    debug!("passwd_hash2:{}", passwd_hash2);
+    let transaction = db.begin().await?;
 
-    let user_info = account::Entity::find()
+    //加锁读取，之后会修改 sessions
+    let Some(user_info) = account::Entity::find()
         .filter(account::Column::Username.eq(username))
+        .lock_exclusive()
         .one(&transaction)
-        .await
-        .unwrap()?;
+        .await?
+    else {
+        verify_dummy(password_hash).await?;
+        return Err(Error::NotAuth);
+    };
 
-    debug!("passwd_hash2_in_db:{}", user_info.passwd_hash2);
-    if user_info.passwd_hash2 != passwd_hash2 {
-        return None;
+    if !verify_passwd(&user_info, password_hash.to_owned()).await? {
+        return Err(Error::NotAuth);
     }
 
-    let mut sessions = user_info.sessions.unwrap_or_default().0;
-    //目前限制为4个
+    //旧格式的哈希在登录成功后升级
+    if user_info.passwd_hash_version != CURRENT_VERSION {
+        account::Entity::update_many()
+            .filter(account::Column::Uid.eq(user_info.uid))
+            .col_expr(
+                account::Column::PasswdHash2,
+                Expr::value(hash_passwd(password_hash).await?),
+            )
+            .col_expr(account::Column::PasswdHashVersion, Expr::value(CURRENT_VERSION))
+            .exec(&transaction)
+            .await?;
+    }
 
-    if sessions.len() >= 4 {
-        sessions.pop_front();
+    let time = now();
+    let mut sessions = user_info.sessions.unwrap_or_default().0;
+    prune(&mut sessions, time);
+    //超过上限时移除最久未使用的
+    while sessions.len() >= MAX_SESSIONS {
+        let oldest = sessions
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, session)| session.last_used.unwrap_or(session.login_time))
+            .map(|(index, _)| index)
+            .unwrap_or_default();
+        sessions.remove(oldest);
     }
 
-    let time = Local::now();
     let mut hasher = Blake2b512::new();
     hasher.update(b"senyoshu-user-token-");
     hasher.update(user_info.uid.to_string().as_bytes());
@@ -58,23 +98,19 @@ pub async fn login(username: String, password_hash: String) -> Option<Token> {
 
     sessions.push_back(Session {
         token: token.to_string(),
-        login_time: time.into(),
+        login_time: time,
         active: true,
+        device,
+        user_agent,
+        last_used: Some(time),
+        expire_time: Some(time + SESSION_TTL),
     });
 
-    account::Entity::update_many()
-        .filter(account::Column::Uid.eq(user_info.uid))
-        .col_expr(
-            account::Column::Sessions,
-            Expr::value(Some(SessionVec(sessions.to_owned()))),
-        )
-        .exec(&transaction)
-        .await
-        .ok()?;
+    save_sessions(user_info.uid, sessions, &transaction).await?;
 
-    transaction.commit().await.ok()?;
+    transaction.commit().await?;
 
-    Some(Token {
+    Ok(Token {
         uid: user_info.uid,
         token,
     })
@@ -0,0 +1,109 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use blake2::{Blake2b512, Digest};
+use chrono::TimeDelta;
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use sea_orm::ActiveValue::Set;
+use sea_orm::prelude::Expr;
+
+use senyoshu_common::types::error::Error;
+
+use crate::api::account::session::now;
+use crate::database::mail_token;
+use crate::database::mail_token::MailPurpose;
+
+/// 邮箱验证码的有效期
+pub(crate) const EMAIL_CODE_TTL: TimeDelta = TimeDelta::minutes(15);
+/// 重置密码 token 的有效期
+pub(crate) const RESET_TOKEN_TTL: TimeDelta = TimeDelta::minutes(30);
+/// 同一账号同一用途两次发送邮件的最小间隔
+const RESEND_INTERVAL: TimeDelta = TimeDelta::seconds(60);
+/// 验证码输错这么多次之后作废
+pub(crate) const MAX_ATTEMPTS: i16 = 5;
+
+/// 6 位数字验证码
+pub(crate) fn new_code() -> String {
+    format!("{:06}", OsRng.next_u32() % 1_000_000)
+}
+
+pub(crate) fn new_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+pub(crate) fn hash_token(token: &str) -> String {
+    let mut hasher = Blake2b512::new();
+    hasher.update(b"senyoshu-mail-token-");
+    hasher.update(token.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// 最近一次发出的验证码或 token
+pub(crate) async fn latest<C: ConnectionTrait>(
+    uid: i64,
+    purpose: MailPurpose,
+    db: &C,
+) -> Result<Option<mail_token::Model>, DbErr> {
+    mail_token::Entity::find()
+        .filter(mail_token::Column::Uid.eq(uid))
+        .filter(mail_token::Column::Purpose.eq(purpose))
+        .order_by_desc(mail_token::Column::Id)
+        .one(db)
+        .await
+}
+
+/// 作废之前未使用的验证码或 token 并保存新的
+///
+/// 距离上次发送不足 [`RESEND_INTERVAL`] 时返回 [`Error::RateLimited`]
+pub(crate) async fn issue<C: ConnectionTrait>(
+    uid: i64,
+    purpose: MailPurpose,
+    e_mail: &str,
+    token: &str,
+    ttl: TimeDelta,
+    db: &C,
+) -> Result<(), Error> {
+    let now = now();
+    if let Some(last) = latest(uid, purpose, db).await? {
+        let wait = last.create_time + RESEND_INTERVAL - now;
+        if wait > TimeDelta::zero() {
+            return Err(Error::RateLimited {
+                retry_after_secs: wait.num_seconds().max(1) as u64,
+            });
+        }
+    }
+
+    mail_token::Entity::update_many()
+        .filter(mail_token::Column::Uid.eq(uid))
+        .filter(mail_token::Column::Purpose.eq(purpose))
+        .filter(mail_token::Column::Used.eq(false))
+        .col_expr(mail_token::Column::Used, Expr::value(true))
+        .exec(db)
+        .await?;
+
+    mail_token::ActiveModel {
+        uid: Set(uid),
+        purpose: Set(purpose),
+        e_mail: Set(e_mail.to_string()),
+        token_hash: Set(hash_token(token)),
+        attempts: Set(0),
+        used: Set(false),
+        create_time: Set(now),
+        expire_time: Set(now + ttl),
+        ..Default::default()
+    }
+        .insert(db)
+        .await?;
+    Ok(())
+}
+
+/// 标记为已使用，已经被其他请求使用时返回 false
+pub(crate) async fn consume<C: ConnectionTrait>(id: i64, db: &C) -> Result<bool, DbErr> {
+    let result = mail_token::Entity::update_many()
+        .filter(mail_token::Column::Id.eq(id))
+        .filter(mail_token::Column::Used.eq(false))
+        .col_expr(mail_token::Column::Used, Expr::value(true))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
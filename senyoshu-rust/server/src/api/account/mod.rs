@@ -1,37 +1,52 @@
-use sea_orm::ConnectionTrait;
-use sea_orm::EntityTrait;
+use std::collections::VecDeque;
+
+use sea_orm::{ConnectionTrait, TransactionTrait};
 
 use senyoshu_common::types::api::account::{Token, UserInfo};
+use senyoshu_common::types::api::session::Session;
+use senyoshu_common::types::error::Error;
 
+use crate::api::account::session::{authenticate, to_session_info};
 use crate::database::account;
 
+pub mod delete_account;
+pub mod export_account;
 pub mod get_other_user_info;
+pub mod get_sessions;
 pub mod update_user_state;
 pub mod login;
+pub(crate) mod mail_token;
+pub(crate) mod passwd;
 pub mod register;
+pub mod request_email_code;
+pub mod request_passwd_reset;
+pub mod reset_passwd;
+pub mod revoke_other_sessions;
+pub mod revoke_session;
+pub(crate) mod session;
 pub mod update_passwd;
+pub mod verify_email;
 
-pub(crate) async fn get_user_info<C: ConnectionTrait>(token: Token, db: &C) -> Option<UserInfo> {
-    let user_info = account::Entity::find_by_id(token.uid)
-        .one(db)
-        .await
-        .ok()??;
-
-    let sessions = user_info.sessions?;
+pub(crate) async fn get_user_info<C: ConnectionTrait + TransactionTrait>(token: Token, db: &C) -> Result<UserInfo, Error> {
+    let (account, sessions) = authenticate(&token, db).await?;
+    Ok(to_user_info(account, &sessions, token.token.as_str()))
+}
 
-    if sessions.0.iter().any(|s| s.token == token.token) {
-        Some(UserInfo {
-            uid: user_info.uid,
-            register_date: user_info.register_date,
-            username: user_info.username,
-            e_mail: user_info.e_mail,
-            post_permission: user_info.post_permission,
-            restrict_user: user_info.restrict_user,
-            content_maintainer: user_info.content_maintainer,
-            vip: user_info.vip,
-            sessions: sessions.0.into(),
-        })
-    } else {
-        None
+fn to_user_info(account: account::Model, sessions: &VecDeque<Session>, current_token: &str) -> UserInfo {
+    UserInfo {
+        uid: account.uid,
+        register_date: account.register_date,
+        username: account.username,
+        e_mail: account.e_mail,
+        post_permission: account.post_permission,
+        restrict_user: account.restrict_user,
+        restrict_reason: account.restrict_reason,
+        content_maintainer: account.content_maintainer,
+        admin: account.admin,
+        vip: account.vip,
+        sessions: sessions
+            .iter()
+            .map(|session| to_session_info(session, current_token))
+            .collect(),
     }
 }
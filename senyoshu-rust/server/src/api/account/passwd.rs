@@ -0,0 +1,80 @@
+use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::SaltString;
+use once_cell::sync::Lazy;
+use tokio::task::spawn_blocking;
+use tracing::error;
+
+use senyoshu_common::types::error::Error;
+use senyoshu_common::util::passwd_hasher::get_passwd_hash;
+
+use crate::database::account::{self, PasswdHashVersion};
+
+/// 新密码统一使用的格式
+pub(crate) const CURRENT_VERSION: PasswdHashVersion = PasswdHashVersion::Argon2id;
+
+/// 用户不存在时用于比较的哈希，第一次使用时生成
+static DUMMY_HASH: Lazy<String> = Lazy::new(|| {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(b"senyoshu-dummy", &salt)
+        .map(|hash| hash.to_string())
+        .unwrap_or_default()
+});
+
+/// 计算保存到数据库的哈希，`passwd_hash` 是客户端已经计算过一次的哈希
+pub(crate) async fn hash_passwd(passwd_hash: String) -> Result<String, Error> {
+    //Argon2 比较耗时，不能阻塞运行时
+    spawn_blocking(move || {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(passwd_hash.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+    })
+        .await
+        .map_err(|err| {
+            error!("hash passwd failed: {err}");
+            Error::DatabaseErr
+        })?
+        .map_err(|err| {
+            error!("hash passwd failed: {err}");
+            Error::DatabaseErr
+        })
+}
+
+pub(crate) async fn verify_passwd(account: &account::Model, passwd_hash: String) -> Result<bool, Error> {
+    match account.passwd_hash_version {
+        PasswdHashVersion::Legacy => Ok(account.passwd_hash2 == get_passwd_hash(passwd_hash)),
+        PasswdHashVersion::Argon2id => {
+            let passwd_hash2 = account.passwd_hash2.to_owned();
+            spawn_blocking(move || {
+                let Ok(parsed) = PasswordHash::new(passwd_hash2.as_str()) else {
+                    error!("illegal passwd_hash2 in database");
+                    return false;
+                };
+                Argon2::default()
+                    .verify_password(passwd_hash.as_bytes(), &parsed)
+                    .is_ok()
+            })
+                .await
+                .map_err(|err| {
+                    error!("verify passwd failed: {err}");
+                    Error::DatabaseErr
+                })
+        }
+    }
+}
+
+/// 用户不存在时也验证一次，耗时与密码错误时相同，不能通过响应时间判断用户名是否存在
+pub(crate) async fn verify_dummy(passwd_hash: String) -> Result<(), Error> {
+    spawn_blocking(move || {
+        if let Ok(parsed) = PasswordHash::new(DUMMY_HASH.as_str()) {
+            let _ = Argon2::default().verify_password(passwd_hash.as_bytes(), &parsed);
+        }
+    })
+        .await
+        .map_err(|err| {
+            error!("verify passwd failed: {err}");
+            Error::DatabaseErr
+        })
+}
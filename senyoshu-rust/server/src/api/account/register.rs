@@ -1,31 +1,39 @@
 use axum::Json;
-use sea_orm::ActiveModelTrait;
+use sea_orm::{ActiveModelTrait, SqlErr};
 use sea_orm::ActiveValue::Set;
 use tracing::instrument;
 
-use senyoshu_common::util::passwd_hasher::{get_passwd_hash, is_legal_username};
+use senyoshu_common::types::error::Error;
+use senyoshu_common::util::passwd_hasher::is_legal_username;
 
+use crate::api::account::passwd::{CURRENT_VERSION, hash_passwd};
+use crate::api::ApiResponse;
 use crate::database::account;
 use crate::database::database::GLOBAL_DATABASE;
 
-pub async fn register_api(Json((username, passwd_hash)): Json<(String, String)>) -> Json<bool> {
-    Json(register(username, passwd_hash.as_str()).await.is_some())
+pub async fn register_api(Json((username, passwd_hash)): Json<(String, String)>) -> ApiResponse<()> {
+    register(username, passwd_hash.as_str()).await.into()
 }
 
-#[instrument]
-pub(crate) async fn register(username: String, passwd_hash: &str) -> Option<()> {
+#[instrument(skip(passwd_hash))]
+pub(crate) async fn register(username: String, passwd_hash: &str) -> Result<(), Error> {
     let db = GLOBAL_DATABASE.get().unwrap();
 
-    if is_legal_username(&username) == false {
-        return None;
+    if username.is_empty() || !is_legal_username(&username) {
+        return Err(Error::ValidationFailed(String::from("illegal username")));
     }
 
     let user = account::ActiveModel {
         username: Set(username.to_string()),
-        passwd_hash2: Set(get_passwd_hash(passwd_hash)),
+        passwd_hash2: Set(hash_passwd(passwd_hash.to_string()).await?),
+        passwd_hash_version: Set(CURRENT_VERSION),
         ..Default::default()
     };
-    user.insert(db).await.ok()?;
+    //用户名是唯一的，违反唯一约束说明用户名已被注册
+    user.insert(db).await.map_err(|err| match err.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(_)) => Error::Conflict,
+        _ => Error::from(err),
+    })?;
 
-    Some(())
+    Ok(())
 }
@@ -0,0 +1,60 @@
+use axum::{Extension, Json};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
+use tracing::instrument;
+
+use senyoshu_common::types::error::Error;
+use senyoshu_common::util::passwd_hasher::is_legal_e_mail;
+
+use crate::api::account::mail_token::{EMAIL_CODE_TTL, issue, new_code};
+use crate::api::ApiResponse;
+use crate::api::auth::AuthUser;
+use crate::database::account;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::mail_token::MailPurpose;
+use crate::mail::{Mail, MailService};
+
+pub async fn request_email_code_api(
+    (user, Extension(mail)): (AuthUser, Extension<MailService>),
+    Json(e_mail): Json<String>,
+) -> ApiResponse<()> {
+    request_email_code(user.user_info.uid, e_mail.trim().to_lowercase(), &mail)
+        .await
+        .into()
+}
+
+#[instrument(skip(mail))]
+pub(crate) async fn request_email_code(uid: i64, e_mail: String, mail: &MailService) -> Result<(), Error> {
+    if !is_legal_e_mail(&e_mail) {
+        return Err(Error::ValidationFailed(String::from("illegal e_mail")));
+    }
+
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
+
+    //已经绑定到其他账号
+    let bound = account::Entity::find()
+        .filter(account::Column::EMail.eq(e_mail.as_str()))
+        .filter(account::Column::Uid.ne(uid))
+        .one(&transaction)
+        .await?;
+    if bound.is_some() {
+        return Err(Error::Conflict);
+    }
+
+    let code = new_code();
+    issue(uid, MailPurpose::VerifyEmail, e_mail.as_str(), code.as_str(), EMAIL_CODE_TTL, &transaction).await?;
+    //发送失败时回滚，不占用重发间隔
+    mail.send(Mail {
+        to: e_mail,
+        subject: String::from("senyoshu 邮箱验证码 / verification code"),
+        body: format!(
+            "验证码 / verification code: {code}\n\n{} 分钟内有效 / valid for {} minutes",
+            EMAIL_CODE_TTL.num_minutes(),
+            EMAIL_CODE_TTL.num_minutes()
+        ),
+    })
+        .await?;
+
+    transaction.commit().await?;
+    Ok(())
+}
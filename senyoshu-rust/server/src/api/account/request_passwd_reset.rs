@@ -0,0 +1,63 @@
+use axum::{Extension, Json};
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, TransactionTrait};
+use tracing::instrument;
+
+use senyoshu_common::types::error::Error;
+
+use crate::api::account::mail_token::{issue, new_token, RESET_TOKEN_TTL};
+use crate::api::ApiResponse;
+use crate::database::account;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::mail_token::MailPurpose;
+use crate::mail::{Mail, MailService};
+
+pub async fn request_passwd_reset_api(
+    Extension(mail): Extension<MailService>,
+    Json(name): Json<String>,
+) -> ApiResponse<()> {
+    request_passwd_reset(name.trim().to_lowercase(), &mail).await.into()
+}
+
+/// 账号不存在、没有绑定邮箱或者发送过于频繁时都返回成功，不暴露账号信息
+#[instrument(skip(mail))]
+pub(crate) async fn request_passwd_reset(name: String, mail: &MailService) -> Result<(), Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
+
+    let account = account::Entity::find()
+        .filter(
+            Condition::any()
+                .add(account::Column::Username.eq(name.as_str()))
+                .add(account::Column::EMail.eq(name.as_str())),
+        )
+        .one(&transaction)
+        .await?;
+    let Some((uid, e_mail)) = account.and_then(|it| Some((it.uid, it.e_mail?))) else {
+        return Ok(());
+    };
+
+    let token = new_token();
+    match issue(uid, MailPurpose::ResetPasswd, e_mail.as_str(), token.as_str(), RESET_TOKEN_TTL, &transaction).await {
+        Err(Error::RateLimited { .. }) => return Ok(()),
+        result => result?,
+    }
+
+    let link = if mail.public_url().is_empty() {
+        format!("token: {token}")
+    } else {
+        format!("{}/reset_passwd?token={token}", mail.public_url())
+    };
+    mail.send(Mail {
+        to: e_mail,
+        subject: String::from("senyoshu 重置密码 / reset password"),
+        body: format!(
+            "{link}\n\n{} 分钟内有效，如果不是您本人的操作请忽略这封邮件 / valid for {} minutes, ignore this mail if you did not request it",
+            RESET_TOKEN_TTL.num_minutes(),
+            RESET_TOKEN_TTL.num_minutes()
+        ),
+    })
+        .await?;
+
+    transaction.commit().await?;
+    Ok(())
+}
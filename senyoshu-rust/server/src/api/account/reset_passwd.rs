@@ -0,0 +1,55 @@
+use std::collections::VecDeque;
+
+use axum::Json;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
+use sea_orm::prelude::Expr;
+use tracing::instrument;
+
+use senyoshu_common::types::error::Error;
+
+use crate::api::account::mail_token::{consume, hash_token};
+use crate::api::account::passwd::{CURRENT_VERSION, hash_passwd};
+use crate::api::account::session::{now, save_sessions};
+use crate::api::ApiResponse;
+use crate::database::{account, mail_token};
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::mail_token::MailPurpose;
+
+pub async fn reset_passwd_api(Json((token, new_passwd_hash)): Json<(String, String)>) -> ApiResponse<()> {
+    reset_passwd(token.trim(), new_passwd_hash).await.into()
+}
+
+#[instrument(skip_all)]
+pub(crate) async fn reset_passwd(token: &str, new_passwd_hash: String) -> Result<(), Error> {
+    if new_passwd_hash.is_empty() {
+        return Err(Error::ValidationFailed(String::from("passwd is empty")));
+    }
+
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
+
+    let invalid = || Error::ValidationFailed(String::from("token is invalid or expired"));
+    let token = mail_token::Entity::find()
+        .filter(mail_token::Column::TokenHash.eq(hash_token(token)))
+        .filter(mail_token::Column::Purpose.eq(MailPurpose::ResetPasswd))
+        .filter(mail_token::Column::Used.eq(false))
+        .one(&transaction)
+        .await?
+        .filter(|it| it.expire_time > now())
+        .ok_or_else(invalid)?;
+    if !consume(token.id, &transaction).await? {
+        return Err(invalid());
+    }
+
+    account::Entity::update_many()
+        .filter(account::Column::Uid.eq(token.uid))
+        .col_expr(account::Column::PasswdHash2, Expr::value(hash_passwd(new_passwd_hash).await?))
+        .col_expr(account::Column::PasswdHashVersion, Expr::value(CURRENT_VERSION))
+        .exec(&transaction)
+        .await?;
+    //重置密码后所有设备都需要重新登录
+    save_sessions(token.uid, VecDeque::new(), &transaction).await?;
+
+    transaction.commit().await?;
+    Ok(())
+}
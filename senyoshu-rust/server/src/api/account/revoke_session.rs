@@ -0,0 +1,33 @@
+use axum::Json;
+use sea_orm::TransactionTrait;
+use tracing::instrument;
+
+use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::error::Error;
+
+use crate::api::account::session::{authenticate_locked, save_sessions};
+use crate::api::ApiResponse;
+use crate::api::auth::AuthUser;
+use crate::database::database::GLOBAL_DATABASE;
+
+pub async fn revoke_session_api(user: AuthUser, Json(session_id): Json<String>) -> ApiResponse<()> {
+    revoke_session(user.token, session_id).await.into()
+}
+
+#[instrument]
+async fn revoke_session(token: Token, session_id: String) -> Result<(), Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
+
+    //加锁读取，避免覆盖同时发生的登录和续期
+    let (account, mut sessions) = authenticate_locked(&token, &transaction).await?;
+    let index = sessions
+        .iter()
+        .position(|session| session.id() == session_id)
+        .ok_or(Error::NotFound)?;
+    sessions.remove(index);
+    save_sessions(account.uid, sessions, &transaction).await?;
+
+    transaction.commit().await?;
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, FixedOffset, Local, TimeDelta};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseTransaction, DbErr, EntityTrait, QueryFilter, QuerySelect,
+    TransactionTrait,
+};
+use sea_orm::prelude::Expr;
+
+use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::api::session::{Session, SessionInfo, SessionVec};
+use senyoshu_common::types::error::Error;
+
+use crate::database::account;
+
+/// session 的有效期，每次使用后顺延
+pub(crate) const SESSION_TTL: TimeDelta = TimeDelta::days(30);
+/// 距离上次续期超过这个时间才写入数据库，避免每个请求都更新 account
+const REFRESH_INTERVAL: TimeDelta = TimeDelta::hours(1);
+/// 每个账号最多保留的 session，超过时移除最久未使用的
+pub(crate) const MAX_SESSIONS: usize = 8;
+
+pub(crate) fn now() -> DateTime<FixedOffset> {
+    Local::now().fixed_offset()
+}
+
+pub(crate) fn expire_time(session: &Session) -> DateTime<FixedOffset> {
+    session
+        .expire_time
+        .unwrap_or(session.login_time + SESSION_TTL)
+}
+
+pub(crate) fn is_alive(session: &Session, now: DateTime<FixedOffset>) -> bool {
+    session.active && expire_time(session) > now
+}
+
+/// 移除已经失效的 session
+pub(crate) fn prune(sessions: &mut VecDeque<Session>, now: DateTime<FixedOffset>) {
+    sessions.retain(|session| is_alive(session, now));
+}
+
+pub(crate) fn to_session_info(session: &Session, current_token: &str) -> SessionInfo {
+    SessionInfo {
+        id: session.id(),
+        device: session.device.to_owned(),
+        user_agent: session.user_agent.to_owned(),
+        login_time: session.login_time,
+        last_used: session.last_used,
+        expire_time: expire_time(session),
+        current: session.token == current_token,
+    }
+}
+
+pub(crate) async fn save_sessions<C: ConnectionTrait>(
+    uid: i64,
+    sessions: VecDeque<Session>,
+    db: &C,
+) -> Result<(), DbErr> {
+    account::Entity::update_many()
+        .filter(account::Column::Uid.eq(uid))
+        .col_expr(account::Column::Sessions, Expr::value(Some(SessionVec(sessions))))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// 加锁读取账号，修改 sessions 前都需要在事务中通过这里读取，避免同时发生的修改互相覆盖
+///
+/// sqlite 不支持行锁，写事务本身是串行的
+pub(crate) async fn lock_account(
+    uid: i64,
+    transaction: &DatabaseTransaction,
+) -> Result<Option<account::Model>, DbErr> {
+    account::Entity::find_by_id(uid)
+        .lock_exclusive()
+        .one(transaction)
+        .await
+}
+
+/// 校验 token 并顺延有效期，返回去掉了失效部分的 session，以及是否需要写回数据库
+fn check_token(
+    account: &account::Model,
+    token: &Token,
+    now: DateTime<FixedOffset>,
+) -> Result<(VecDeque<Session>, bool), Error> {
+    let mut sessions = account.sessions.to_owned().unwrap_or_default().0;
+    let session = sessions
+        .iter_mut()
+        .find(|session| session.token == token.token)
+        .ok_or(Error::NotAuth)?;
+    if !is_alive(session, now) {
+        return Err(Error::NotAuth);
+    }
+
+    let need_refresh = session
+        .last_used
+        .is_none_or(|last_used| now - last_used >= REFRESH_INTERVAL);
+    if need_refresh {
+        session.last_used = Some(now);
+        session.expire_time = Some(now + SESSION_TTL);
+    }
+    prune(&mut sessions, now);
+    Ok((sessions, need_refresh))
+}
+
+/// 校验 token，撤销或过期的 token 返回 [`Error::NotAuth`]，有效时顺延有效期
+///
+/// 返回的 session 中已经去掉了失效的部分。不需要续期时不加锁，续期时在事务中重新校验后写入
+pub(crate) async fn authenticate<C: ConnectionTrait + TransactionTrait>(
+    token: &Token,
+    db: &C,
+) -> Result<(account::Model, VecDeque<Session>), Error> {
+    let account = account::Entity::find_by_id(token.uid)
+        .one(db)
+        .await?
+        .ok_or(Error::NotAuth)?;
+    let (sessions, need_refresh) = check_token(&account, token, now())?;
+    if !need_refresh {
+        return Ok((account, sessions));
+    }
+
+    let transaction = db.begin().await?;
+    let result = authenticate_locked(token, &transaction).await?;
+    transaction.commit().await?;
+    Ok(result)
+}
+
+/// 与 [`authenticate`] 相同，但在调用者的事务中加锁读取，之后可以修改 sessions 并保存
+pub(crate) async fn authenticate_locked(
+    token: &Token,
+    transaction: &DatabaseTransaction,
+) -> Result<(account::Model, VecDeque<Session>), Error> {
+    let account = lock_account(token.uid, transaction)
+        .await?
+        .ok_or(Error::NotAuth)?;
+    let (sessions, need_refresh) = check_token(&account, token, now())?;
+    if need_refresh {
+        save_sessions(account.uid, sessions.to_owned(), transaction).await?;
+    }
+    Ok((account, sessions))
+}
@@ -1,56 +1,61 @@
+use std::collections::VecDeque;
+
 use axum::Json;
-use sea_orm::{ActiveModelTrait, ColumnTrait, Condition, EntityTrait, QueryFilter};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
 use sea_orm::prelude::Expr;
-use tracing::{error, instrument};
+use tracing::instrument;
 
-use senyoshu_common::util::passwd_hasher::get_passwd_hash;
+use senyoshu_common::types::error::Error;
 
+use crate::api::account::passwd::{CURRENT_VERSION, hash_passwd, verify_passwd};
+use crate::api::account::session::save_sessions;
+use crate::api::ApiResponse;
 use crate::database::account;
 use crate::database::database::GLOBAL_DATABASE;
 
 pub async fn update_passwd_api(
     Json((username, new_passwd_hash, old_passwd_hash)): Json<(String, String, String)>,
-) -> Json<bool> {
-    Json(
-        update_passwd(
-            username.as_str(),
-            new_passwd_hash.as_str(),
-            old_passwd_hash.as_str(),
-        )
-            .await
-            .is_some(),
+) -> ApiResponse<()> {
+    update_passwd(
+        username.as_str(),
+        new_passwd_hash.as_str(),
+        old_passwd_hash.as_str(),
     )
+        .await
+        .into()
 }
 
-#[instrument]
+#[instrument(skip(new_passwd_hash, old_passwd_hash))]
 pub(crate) async fn update_passwd(
     username: &str,
     new_passwd_hash: &str,
     old_passwd_hash: &str,
-) -> Option<()> {
+) -> Result<(), Error> {
     let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
 
-    let result = account::Entity::update_many()
-        .filter(
-            Condition::all()
-                .add(account::Column::Username.eq(username))
-                .add(account::Column::PasswdHash2.eq(get_passwd_hash(old_passwd_hash))),
-        )
+    let user_info = account::Entity::find()
+        .filter(account::Column::Username.eq(username))
+        .one(&transaction)
+        .await?
+        .ok_or(Error::NotAuth)?;
+
+    if !verify_passwd(&user_info, old_passwd_hash.to_string()).await? {
+        return Err(Error::NotAuth);
+    }
+
+    account::Entity::update_many()
+        .filter(account::Column::Uid.eq(user_info.uid))
         .col_expr(
             account::Column::PasswdHash2,
-            Expr::value(get_passwd_hash(new_passwd_hash)),
+            Expr::value(hash_passwd(new_passwd_hash.to_string()).await?),
         )
-        .exec(db)
-        .await
-        .ok()?;
-
-    match result.rows_affected {
-        0 => None,
-        1 => Some(()),
-        _ => {
-            let rows_affected = result.rows_affected;
-            error!(rows_affected);
-            Some(())
-        }
-    }
+        .col_expr(account::Column::PasswdHashVersion, Expr::value(CURRENT_VERSION))
+        .exec(&transaction)
+        .await?;
+    //与重置密码相同，修改后所有设备都需要重新登录
+    save_sessions(user_info.uid, VecDeque::new(), &transaction).await?;
+
+    transaction.commit().await?;
+    Ok(())
 }
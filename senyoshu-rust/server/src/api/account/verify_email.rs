@@ -0,0 +1,68 @@
+use axum::Json;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
+use sea_orm::prelude::Expr;
+use tracing::instrument;
+
+use senyoshu_common::types::error::Error;
+
+use crate::api::account::mail_token::{consume, hash_token, latest, MAX_ATTEMPTS};
+use crate::api::account::session::now;
+use crate::api::ApiResponse;
+use crate::api::auth::AuthUser;
+use crate::database::{account, mail_token};
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::mail_token::MailPurpose;
+
+pub async fn verify_email_api(user: AuthUser, Json((e_mail, code)): Json<(String, String)>) -> ApiResponse<()> {
+    verify_email(user.user_info.uid, e_mail.trim().to_lowercase(), code.trim())
+        .await
+        .into()
+}
+
+#[instrument(skip(code))]
+pub(crate) async fn verify_email(uid: i64, e_mail: String, code: &str) -> Result<(), Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
+
+    //只有最近一次发送的验证码有效
+    let token = latest(uid, MailPurpose::VerifyEmail, &transaction)
+        .await?
+        .filter(|it| !it.used && it.e_mail == e_mail && it.expire_time > now())
+        .ok_or(Error::ValidationFailed(String::from("code is expired")))?;
+
+    if token.token_hash != hash_token(code) {
+        //输错次数过多时作废，需要重新发送
+        let attempts = token.attempts + 1;
+        mail_token::Entity::update_many()
+            .filter(mail_token::Column::Id.eq(token.id))
+            .col_expr(mail_token::Column::Attempts, Expr::value(attempts))
+            .col_expr(mail_token::Column::Used, Expr::value(attempts >= MAX_ATTEMPTS))
+            .exec(&transaction)
+            .await?;
+        transaction.commit().await?;
+        return Err(Error::ValidationFailed(String::from("code is wrong")));
+    }
+
+    if !consume(token.id, &transaction).await? {
+        return Err(Error::ValidationFailed(String::from("code is expired")));
+    }
+
+    //发送验证码之后可能已经被其他账号绑定
+    let bound = account::Entity::find()
+        .filter(account::Column::EMail.eq(e_mail.as_str()))
+        .filter(account::Column::Uid.ne(uid))
+        .one(&transaction)
+        .await?;
+    if bound.is_some() {
+        return Err(Error::Conflict);
+    }
+
+    account::Entity::update_many()
+        .filter(account::Column::Uid.eq(uid))
+        .col_expr(account::Column::EMail, Expr::value(Some(e_mail)))
+        .exec(&transaction)
+        .await?;
+
+    transaction.commit().await?;
+    Ok(())
+}
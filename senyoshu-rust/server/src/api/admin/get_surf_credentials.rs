@@ -0,0 +1,44 @@
+use axum::Json;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use tracing::instrument;
+
+use senyoshu_common::types::api::admin::SurfCredential;
+use senyoshu_common::types::error::Error;
+
+use crate::api::account::session::now;
+use crate::api::ApiResponse;
+use crate::api::auth::Admin;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::surf::{surf_credential, surf_server};
+
+pub async fn get_surf_credentials_api(_: Admin, Json(id): Json<i64>) -> ApiResponse<Vec<SurfCredential>> {
+    get_surf_credentials(id).await.into()
+}
+
+#[instrument]
+async fn get_surf_credentials(id: i64) -> Result<Vec<SurfCredential>, Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    surf_server::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound)?;
+
+    let now = now();
+    let credentials = surf_credential::Entity::find()
+        .filter(surf_credential::Column::ServerId.eq(id))
+        .filter(surf_credential::Column::Revoked.eq(false))
+        .order_by_asc(surf_credential::Column::Uid)
+        .all(db)
+        .await?;
+
+    //到期时间在不同数据库中的比较方式不同，这里直接过滤
+    Ok(credentials
+        .into_iter()
+        .filter(|it| it.expire_time > now)
+        .map(|it| SurfCredential {
+            uid: it.uid,
+            password: it.password,
+            expire_time: it.expire_time,
+        })
+        .collect())
+}
@@ -0,0 +1,49 @@
+use axum::Json;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use tracing::instrument;
+
+use senyoshu_common::types::api::admin::SurfServerConfig;
+use senyoshu_common::types::error::Error;
+
+use crate::api::account::session::now;
+use crate::api::ApiResponse;
+use crate::api::auth::Admin;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::surf::{surf_credential, surf_server};
+
+pub async fn get_surf_server_configs_api(_: Admin, Json(()): Json<()>) -> ApiResponse<Vec<SurfServerConfig>> {
+    get_surf_server_configs().await.into()
+}
+
+#[instrument]
+async fn get_surf_server_configs() -> Result<Vec<SurfServerConfig>, Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let now = now();
+
+    let servers = surf_server::Entity::find()
+        .order_by_asc(surf_server::Column::Id)
+        .all(db)
+        .await?;
+    let credentials = surf_credential::Entity::find()
+        .filter(surf_credential::Column::Revoked.eq(false))
+        .all(db)
+        .await?;
+
+    Ok(servers
+        .into_iter()
+        .map(|server| SurfServerConfig {
+            active_users: credentials
+                .iter()
+                .filter(|it| it.server_id == server.id && it.expire_time > now)
+                .count() as u32,
+            id: Some(server.id),
+            name: server.name,
+            region: server.region,
+            server: server.server,
+            server_port: server.server_port as u16,
+            method: server.method,
+            capacity: server.capacity as u32,
+            enabled: server.enabled,
+        })
+        .collect())
+}
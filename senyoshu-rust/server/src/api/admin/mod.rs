@@ -0,0 +1,52 @@
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
+use sea_orm::sea_query::SimpleExpr;
+
+use senyoshu_common::types::api::admin::AdminUserInfo;
+use senyoshu_common::types::error::Error;
+
+use crate::database::account;
+
+pub mod get_surf_credentials;
+pub mod get_surf_server_configs;
+pub mod rotate_surf_credentials;
+pub mod save_surf_server;
+pub mod search_users;
+pub mod set_restrict;
+pub mod set_user_role;
+pub mod set_vip;
+
+fn to_admin_user_info(account: account::Model) -> AdminUserInfo {
+    AdminUserInfo {
+        uid: account.uid,
+        register_date: account.register_date,
+        username: account.username,
+        e_mail: account.e_mail,
+        post_permission: account.post_permission,
+        restrict_user: account.restrict_user,
+        restrict_reason: account.restrict_reason,
+        content_maintainer: account.content_maintainer,
+        admin: account.admin,
+        vip: account.vip,
+    }
+}
+
+/// 修改某个用户的若干列，返回修改后的用户信息
+async fn update_account<C: ConnectionTrait>(
+    uid: i64,
+    values: Vec<(account::Column, SimpleExpr)>,
+    db: &C,
+) -> Result<AdminUserInfo, Error> {
+    let mut update = account::Entity::update_many().filter(account::Column::Uid.eq(uid));
+    for (column, value) in values {
+        update = update.col_expr(column, value);
+    }
+    if update.exec(db).await?.rows_affected == 0 {
+        return Err(Error::NotFound);
+    }
+
+    let account = account::Entity::find_by_id(uid)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound)?;
+    Ok(to_admin_user_info(account))
+}
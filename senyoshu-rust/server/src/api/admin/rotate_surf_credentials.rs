@@ -0,0 +1,34 @@
+use axum::Json;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm::prelude::Expr;
+use tracing::{info, instrument};
+
+use senyoshu_common::types::error::Error;
+
+use crate::api::ApiResponse;
+use crate::api::auth::Admin;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::surf::{surf_credential, surf_server};
+
+pub async fn rotate_surf_credentials_api(_: Admin, Json(id): Json<i64>) -> ApiResponse<u64> {
+    rotate_surf_credentials(id).await.into()
+}
+
+#[instrument]
+async fn rotate_surf_credentials(id: i64) -> Result<u64, Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    surf_server::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound)?;
+
+    let result = surf_credential::Entity::update_many()
+        .filter(surf_credential::Column::ServerId.eq(id))
+        .filter(surf_credential::Column::Revoked.eq(false))
+        .col_expr(surf_credential::Column::Revoked, Expr::value(true))
+        .exec(db)
+        .await?;
+    info!("{} credentials of surf server {id} are revoked", result.rows_affected);
+
+    Ok(result.rows_affected)
+}
@@ -0,0 +1,57 @@
+use axum::Json;
+use sea_orm::{ActiveModelTrait, EntityTrait};
+use sea_orm::ActiveValue::{NotSet, Set};
+use tracing::instrument;
+
+use senyoshu_common::types::api::admin::SurfServerConfig;
+use senyoshu_common::types::error::Error;
+
+use crate::api::account::session::now;
+use crate::api::ApiResponse;
+use crate::api::auth::Admin;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::surf::surf_server;
+
+pub async fn save_surf_server_api(_: Admin, Json(config): Json<SurfServerConfig>) -> ApiResponse<i64> {
+    save_surf_server(config).await.into()
+}
+
+#[instrument]
+async fn save_surf_server(config: SurfServerConfig) -> Result<i64, Error> {
+    if config.name.trim().is_empty() || config.server.trim().is_empty() || config.method.trim().is_empty() {
+        return Err(Error::ValidationFailed(String::from("name, server and method should not be empty")));
+    }
+    if config.server_port == 0 {
+        return Err(Error::ValidationFailed(String::from("server_port should not be 0")));
+    }
+    let capacity = i32::try_from(config.capacity)
+        .map_err(|_| Error::ValidationFailed(String::from("capacity is too large")))?;
+
+    let db = GLOBAL_DATABASE.get().unwrap();
+    if let Some(id) = config.id {
+        surf_server::Entity::find_by_id(id)
+            .one(db)
+            .await?
+            .ok_or(Error::NotFound)?;
+    }
+
+    let now = now();
+    let server = surf_server::ActiveModel {
+        id: config.id.map(Set).unwrap_or(NotSet),
+        name: Set(config.name.trim().to_string()),
+        region: Set(config.region.trim().to_string()),
+        server: Set(config.server.trim().to_string()),
+        server_port: Set(config.server_port.into()),
+        method: Set(config.method.trim().to_string()),
+        capacity: Set(capacity),
+        enabled: Set(config.enabled),
+        create_time: if config.id.is_some() { NotSet } else { Set(now) },
+        update_time: Set(now),
+    };
+    let server = match config.id {
+        Some(_) => server.update(db).await?,
+        None => server.insert(db).await?,
+    };
+
+    Ok(server.id)
+}
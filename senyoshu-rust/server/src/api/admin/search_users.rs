@@ -0,0 +1,56 @@
+use axum::Json;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use sea_orm::prelude::Expr;
+use sea_orm::sea_query::LikeExpr;
+use tracing::instrument;
+
+use senyoshu_common::types::api::admin::{AdminUserInfo, MAX_USER_QUERY_LIMIT, UserQuery};
+use senyoshu_common::types::error::Error;
+
+use crate::api::admin::to_admin_user_info;
+use crate::api::ApiResponse;
+use crate::api::auth::Admin;
+use crate::database::account;
+use crate::database::database::GLOBAL_DATABASE;
+
+pub async fn search_users_api(_: Admin, Json(query): Json<UserQuery>) -> ApiResponse<Vec<AdminUserInfo>> {
+    search_users(query).await.into()
+}
+
+#[instrument]
+async fn search_users(query: UserQuery) -> Result<Vec<AdminUserInfo>, Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+
+    let mut select = account::Entity::find();
+    let keyword = query.keyword.trim();
+    if !keyword.is_empty() {
+        let pattern = LikeExpr::new(format!("%{}%", escape_like(keyword))).escape('\\');
+        let mut condition = Condition::any()
+            .add(Expr::col((account::Entity, account::Column::Username)).like(pattern));
+        if let Ok(uid) = keyword.parse::<i64>() {
+            condition = condition.add(account::Column::Uid.eq(uid));
+        }
+        select = select.filter(condition);
+    }
+
+    let accounts = select
+        .order_by_asc(account::Column::Uid)
+        .offset(query.offset)
+        .limit(query.limit.clamp(1, MAX_USER_QUERY_LIMIT))
+        .all(db)
+        .await?;
+
+    Ok(accounts.into_iter().map(to_admin_user_info).collect())
+}
+
+/// 关键字中的 `%` 和 `_` 按普通字符匹配
+fn escape_like(keyword: &str) -> String {
+    let mut escaped = String::with_capacity(keyword.len());
+    for c in keyword.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
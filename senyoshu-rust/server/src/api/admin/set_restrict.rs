@@ -0,0 +1,53 @@
+use axum::Json;
+use sea_orm::prelude::Expr;
+use sea_orm::TransactionTrait;
+use tracing::instrument;
+
+use senyoshu_common::types::api::admin::{AdminUserInfo, MAX_RESTRICT_REASON_LEN};
+use senyoshu_common::types::error::Error;
+
+use crate::api::admin::update_account;
+use crate::api::ApiResponse;
+use crate::api::auth::Admin;
+use crate::database::account;
+use crate::database::database::GLOBAL_DATABASE;
+
+pub async fn set_restrict_api(
+    admin: Admin,
+    Json((uid, reason)): Json<(i64, Option<String>)>,
+) -> ApiResponse<AdminUserInfo> {
+    set_restrict(admin.user_info.uid, uid, reason).await.into()
+}
+
+#[instrument]
+async fn set_restrict(operator: i64, uid: i64, reason: Option<String>) -> Result<AdminUserInfo, Error> {
+    let reason = reason.map(|reason| reason.trim().to_string());
+    if let Some(reason) = &reason {
+        if operator == uid {
+            return Err(Error::ValidationFailed(String::from("can not restrict yourself")));
+        }
+        if reason.is_empty() {
+            return Err(Error::ValidationFailed(String::from("reason is required")));
+        }
+        if reason.chars().count() > MAX_RESTRICT_REASON_LEN {
+            return Err(Error::ValidationFailed(format!(
+                "reason is longer than {MAX_RESTRICT_REASON_LEN} characters"
+            )));
+        }
+    }
+
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
+    let user = update_account(
+        uid,
+        Vec::from([
+            (account::Column::RestrictUser, Expr::value(reason.is_some())),
+            (account::Column::RestrictReason, Expr::value(reason)),
+        ]),
+        &transaction,
+    )
+    .await?;
+    transaction.commit().await?;
+
+    Ok(user)
+}
@@ -0,0 +1,43 @@
+use axum::Json;
+use sea_orm::prelude::Expr;
+use sea_orm::TransactionTrait;
+use tracing::instrument;
+
+use senyoshu_common::types::api::admin::{AdminUserInfo, Role};
+use senyoshu_common::types::error::Error;
+
+use crate::api::admin::update_account;
+use crate::api::ApiResponse;
+use crate::api::auth::Admin;
+use crate::database::account;
+use crate::database::database::GLOBAL_DATABASE;
+
+pub async fn set_user_role_api(
+    admin: Admin,
+    Json((uid, role, enable)): Json<(i64, Role, bool)>,
+) -> ApiResponse<AdminUserInfo> {
+    set_user_role(admin.user_info.uid, uid, role, enable).await.into()
+}
+
+#[instrument]
+async fn set_user_role(operator: i64, uid: i64, role: Role, enable: bool) -> Result<AdminUserInfo, Error> {
+    //不能撤销自己的管理员，避免没有管理员
+    if operator == uid && role == Role::Admin && !enable {
+        return Err(Error::ValidationFailed(String::from(
+            "can not revoke admin from yourself",
+        )));
+    }
+
+    let column = match role {
+        Role::PostPermission => account::Column::PostPermission,
+        Role::ContentMaintainer => account::Column::ContentMaintainer,
+        Role::Admin => account::Column::Admin,
+    };
+
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
+    let user = update_account(uid, Vec::from([(column, Expr::value(enable))]), &transaction).await?;
+    transaction.commit().await?;
+
+    Ok(user)
+}
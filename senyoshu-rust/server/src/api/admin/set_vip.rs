@@ -0,0 +1,48 @@
+use axum::Json;
+use chrono::TimeDelta;
+use sea_orm::{EntityTrait, TransactionTrait};
+use sea_orm::prelude::Expr;
+use tracing::instrument;
+
+use senyoshu_common::types::api::admin::{AdminUserInfo, VipChange};
+use senyoshu_common::types::error::Error;
+
+use crate::api::account::session::now;
+use crate::api::admin::update_account;
+use crate::api::ApiResponse;
+use crate::api::auth::Admin;
+use crate::database::account;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::surf::surf_credential;
+
+pub async fn set_vip_api(_: Admin, Json((uid, change)): Json<(i64, VipChange)>) -> ApiResponse<AdminUserInfo> {
+    set_vip(uid, change).await.into()
+}
+
+#[instrument]
+async fn set_vip(uid: i64, change: VipChange) -> Result<AdminUserInfo, Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
+
+    let account = account::Entity::find_by_id(uid)
+        .one(&transaction)
+        .await?
+        .ok_or(Error::NotFound)?;
+
+    let vip = match change {
+        VipChange::Until(until) => Some(until),
+        VipChange::ExtendDays(days) => {
+            //已经过期的从现在开始计算
+            let now = now();
+            let from = account.vip.filter(|vip| *vip > now).unwrap_or(now);
+            Some(from + TimeDelta::days(days.into()))
+        }
+        VipChange::Revoke => None,
+    };
+
+    let user = update_account(uid, Vec::from([(account::Column::Vip, Expr::value(vip))]), &transaction).await?;
+    surf_credential::sync_vip(uid, vip, &transaction).await?;
+    transaction.commit().await?;
+
+    Ok(user)
+}
@@ -0,0 +1,160 @@
+use std::ops::Deref;
+
+use axum::async_trait;
+use axum::extract::FromRequestParts;
+use axum::response::{IntoResponse, Response};
+use http::header::AUTHORIZATION;
+use http::request::Parts;
+use http::HeaderMap;
+
+use senyoshu_common::types::api::account::{Token, UserInfo};
+use senyoshu_common::types::error::Error;
+
+use crate::api::account::get_user_info;
+use crate::api::account::session::now;
+use crate::api::ApiResponse;
+use crate::database::database::GLOBAL_DATABASE;
+
+/// 已登录的用户，从 `Authorization: Bearer <uid>.<token>` 中解析并校验 token
+///
+/// 校验失败时直接返回 [`Error::NotAuth`]，不会执行处理函数
+#[derive(Clone, Debug)]
+pub struct AuthUser {
+    pub token: Token,
+    pub user_info: UserInfo,
+}
+
+/// 鉴权失败时的响应，与处理函数返回的错误格式相同
+#[derive(Debug)]
+pub struct AuthRejection(pub Error);
+
+impl IntoResponse for AuthRejection {
+    fn into_response(self) -> Response {
+        ApiResponse::<()>(Err(self.0)).into_response()
+    }
+}
+
+pub(crate) fn bearer_token(headers: &HeaderMap) -> Option<Token> {
+    let value = headers.get(AUTHORIZATION)?.to_str().ok()?;
+    let credential = value.strip_prefix("Bearer ")?;
+    Token::from_credential(credential.trim())
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for AuthUser {
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let token = bearer_token(&parts.headers).ok_or(AuthRejection(Error::NotAuth))?;
+        let db = GLOBAL_DATABASE.get().unwrap();
+        let user_info = get_user_info(token.to_owned(), db)
+            .await
+            .map_err(AuthRejection)?;
+        Ok(AuthUser { token, user_info })
+    }
+}
+
+async fn guard<S: Send + Sync>(
+    parts: &mut Parts,
+    state: &S,
+    check: fn(&UserInfo) -> bool,
+) -> Result<AuthUser, AuthRejection> {
+    let user = AuthUser::from_request_parts(parts, state).await?;
+    if check(&user.user_info) {
+        Ok(user)
+    } else {
+        Err(AuthRejection(Error::PermissionDenied))
+    }
+}
+
+/// 没有被限制的审核者，否则返回 [`Error::PermissionDenied`]
+#[derive(Clone, Debug)]
+pub struct ContentMaintainer(pub AuthUser);
+
+/// 没有被限制且可以提交修改的用户，审核者总是可以提交
+#[derive(Clone, Debug)]
+pub struct PostPermission(pub AuthUser);
+
+/// 管理员，可以修改其他用户的权限
+#[derive(Clone, Debug)]
+pub struct Admin(pub AuthUser);
+
+/// 会员没有过期的用户
+#[derive(Clone, Debug)]
+pub struct Vip(pub AuthUser);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ContentMaintainer {
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        guard(parts, state, |user| !user.restrict_user && user.content_maintainer)
+            .await
+            .map(Self)
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for PostPermission {
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        guard(parts, state, |user| {
+            !user.restrict_user && (user.post_permission || user.content_maintainer)
+        })
+            .await
+            .map(Self)
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Admin {
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        guard(parts, state, |user| user.admin).await.map(Self)
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Vip {
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        guard(parts, state, |user| user.vip.is_some_and(|vip| vip > now()))
+            .await
+            .map(Self)
+    }
+}
+
+impl Deref for ContentMaintainer {
+    type Target = AuthUser;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Deref for PostPermission {
+    type Target = AuthUser;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Deref for Admin {
+    type Target = AuthUser;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Deref for Vip {
+    type Target = AuthUser;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -0,0 +1,71 @@
+use axum::Json;
+use sea_orm::{ActiveModelTrait, EntityTrait, Set, TransactionTrait};
+use tracing::instrument;
+
+use senyoshu_common::types::api::account::UserInfo;
+use senyoshu_common::types::api::comment::{Comment, CommentTarget, NewComment};
+use senyoshu_common::types::error::Error;
+
+use crate::api::ApiResponse;
+use crate::api::auth::PostPermission;
+use crate::api::comment::{target_of, to_comments, validate_content};
+use crate::database::comment;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::dic::{word_history, words};
+
+pub async fn create_comment_api(user: PostPermission, Json(new_comment): Json<NewComment>) -> ApiResponse<Comment> {
+    create_comment(user.0.user_info, new_comment).await.into()
+}
+
+#[instrument]
+async fn create_comment(user_info: UserInfo, new_comment: NewComment) -> Result<Comment, Error> {
+    let content = validate_content(new_comment.content.as_str())?;
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
+
+    let (wid, pid) = match new_comment.target {
+        CommentTarget::Word(wid) => {
+            //已删除的词条不能再评论
+            words::Entity::find_by_id(wid)
+                .one(&transaction)
+                .await?
+                .filter(|it| it.word_define.is_some())
+                .ok_or(Error::WordIsNotExist)?;
+            (Some(wid.0), None)
+        }
+        CommentTarget::ChangeRequest(pid) => {
+            word_history::Entity::find_by_id(pid)
+                .one(&transaction)
+                .await?
+                .ok_or(Error::NotFound)?;
+            (None, Some(pid))
+        }
+    };
+    if let Some(parent) = new_comment.parent {
+        let parent = comment::Entity::find_by_id(parent)
+            .one(&transaction)
+            .await?
+            .ok_or(Error::NotFound)?;
+        if target_of(&parent) != new_comment.target {
+            return Err(Error::ValidationFailed(String::from(
+                "parent comment belongs to another target",
+            )));
+        }
+    }
+
+    let model = comment::ActiveModel {
+        wid: Set(wid),
+        pid: Set(pid),
+        parent: Set(new_comment.parent),
+        author: Set(user_info.uid),
+        content: Set(content),
+        deleted: Set(false),
+        edit_time: Set(None),
+        ..Default::default()
+    }
+        .insert(&transaction)
+        .await?;
+    let comment = to_comments(Vec::from([model]), &transaction).await?.remove(0);
+    transaction.commit().await?;
+    Ok(comment)
+}
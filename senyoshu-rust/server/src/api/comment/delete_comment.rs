@@ -0,0 +1,37 @@
+use axum::Json;
+use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, Set};
+use tracing::instrument;
+
+use senyoshu_common::types::api::account::UserInfo;
+use senyoshu_common::types::error::Error;
+
+use crate::api::ApiResponse;
+use crate::api::auth::AuthUser;
+use crate::database::comment;
+use crate::database::database::GLOBAL_DATABASE;
+
+pub async fn delete_comment_api(user: AuthUser, Json(id): Json<i64>) -> ApiResponse<()> {
+    delete_comment(user.user_info, id).await.into()
+}
+
+/// 被限制的用户仍然可以删除自己的评论
+#[instrument]
+async fn delete_comment(user_info: UserInfo, id: i64) -> Result<(), Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+
+    let model = comment::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .filter(|it| !it.deleted)
+        .ok_or(Error::NotFound)?;
+    let is_maintainer = user_info.content_maintainer && !user_info.restrict_user;
+    if model.author != user_info.uid && !is_maintainer {
+        return Err(Error::PermissionDenied);
+    }
+
+    let mut model = model.into_active_model();
+    model.content = Set(String::new());
+    model.deleted = Set(true);
+    model.update(db).await?;
+    Ok(())
+}
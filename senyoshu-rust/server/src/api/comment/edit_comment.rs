@@ -0,0 +1,45 @@
+use axum::Json;
+use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, Set, TransactionTrait};
+use tracing::instrument;
+
+use senyoshu_common::types::api::account::UserInfo;
+use senyoshu_common::types::api::comment::Comment;
+use senyoshu_common::types::error::Error;
+
+use crate::api::account::session::now;
+use crate::api::ApiResponse;
+use crate::api::auth::PostPermission;
+use crate::api::comment::{to_comments, validate_content};
+use crate::database::comment;
+use crate::database::database::GLOBAL_DATABASE;
+
+pub async fn edit_comment_api(
+    user: PostPermission,
+    Json((id, content)): Json<(i64, String)>,
+) -> ApiResponse<Comment> {
+    edit_comment(user.0.user_info, id, content).await.into()
+}
+
+#[instrument]
+async fn edit_comment(user_info: UserInfo, id: i64, content: String) -> Result<Comment, Error> {
+    let content = validate_content(content.as_str())?;
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
+
+    let model = comment::Entity::find_by_id(id)
+        .one(&transaction)
+        .await?
+        .filter(|it| !it.deleted)
+        .ok_or(Error::NotFound)?;
+    if model.author != user_info.uid {
+        return Err(Error::PermissionDenied);
+    }
+
+    let mut model = model.into_active_model();
+    model.content = Set(content);
+    model.edit_time = Set(Some(now()));
+    let model = model.update(&transaction).await?;
+    let comment = to_comments(Vec::from([model]), &transaction).await?.remove(0);
+    transaction.commit().await?;
+    Ok(comment)
+}
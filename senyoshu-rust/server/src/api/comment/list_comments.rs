@@ -0,0 +1,68 @@
+use std::collections::{BTreeSet, HashMap};
+
+use axum::Json;
+use itertools::Itertools;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use tracing::instrument;
+
+use senyoshu_common::types::api::comment::{Comment, CommentQuery, CommentTarget, MAX_COMMENT_LIMIT};
+use senyoshu_common::types::error::Error;
+
+use crate::api::ApiResponse;
+use crate::api::comment::to_comments;
+use crate::database::comment;
+use crate::database::database::GLOBAL_DATABASE;
+
+pub async fn list_comments_api(Json(query): Json<CommentQuery>) -> ApiResponse<Vec<Comment>> {
+    list_comments(query).await.into()
+}
+
+/// 已删除的评论只在还有显示的回复时返回，用于保持讨论的结构
+#[instrument]
+async fn list_comments(query: CommentQuery) -> Result<Vec<Comment>, Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let limit = match query.limit {
+        0 => MAX_COMMENT_LIMIT,
+        limit => limit.min(MAX_COMMENT_LIMIT),
+    };
+
+    let select = match query.target {
+        CommentTarget::Word(wid) => comment::Entity::find().filter(comment::Column::Wid.eq(wid.0)),
+        CommentTarget::ChangeRequest(pid) => comment::Entity::find().filter(comment::Column::Pid.eq(pid)),
+    };
+    //先只读取结构判断哪些需要显示，再读取这一页的内容
+    let nodes = select
+        .select_only()
+        .column(comment::Column::Id)
+        .column(comment::Column::Parent)
+        .column(comment::Column::Deleted)
+        .into_tuple::<(i64, Option<i64>, bool)>()
+        .all(db)
+        .await?;
+    let ids = visible(nodes)
+        .into_iter()
+        .filter(|id| query.after.is_none_or(|after| *id > after))
+        .take(limit as usize)
+        .collect_vec();
+
+    let models = comment::Entity::find()
+        .filter(comment::Column::Id.is_in(ids))
+        .order_by_asc(comment::Column::Id)
+        .all(db)
+        .await?;
+    to_comments(models, db).await
+}
+
+/// 没有删除的评论和它们的所有上级评论
+fn visible(nodes: Vec<(i64, Option<i64>, bool)>) -> BTreeSet<i64> {
+    let parents: HashMap<i64, Option<i64>> = nodes.iter().map(|(id, parent, _)| (*id, *parent)).collect();
+    let mut visible = BTreeSet::new();
+    for (id, _, _) in nodes.iter().filter(|(_, _, deleted)| !deleted) {
+        let mut current = Some(*id);
+        //上级已经加入过时，更上面的也已经加入
+        while let Some(id) = current.filter(|it| visible.insert(*it)) {
+            current = parents.get(&id).copied().flatten();
+        }
+    }
+    visible
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QuerySelect};
+
+use senyoshu_common::types::api::comment::{Comment, CommentTarget, MAX_COMMENT_LEN};
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::word::wid::WordIdentity;
+
+use crate::database::{account, comment};
+
+pub mod create_comment;
+pub mod delete_comment;
+pub mod edit_comment;
+pub mod list_comments;
+
+fn target_of(model: &comment::Model) -> CommentTarget {
+    match (model.wid, model.pid) {
+        (Some(wid), _) => CommentTarget::Word(WordIdentity::from(wid)),
+        (None, pid) => CommentTarget::ChangeRequest(pid.unwrap_or_default()),
+    }
+}
+
+/// 去掉首尾空白，不能为空也不能超过 [`MAX_COMMENT_LEN`]
+fn validate_content(content: &str) -> Result<String, Error> {
+    let content = content.trim();
+    if content.is_empty() {
+        return Err(Error::ValidationFailed(String::from("comment should not be empty")));
+    }
+    if content.chars().count() > MAX_COMMENT_LEN {
+        return Err(Error::ValidationFailed(format!(
+            "comment should not be longer than {MAX_COMMENT_LEN}"
+        )));
+    }
+    Ok(content.to_string())
+}
+
+/// 补上作者的用户名
+pub(crate) async fn to_comments<C: ConnectionTrait>(models: Vec<comment::Model>, db: &C) -> Result<Vec<Comment>, Error> {
+    let authors = models.iter().map(|it| it.author).unique().collect_vec();
+    let names: HashMap<i64, String> = account::Entity::find()
+        .select_only()
+        .column(account::Column::Uid)
+        .column(account::Column::Username)
+        .filter(account::Column::Uid.is_in(authors))
+        .into_tuple::<(i64, String)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    Ok(models
+        .into_iter()
+        .map(|it| Comment {
+            id: it.id,
+            target: target_of(&it),
+            parent: it.parent,
+            author: it.author,
+            author_name: names.get(&it.author).cloned().unwrap_or_default(),
+            content: it.content,
+            deleted: it.deleted,
+            create_time: it.create_time,
+            edit_time: it.edit_time,
+        })
+        .collect())
+}
@@ -2,41 +2,39 @@ use axum::Json;
 use sea_orm::ActiveModelTrait;
 use sea_orm::ActiveValue::Set;
 use sea_orm::TransactionTrait;
-use tracing::instrument;
+use tracing::{error, instrument};
 
-use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::api::account::UserInfo;
+use senyoshu_common::types::error::Error;
 use senyoshu_common::types::state::State;
 use senyoshu_common::types::word::wid::WordIdentity;
 use senyoshu_common::types::word::word_entry::WordDefine;
 
-use crate::api::account::get_user_info;
+use crate::api::ApiResponse;
+use crate::api::auth::PostPermission;
 use crate::database::database::GLOBAL_DATABASE;
 use crate::database::dic::word_history;
 use crate::database::dic::words;
+use crate::push::publish_dic;
 
 pub async fn create_word_api(
-    Json((token, new_word_define)): Json<(Token, WordDefine)>,
-) -> Json<Option<WordIdentity>> {
-    Json(create_word(token, new_word_define).await)
+    user: PostPermission,
+    Json(new_word_define): Json<WordDefine>,
+) -> ApiResponse<WordIdentity> {
+    create_word(user.0.user_info, new_word_define).await.into()
 }
 
 #[instrument]
-async fn create_word(token: Token, new_word_define: WordDefine) -> Option<WordIdentity> {
+async fn create_word(user_info: UserInfo, new_word_define: WordDefine) -> Result<WordIdentity, Error> {
     let db = GLOBAL_DATABASE.get().unwrap();
-    let transaction = db.begin().await.ok()?;
-
-    let user_info = get_user_info(token, &transaction).await?;
-    if (!user_info.post_permission) && (!user_info.content_maintainer) {
-        return None;
-    }
+    let transaction = db.begin().await?;
 
     let word = words::ActiveModel {
         word_define: Set(Option::from(new_word_define.to_owned())),
         ..Default::default()
     }
         .insert(&transaction)
-        .await
-        .ok()?;
+        .await?;
 
     word_history::ActiveModel {
         author: Set(user_info.uid),
@@ -47,12 +45,9 @@ async fn create_word(token: Token, new_word_define: WordDefine) -> Option<WordId
     }
         .insert(&transaction)
         .await
-        .map_err(|it| {
-            println!("{}", it.to_string());
-            it
-        })
-        .ok()?;
+        .inspect_err(|err| error!("failed to insert word history: {err}"))?;
 
-    transaction.commit().await.ok()?;
-    Some(word.wid)
+    transaction.commit().await?;
+    publish_dic(Vec::from([(word.wid, word.word_define)]));
+    Ok(word.wid)
 }
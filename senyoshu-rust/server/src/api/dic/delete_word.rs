@@ -1,26 +1,32 @@
 use axum::Json;
-use sea_orm::{ActiveModelTrait, IntoSimpleExpr, QueryFilter};
+use sea_orm::{ActiveModelTrait, IntoSimpleExpr, QueryFilter, Set, TransactionTrait};
 use sea_orm::ColumnTrait;
 use sea_orm::EntityTrait;
 use sea_orm::prelude::Expr;
 
-use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::moderation::ModerationAction;
+use senyoshu_common::types::word::wid::WordIdentity;
 use senyoshu_common::types::word::word_entry::WordDefine;
 
-use crate::api::account::get_user_info;
+use crate::api::ApiResponse;
+use crate::api::auth::ContentMaintainer;
+use crate::api::dic::set_adopted::review_comment;
 use crate::database::database::GLOBAL_DATABASE;
-use crate::database::dic::words;
+use crate::database::dic::{moderation_log, words};
+use crate::push::publish_dic;
 
-pub async fn delete_word_api(Json((token, wid)): Json<(Token, i64)>) -> Json<bool> {
-    Json(delete_word(token, wid).await.is_some())
+pub async fn delete_word_api(
+    user: ContentMaintainer,
+    Json((wid, comment)): Json<(i64, Option<String>)>,
+) -> ApiResponse<()> {
+    delete_word(user.0.user_info.uid, wid, comment).await.into()
 }
 
-async fn delete_word(token: Token, wid: i64) -> Option<()> {
+async fn delete_word(uid: i64, wid: i64, comment: Option<String>) -> Result<(), Error> {
+    let comment = review_comment(comment)?;
     let db = GLOBAL_DATABASE.get().unwrap();
-    let user_info = get_user_info(token, db).await?;
-    if !user_info.content_maintainer {
-        return None;
-    }
+    let transaction = db.begin().await?;
 
     if words::Entity::update_many()
         .filter(words::Column::Wid.eq(wid))
@@ -29,14 +35,21 @@ async fn delete_word(token: Token, wid: i64) -> Option<()> {
             words::Column::UpdateDate,
             Expr::current_timestamp().into_simple_expr(),
         )
-        .exec(db)
-        .await
-        .ok()?
+        .exec(&transaction)
+        .await?
         .rows_affected
         == 0
     {
-        return None;
+        return Err(Error::WordIsNotExist);
+    }
+    moderation_log::ActiveModel {
+        comment: Set(comment),
+        ..moderation_log::entry(uid, ModerationAction::Delete, None, Some(WordIdentity::from(wid)))
     }
+        .insert(&transaction)
+        .await?;
+    transaction.commit().await?;
 
-    Some(())
+    publish_dic(Vec::from([(WordIdentity::from(wid), None)]));
+    Ok(())
 }
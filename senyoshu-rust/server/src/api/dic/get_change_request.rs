@@ -4,27 +4,30 @@ use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use sea_orm::ActiveModelTrait;
 use tracing::instrument;
 
-use senyoshu_common::types::api::account::Token;
 use senyoshu_common::types::api::api::WordHistoryEntry;
+use senyoshu_common::types::error::Error;
 use senyoshu_common::types::state::State;
 
+use crate::api::ApiResponse;
+use crate::api::auth::ContentMaintainer;
 use crate::database::database::GLOBAL_DATABASE;
 use crate::database::dic::word_history;
 
-pub async fn get_change_request_api(Json(token): Json<Token>) -> Json<Vec<WordHistoryEntry>> {
-    Json(get_change_request(token).await.unwrap_or_default())
+pub async fn get_change_request_api(
+    _: ContentMaintainer,
+    Json(()): Json<()>,
+) -> ApiResponse<Vec<WordHistoryEntry>> {
+    get_change_request().await.into()
 }
 
-//todo: 鉴权
 #[instrument]
-async fn get_change_request(token: Token) -> Option<Vec<WordHistoryEntry>> {
+async fn get_change_request() -> Result<Vec<WordHistoryEntry>, Error> {
     let db = GLOBAL_DATABASE.get().unwrap();
 
     let rv = word_history::Entity::find()
         .filter(word_history::Column::State.eq(State::Pending))
         .all(db)
-        .await
-        .ok()?
+        .await?
         .into_iter()
         .map(|it| WordHistoryEntry {
             pid: it.pid,
@@ -34,5 +37,5 @@ async fn get_change_request(token: Token) -> Option<Vec<WordHistoryEntry>> {
         })
         .collect_vec();
 
-    Some(rv)
+    Ok(rv)
 }
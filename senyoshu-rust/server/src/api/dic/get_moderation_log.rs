@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use axum::Json;
+use itertools::Itertools;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use tracing::instrument;
+
+use senyoshu_common::types::api::dic::{MAX_MODERATION_LOG_LIMIT, ModerationLogEntry, ModerationLogQuery};
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::word::wid::WordIdentity;
+
+use crate::api::ApiResponse;
+use crate::api::auth::ContentMaintainer;
+use crate::database::account;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::dic::moderation_log;
+
+pub async fn get_moderation_log_api(
+    _: ContentMaintainer,
+    Json(query): Json<ModerationLogQuery>,
+) -> ApiResponse<Vec<ModerationLogEntry>> {
+    get_moderation_log(query).await.into()
+}
+
+#[instrument]
+async fn get_moderation_log(query: ModerationLogQuery) -> Result<Vec<ModerationLogEntry>, Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let limit = match query.limit {
+        0 => MAX_MODERATION_LOG_LIMIT,
+        limit => limit.min(MAX_MODERATION_LOG_LIMIT),
+    };
+
+    let mut select = moderation_log::Entity::find();
+    if let Some(before) = query.before {
+        select = select.filter(moderation_log::Column::Id.lt(before));
+    }
+    if let Some(actor) = query.actor {
+        select = select.filter(moderation_log::Column::Actor.eq(actor));
+    }
+    if let Some(action) = query.action {
+        select = select.filter(moderation_log::Column::Action.eq(action));
+    }
+    if let Some(wid) = query.wid {
+        select = select.filter(moderation_log::Column::Wid.eq(wid.0));
+    }
+    let logs = select
+        .order_by_desc(moderation_log::Column::Id)
+        .limit(limit)
+        .all(db)
+        .await?;
+
+    //已注销的账号没有用户名
+    let actors = logs.iter().map(|it| it.actor).unique().collect_vec();
+    let names: HashMap<i64, String> = account::Entity::find()
+        .select_only()
+        .column(account::Column::Uid)
+        .column(account::Column::Username)
+        .filter(account::Column::Uid.is_in(actors))
+        .into_tuple::<(i64, String)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    Ok(logs
+        .into_iter()
+        .map(|it| ModerationLogEntry {
+            id: it.id,
+            actor: it.actor,
+            actor_name: names.get(&it.actor).cloned().unwrap_or_default(),
+            action: it.action,
+            pid: it.pid,
+            wid: it.wid.map(WordIdentity::from),
+            comment: it.comment,
+            create_time: it.create_time,
+        })
+        .collect())
+}
@@ -0,0 +1,42 @@
+use axum::Json;
+use itertools::Itertools;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use tracing::instrument;
+
+use senyoshu_common::types::api::api::PostList;
+use senyoshu_common::types::error::Error;
+
+use crate::api::ApiResponse;
+use crate::api::auth::AuthUser;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::dic::word_history;
+
+pub async fn get_my_submissions_api(user: AuthUser, Json(()): Json<()>) -> ApiResponse<Vec<PostList>> {
+    get_my_submissions(user.user_info.uid).await.into()
+}
+
+#[instrument]
+async fn get_my_submissions(uid: i64) -> Result<Vec<PostList>, Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+
+    let rv = word_history::Entity::find()
+        .filter(word_history::Column::Author.eq(uid))
+        .order_by_desc(word_history::Column::Pid)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|it| PostList {
+            pid: it.pid,
+            post_date: it.post_date,
+            author: it.author,
+            wid: it.wid,
+            word: it.word_define.word,
+            state: it.state,
+            reviewer: it.reviewer,
+            review_date: it.review_date,
+            review_comment: it.review_comment,
+        })
+        .collect_vec();
+
+    Ok(rv)
+}
@@ -3,27 +3,29 @@ use sea_orm::ActiveModelTrait;
 use sea_orm::EntityTrait;
 use tracing::instrument;
 
+use senyoshu_common::types::error::Error;
 use senyoshu_common::types::word::word_entry::WordEntry;
 
+use crate::api::ApiResponse;
 use crate::database::database::GLOBAL_DATABASE;
 use crate::database::dic::word_history;
 
-pub async fn get_word_by_pid_api(Json(pid): Json<i64>) -> Json<Option<WordEntry>> {
-    Json(get_word_by_pid(pid).await)
+pub async fn get_word_by_pid_api(Json(pid): Json<i64>) -> ApiResponse<WordEntry> {
+    get_word_by_pid(pid).await.into()
 }
 
 #[instrument]
-async fn get_word_by_pid(pid: i64) -> Option<WordEntry> {
+async fn get_word_by_pid(pid: i64) -> Result<WordEntry, Error> {
     let db = GLOBAL_DATABASE.get().unwrap();
 
     let rv = word_history::Entity::find_by_id(pid)
         .one(db)
-        .await
-        .ok()?
+        .await?
         .map(|it| WordEntry {
             id: it.wid,
             word_define: it.word_define,
-        });
+        })
+        .ok_or(Error::NotFound)?;
 
-    rv
+    Ok(rv)
 }
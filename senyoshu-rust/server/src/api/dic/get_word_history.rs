@@ -5,24 +5,25 @@ use sea_orm::ActiveModelTrait;
 use tracing::instrument;
 
 use senyoshu_common::types::api::api::WordHistoryEntry;
+use senyoshu_common::types::error::Error;
 
+use crate::api::ApiResponse;
 use crate::database::database::GLOBAL_DATABASE;
 use crate::database::dic::word_history;
 
-pub async fn get_word_history_api(Json(wid): Json<i64>) -> Json<Vec<WordHistoryEntry>> {
-    Json(get_word_history(wid).await.unwrap_or_default())
+pub async fn get_word_history_api(Json(wid): Json<i64>) -> ApiResponse<Vec<WordHistoryEntry>> {
+    get_word_history(wid).await.into()
 }
 
 #[instrument]
-async fn get_word_history(wid: i64) -> Option<Vec<WordHistoryEntry>> {
+async fn get_word_history(wid: i64) -> Result<Vec<WordHistoryEntry>, Error> {
     let db = GLOBAL_DATABASE.get().unwrap();
 
     let rv = word_history::Entity::find()
         .filter(word_history::Column::Wid.eq(wid))
         .order_by_desc(word_history::Column::Pid)
         .all(db)
-        .await
-        .ok()?
+        .await?
         .into_iter()
         .map(|it| WordHistoryEntry {
             pid: it.pid,
@@ -32,5 +33,5 @@ async fn get_word_history(wid: i64) -> Option<Vec<WordHistoryEntry>> {
         })
         .collect_vec();
 
-    Some(rv)
+    Ok(rv)
 }
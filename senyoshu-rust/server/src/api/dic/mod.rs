@@ -1,6 +1,8 @@
 pub mod create_word;
 pub mod delete_word;
 pub mod get_change_request;
+pub mod get_moderation_log;
+pub mod get_my_submissions;
 pub mod get_word_by_pid;
 pub mod get_word_history;
 pub mod post_word;
@@ -1,31 +1,29 @@
 use axum::Json;
 use sea_orm::{ActiveModelTrait, TransactionTrait};
 use sea_orm::ActiveValue::Set;
-use tracing::instrument;
+use tracing::{error, instrument};
 
-use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::api::account::UserInfo;
+use senyoshu_common::types::error::Error;
 use senyoshu_common::types::state::State;
 use senyoshu_common::types::word::word_entry::WordEntry;
 
-use crate::api::account::get_user_info;
+use crate::api::ApiResponse;
+use crate::api::auth::PostPermission;
 use crate::database::database::GLOBAL_DATABASE;
 use crate::database::dic::word_history;
 
 pub async fn post_word_api(
-    Json((token, update_word_entry)): Json<(Token, WordEntry)>,
-) -> Json<bool> {
-    Json(post_word(token, update_word_entry).await.is_some())
+    user: PostPermission,
+    Json(update_word_entry): Json<WordEntry>,
+) -> ApiResponse<()> {
+    post_word(user.0.user_info, update_word_entry).await.into()
 }
 
 #[instrument]
-async fn post_word(token: Token, update_word_entry: WordEntry) -> Option<()> {
+async fn post_word(user_info: UserInfo, update_word_entry: WordEntry) -> Result<(), Error> {
     let db = GLOBAL_DATABASE.get().unwrap();
-    let transaction = db.begin().await.ok()?;
-
-    let user_info = get_user_info(token, &transaction).await?;
-    if (!user_info.post_permission) && (!user_info.content_maintainer) {
-        return None;
-    }
+    let transaction = db.begin().await?;
 
     word_history::ActiveModel {
         author: Set(user_info.uid),
@@ -36,12 +34,8 @@ async fn post_word(token: Token, update_word_entry: WordEntry) -> Option<()> {
     }
         .insert(&transaction)
         .await
-        .map_err(|it| {
-            println!("{}", it.to_string());
-            it
-        })
-        .ok()?;
+        .inspect_err(|err| error!("failed to insert word history: {err}"))?;
 
-    transaction.commit().await.ok()?;
-    Some(())
+    transaction.commit().await?;
+    Ok(())
 }
@@ -6,53 +6,102 @@ use sea_orm::prelude::Expr;
 use sea_orm::TransactionTrait;
 use tracing::instrument;
 
-use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::api::account::UserInfo;
+use senyoshu_common::types::api::dic::MAX_REVIEW_COMMENT_LEN;
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::moderation::ModerationAction;
 use senyoshu_common::types::state::State;
 
-use crate::api::account::get_user_info;
+use crate::api::ApiResponse;
+use crate::api::auth::AuthUser;
 use crate::database::database::GLOBAL_DATABASE;
-use crate::database::dic::word_history;
+use crate::database::dic::{moderation_log, word_history};
 use crate::database::dic::words;
+use crate::push::publish_dic;
 
-pub async fn set_adopted_api(Json((token, pid, state)): Json<(Token, i64, State)>) -> Json<bool> {
-    if let State::Pending = state {
-        return Json(false);
-    }
+pub async fn set_adopted_api(
+    user: AuthUser,
+    Json((pid, state, comment)): Json<(i64, State, Option<String>)>,
+) -> ApiResponse<()> {
+    set_adopted(user.user_info, pid, state, comment).await.into()
+}
 
-    Json(set_adopted(token, pid, state).await.is_some())
+/// 去掉首尾空白，空的视为没有，过长的拒绝
+pub(crate) fn review_comment(comment: Option<String>) -> Result<Option<String>, Error> {
+    let comment = comment
+        .map(|it| it.trim().to_string())
+        .filter(|it| !it.is_empty());
+    if comment.as_ref().is_some_and(|it| it.chars().count() > MAX_REVIEW_COMMENT_LEN) {
+        return Err(Error::ValidationFailed(format!(
+            "comment should not be longer than {MAX_REVIEW_COMMENT_LEN}"
+        )));
+    }
+    Ok(comment)
 }
 
 #[instrument]
-async fn set_adopted(token: Token, pid: i64, state: State) -> Option<()> {
-    let db = GLOBAL_DATABASE.get().unwrap();
-    let transaction = db.begin().await.ok()?;
-
-    let user_info = get_user_info(token, &transaction).await?;
+async fn set_adopted(user_info: UserInfo, pid: i64, state: State, comment: Option<String>) -> Result<(), Error> {
     if state == State::Pending {
-        return None;
+        return Err(Error::ValidationFailed(String::from(
+            "state can not be set to pending",
+        )));
     }
+    let comment = review_comment(comment)?;
+
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
 
     let word_history_row = word_history::Entity::find_by_id(pid)
-        .filter(word_history::Column::State.eq(State::Pending))
         .one(&transaction)
-        .await
-        .ok()??;
+        .await?
+        .ok_or(Error::NotFound)?;
+    //已经处理过的请求不能再次处理
+    if word_history_row.state != State::Pending {
+        return Err(Error::Conflict);
+    }
 
+    let action = match state {
+        State::Pass => ModerationAction::Pass,
+        State::Cancel => ModerationAction::Cancel,
+        //Pending 已经在前面拒绝
+        _ => ModerationAction::Withdraw,
+    };
     let update_state = || async {
-        word_history::Entity::update_many()
+        //只更新仍在等待中的请求，同时处理同一个请求时只有一个成功
+        let mut update = word_history::Entity::update_many()
             .filter(word_history::Column::Pid.eq(word_history_row.pid))
+            .filter(word_history::Column::State.eq(State::Pending))
             .col_expr(word_history::Column::State, Expr::value(state))
             .col_expr(
                 word_history::Column::UpdateDate,
                 Expr::current_timestamp().into_simple_expr(),
-            )
-            .exec(&transaction)
-            .await
+            );
+        //撤回不是审核，审核意见只记录在审核日志中
+        if state != State::Withdraw {
+            update = update
+                .col_expr(word_history::Column::Reviewer, Expr::value(user_info.uid))
+                .col_expr(
+                    word_history::Column::ReviewDate,
+                    Expr::current_timestamp().into_simple_expr(),
+                )
+                .col_expr(word_history::Column::ReviewComment, Expr::value(comment.to_owned()));
+        }
+        if update.exec(&transaction).await?.rows_affected == 0 {
+            return Err(Error::Conflict);
+        }
+        moderation_log::ActiveModel {
+            comment: Set(comment.to_owned()),
+            ..moderation_log::entry(user_info.uid, action, Some(word_history_row.pid), Some(word_history_row.wid))
+        }
+            .insert(&transaction)
+            .await?;
+        Ok::<_, Error>(())
     };
+    let mut adopted = Vec::new();
     if word_history_row.author == user_info.uid && state == State::Withdraw {
-        update_state().await.ok()?;
-    } else if user_info.content_maintainer && state != State::Withdraw {
-        update_state().await.ok()?;
+        update_state().await?;
+    } else if user_info.content_maintainer && !user_info.restrict_user && state != State::Withdraw {
+        update_state().await?;
 
         if let State::Pass = state {
             let result = words::Entity::update_many()
@@ -66,8 +115,7 @@ async fn set_adopted(token: Token, pid: i64, state: State) -> Option<()> {
                 )
                 .filter(words::Column::Wid.eq(word_history_row.wid))
                 .exec(&transaction)
-                .await
-                .ok()?;
+                .await?;
 
             //不会出现更新失败
             match result.rows_affected {
@@ -75,22 +123,23 @@ async fn set_adopted(token: Token, pid: i64, state: State) -> Option<()> {
                     words::ActiveModel {
                         wid: Set(word_history_row.wid),
                         update_date: ActiveValue::NotSet,
-                        word_define: Set(Some(word_history_row.word_define)),
+                        word_define: Set(Some(word_history_row.word_define.to_owned())),
                     }
                         .insert(&transaction)
-                        .await
-                        .ok()?;
+                        .await?;
                 }
                 1 => {}
                 _ => {
-                    return None;
+                    return Err(Error::DatabaseErr);
                 }
             }
+            adopted.push((word_history_row.wid, Some(word_history_row.word_define)));
         }
     } else {
-        return None;
+        return Err(Error::PermissionDenied);
     }
 
-    transaction.commit().await.ok()?;
-    Some(())
+    transaction.commit().await?;
+    publish_dic(adopted);
+    Ok(())
 }
@@ -1,43 +1,83 @@
-use std::collections::HashMap;
-
 use axum::Json;
-use chrono::{DateTime, FixedOffset};
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
-use sea_orm::ActiveModelTrait;
+use axum::response::{IntoResponse, Response};
+use http::HeaderMap;
+use sea_orm::{ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use sea_orm::prelude::Expr;
+use sea_orm::sea_query::Order;
 use tracing::instrument;
 
-use senyoshu_common::types::word::wid::WordIdentity;
-use senyoshu_common::types::word::word_entry::WordDefine;
+use senyoshu_common::types::api::CBOR_LZ4;
+use senyoshu_common::types::api::dic::{MAX_SYNC_DIC_LIMIT, SyncCursor, SyncDicPage, SyncDicRequest};
+use senyoshu_common::types::error::Error;
 
-use crate::database::database::GLOBAL_DATABASE;
+use crate::api::{accepts, ApiResponse, cbor_lz4_response};
+use crate::api::account::session::now;
+use crate::database::database::{GLOBAL_DATABASE, SYNC_OVERLAP, time_key, time_since, time_value};
 use crate::database::dic::words;
 
-#[instrument]
-pub async fn sync_dic_api(
-    Json(from): Json<Option<DateTime<FixedOffset>>>,
-) -> Json<HashMap<WordIdentity, Option<WordDefine>>> {
-    //todo:response error
-    Json(sync_dic(from).await.unwrap())
+/// 请求 [`CBOR_LZ4`] 时返回压缩的响应，否则与其他 API 一样返回 json
+pub async fn sync_dic_api(headers: HeaderMap, Json(request): Json<SyncDicRequest>) -> Response {
+    let result = sync_dic(request).await;
+    if accepts(&headers, CBOR_LZ4) {
+        cbor_lz4_response(result)
+    } else {
+        ApiResponse(result).into_response()
+    }
 }
 
-async fn sync_dic(
-    from: Option<DateTime<FixedOffset>>,
-) -> Option<HashMap<WordIdentity, Option<WordDefine>>> {
+#[instrument]
+async fn sync_dic(request: SyncDicRequest) -> Result<SyncDicPage, Error> {
     let db = GLOBAL_DATABASE.get().unwrap();
+    let backend = db.get_database_backend();
+    let limit = match request.limit {
+        0 => MAX_SYNC_DIC_LIMIT,
+        limit => limit.min(MAX_SYNC_DIC_LIMIT),
+    };
+    //高水位使用服务端的时间，之后的页都不超过第一页确定的高水位
+    let watermark = request
+        .cursor
+        .as_ref()
+        .map(|cursor| cursor.watermark)
+        .unwrap_or_else(now);
 
-    let mut selected = words::Entity::find();
-    if let Some(from) = from {
-        selected = selected.filter(words::Column::UpdateDate.gte(from));
+    let update_date = || time_key(backend, words::Column::UpdateDate);
+    let mut select = words::Entity::find().filter(Expr::expr(update_date()).lte(time_value(backend, watermark)));
+    if let Some(since) = request.since {
+        select = select.filter(time_since(backend, words::Column::UpdateDate, since - SYNC_OVERLAP));
     } else {
-        selected = selected.filter(words::Column::WordDefine.is_not_null());
+        select = select.filter(words::Column::WordDefine.is_not_null());
     }
-
-    let rows = selected.all(db).await.unwrap();
-
-    let mut rv = HashMap::with_capacity(rows.len());
-    for row in rows.into_iter() {
-        rv.insert(row.wid, row.word_define);
+    if let Some(cursor) = request.cursor {
+        let cursor_date = || time_value(backend, cursor.update_date);
+        select = select.filter(
+            Condition::any()
+                .add(Expr::expr(update_date()).gt(cursor_date()))
+                .add(
+                    Condition::all()
+                        .add(Expr::expr(update_date()).eq(cursor_date()))
+                        .add(words::Column::Wid.gt(cursor.wid)),
+                ),
+        );
     }
 
-    Some(rv)
+    //多取一条判断是否还有下一页
+    let mut rows = select
+        .order_by(update_date(), Order::Asc)
+        .order_by_asc(words::Column::Wid)
+        .limit(limit + 1)
+        .all(db)
+        .await?;
+    let has_next = rows.len() as u64 > limit;
+    rows.truncate(limit as usize);
+
+    let next = has_next.then(|| rows.last()).flatten().map(|last| SyncCursor {
+        update_date: last.update_date,
+        wid: last.wid,
+        watermark,
+    });
+    Ok(SyncDicPage {
+        words: rows.into_iter().map(|row| (row.wid, row.word_define)).collect(),
+        next,
+        watermark,
+    })
 }
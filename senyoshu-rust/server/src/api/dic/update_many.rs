@@ -9,46 +9,51 @@ use sea_orm::QueryFilter;
 use sea_orm::TransactionTrait;
 use tracing::instrument;
 
-use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::api::account::UserInfo;
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::moderation::ModerationAction;
 use senyoshu_common::types::state::State;
 use senyoshu_common::types::word::wid::WordIdentity;
 use senyoshu_common::types::word::word_entry::WordDefine;
 
-use crate::api::account::get_user_info;
+use crate::api::ApiResponse;
+use crate::api::auth::ContentMaintainer;
 use crate::database::database::GLOBAL_DATABASE;
-use crate::database::dic::{word_history, words};
+use crate::database::dic::{moderation_log, word_history, words};
+use crate::push::publish_dic;
 
 pub async fn update_many_api(
-    Json((token, update)): Json<(Token, HashMap<WordIdentity, WordDefine>)>,
-) -> Json<bool> {
-    Json(update_many(token, update).await.is_some())
+    user: ContentMaintainer,
+    Json(update): Json<HashMap<WordIdentity, WordDefine>>,
+) -> ApiResponse<()> {
+    update_many(user.0.user_info, update).await.into()
 }
 
 
 #[instrument]
 pub async fn update_many(
-    token: Token,
+    user_info: UserInfo,
     update: HashMap<WordIdentity, WordDefine>,
-) -> Option<()> {
+) -> Result<(), Error> {
     let db = GLOBAL_DATABASE.get().unwrap();
-    let transaction = db.begin().await.ok()?;
-    let user_info = get_user_info(token, &transaction).await?;
-    if user_info.content_maintainer == false {
-        return None;
-    }
+    let transaction = db.begin().await?;
+    let mut updated = Vec::with_capacity(update.len());
     for (wid, word_define) in update.into_iter() {
-        word_history::ActiveModel {
+        let history = word_history::ActiveModel {
             author: Set(user_info.uid),
             wid: Set(wid),
             word_define: Set(word_define.to_owned()),
             state: Set(State::Pass),
             ..Default::default()
         }.insert(&transaction)
-            .await.ok()?;
+            .await?;
+        moderation_log::entry(user_info.uid, ModerationAction::UpdateMany, Some(history.pid), Some(wid))
+            .insert(&transaction)
+            .await?;
         let result = words::Entity::update_many()
             .col_expr(
                 words::Column::WordDefine,
-                Expr::value(word_define),
+                Expr::value(word_define.to_owned()),
             )
             .col_expr(
                 words::Column::UpdateDate,
@@ -56,16 +61,17 @@ pub async fn update_many(
             )
             .filter(words::Column::Wid.eq(wid))
             .exec(&transaction)
-            .await
-            .ok()?;
+            .await?;
         if result.rows_affected == 0 {
-            return None;
+            return Err(Error::WordIsNotExist);
         }
+        updated.push((wid, Some(word_define)));
     }
 
 
-    transaction.commit().await.ok()?;
-    Some(())
+    transaction.commit().await?;
+    publish_dic(updated);
+    Ok(())
 }
 
 
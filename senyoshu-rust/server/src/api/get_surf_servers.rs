@@ -1,20 +1,174 @@
+use std::collections::HashMap;
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
 use axum::Json;
+use chrono::{DateTime, FixedOffset};
+use itertools::Itertools;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    TransactionTrait,
+};
+use sea_orm::ActiveValue::Set;
+use sea_orm::prelude::Expr;
+use sea_orm::sea_query::OnConflict;
+use tracing::instrument;
 
 use senyoshu_common::types::api::api::SurfServer;
+use senyoshu_common::types::error::Error;
+
+use crate::api::account::session::now;
+use crate::api::ApiResponse;
+use crate::api::auth::Vip;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::surf::{surf_credential, surf_server};
+
+pub async fn get_surf_servers_api(user: Vip, Json(()): Json<()>) -> ApiResponse<Vec<SurfServer>> {
+    let Some(vip) = user.user_info.vip else {
+        return ApiResponse(Err(Error::PermissionDenied));
+    };
+    get_surf_servers(user.user_info.uid, vip).await.into()
+}
+
+fn new_password() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// 没有密码的服务器生成新的密码，已满的服务器不返回
+///
+/// 密码的到期时间跟随会员的到期时间
+#[instrument]
+pub(crate) async fn get_surf_servers(uid: i64, vip: DateTime<FixedOffset>) -> Result<Vec<SurfServer>, Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
+    let now = now();
+
+    let servers = surf_server::Entity::find()
+        .filter(surf_server::Column::Enabled.eq(true))
+        .order_by_asc(surf_server::Column::Id)
+        .all(&transaction)
+        .await?;
+    let ids = servers.iter().map(|it| it.id).collect_vec();
+
+    let mut credentials: HashMap<i64, surf_credential::Model> = surf_credential::Entity::find()
+        .filter(surf_credential::Column::ServerId.is_in(ids))
+        .filter(surf_credential::Column::Uid.eq(uid))
+        .filter(surf_credential::Column::Revoked.eq(false))
+        .all(&transaction)
+        .await?
+        .into_iter()
+        .map(|it| (it.server_id, it))
+        .collect();
+
+    //需要分配新密码的服务器先加锁再计数，同时请求时不会超过容量
+    let missing = servers
+        .iter()
+        .filter(|it| it.capacity > 0 && !credentials.contains_key(&it.id))
+        .map(|it| it.id)
+        .collect_vec();
+    let mut active: HashMap<i64, i64> = HashMap::new();
+    if !missing.is_empty() {
+        surf_server::Entity::find()
+            .filter(surf_server::Column::Id.is_in(missing.to_owned()))
+            .order_by_asc(surf_server::Column::Id)
+            .lock_exclusive()
+            .all(&transaction)
+            .await?;
+        active = surf_credential::Entity::find()
+            .select_only()
+            .column(surf_credential::Column::ServerId)
+            .column_as(surf_credential::Column::Id.count(), "active")
+            .filter(surf_credential::Column::ServerId.is_in(missing))
+            .filter(surf_credential::Column::Revoked.eq(false))
+            .filter(surf_credential::Column::ExpireTime.gt(now))
+            .group_by(surf_credential::Column::ServerId)
+            .into_tuple::<(i64, i64)>()
+            .all(&transaction)
+            .await?
+            .into_iter()
+            .collect();
+    }
+
+    let mut rv = Vec::with_capacity(servers.len());
+    for server in servers {
+        let password = match credentials.remove(&server.id) {
+            Some(credential) => {
+                if credential.expire_time != vip {
+                    surf_credential::Entity::update_many()
+                        .filter(surf_credential::Column::Id.eq(credential.id))
+                        .col_expr(surf_credential::Column::ExpireTime, Expr::value(vip))
+                        .exec(&transaction)
+                        .await?;
+                }
+                credential.password
+            }
+            None => {
+                let active = active.get(&server.id).copied().unwrap_or_default();
+                if server.capacity > 0 && active >= server.capacity as i64 {
+                    continue;
+                }
+                issue_credential(server.id, uid, vip, now, &transaction).await?
+            }
+        };
+
+        rv.push(SurfServer {
+            name: server.name,
+            server: server.server,
+            server_port: server.server_port as u16,
+            password,
+            method: server.method,
+            region: server.region,
+            expire_time: Some(vip),
+        });
+    }
+
+    transaction.commit().await?;
+    Ok(rv)
+}
+
+/// 重新启用已撤销的密码或者插入新的密码，返回最终保存的密码
+///
+/// `(server_id, uid)` 是唯一的，同时请求时以先写入的为准
+async fn issue_credential<C: ConnectionTrait>(
+    server_id: i64,
+    uid: i64,
+    vip: DateTime<FixedOffset>,
+    now: DateTime<FixedOffset>,
+    db: &C,
+) -> Result<String, DbErr> {
+    surf_credential::Entity::update_many()
+        .filter(surf_credential::Column::ServerId.eq(server_id))
+        .filter(surf_credential::Column::Uid.eq(uid))
+        .filter(surf_credential::Column::Revoked.eq(true))
+        .col_expr(surf_credential::Column::Password, Expr::value(new_password()))
+        .col_expr(surf_credential::Column::ExpireTime, Expr::value(vip))
+        .col_expr(surf_credential::Column::Revoked, Expr::value(false))
+        .col_expr(surf_credential::Column::CreateTime, Expr::value(now))
+        .exec(db)
+        .await?;
+    surf_credential::Entity::insert(surf_credential::ActiveModel {
+        server_id: Set(server_id),
+        uid: Set(uid),
+        password: Set(new_password()),
+        expire_time: Set(vip),
+        revoked: Set(false),
+        create_time: Set(now),
+        ..Default::default()
+    })
+        .on_conflict(
+            OnConflict::columns([surf_credential::Column::ServerId, surf_credential::Column::Uid])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
 
-pub async fn get_surf_servers_api(
-    Json(()): Json<()>,
-) -> Json<Vec<SurfServer>> {
-    let servers = Vec::from([
-        SurfServer {
-            name: "日本-1".to_string(),
-            server: "103.75.70.120".to_string(),
-            server_port: 34274,
-            password: "xmzxis43axpa6h21".to_string(),
-            method: "aes-256-gcm".to_string(),
-        },
-    ]);
-
-
-    Json(servers)
-}
\ No newline at end of file
+    let credential = surf_credential::Entity::find()
+        .filter(surf_credential::Column::ServerId.eq(server_id))
+        .filter(surf_credential::Column::Uid.eq(uid))
+        .one(db)
+        .await?
+        .ok_or(DbErr::RecordNotFound(String::from("surf_credential")))?;
+    Ok(credential.password)
+}
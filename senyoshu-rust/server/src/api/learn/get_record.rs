@@ -1,60 +1,121 @@
-use std::collections::HashMap;
-
 use axum::Json;
-use chrono::{DateTime, FixedOffset};
-use itertools::Itertools;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
-use sea_orm::ActiveModelTrait;
+use sea_orm::{ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use sea_orm::prelude::Expr;
+use sea_orm::sea_query::Order;
+use tracing::instrument;
 
-use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::api::learn::{GetRecordPage, GetRecordRequest, MAX_GET_RECORD_LIMIT, RecordCursor};
+use senyoshu_common::types::error::Error;
 use senyoshu_common::types::learn::knowledge::Knowledge;
 use senyoshu_common::types::learn::learn_knowledge_history::LearnKnowledgeHistory;
 use senyoshu_common::types::learn::LearnHistoryMap;
 
-use crate::api::account::get_user_info;
-use crate::database::database::GLOBAL_DATABASE;
+use crate::api::account::session::now;
+use crate::api::ApiResponse;
+use crate::api::auth::AuthUser;
+use crate::database::database::{GLOBAL_DATABASE, SYNC_OVERLAP, time_key, time_since, time_value};
 use crate::database::learn;
 
 pub async fn get_record_api(
-    Json((token, from)): Json<(Token, Option<DateTime<FixedOffset>>)>,
-) -> Json<Option<Vec<(Knowledge, LearnKnowledgeHistory)>>> {
-    //todo:response error
-    let rv = get_record(token, from)
-        .await
-        .map(|ops| ops.into_iter().collect_vec());
-    Json(rv)
+    user: AuthUser,
+    Json(request): Json<GetRecordRequest>,
+) -> ApiResponse<GetRecordPage> {
+    get_record(user.user_info.uid, request).await.into()
 }
 
-pub(crate) async fn get_record(
-    token: Token,
-    from: Option<DateTime<FixedOffset>>,
-) -> Option<LearnHistoryMap> {
+/// 按 `(update_time, knowledge_type, knowledge_key)` 分页，与 `sync_dic` 一样使用服务端的高水位
+#[instrument]
+pub(crate) async fn get_record(uid: i64, request: GetRecordRequest) -> Result<GetRecordPage, Error> {
     let db = GLOBAL_DATABASE.get().unwrap();
-    let user_info = get_user_info(token, db).await?;
+    let backend = db.get_database_backend();
+    let limit = match request.limit {
+        0 => MAX_GET_RECORD_LIMIT,
+        limit => limit.min(MAX_GET_RECORD_LIMIT),
+    };
+    let watermark = request
+        .cursor
+        .as_ref()
+        .map(|cursor| cursor.watermark)
+        .unwrap_or_else(now);
 
-    const DATE_MILLIS: f64 = (24 * 60 * 60) as f64;
-    let mut select = learn::Entity::find().filter(learn::Column::Uid.eq(user_info.uid));
-    if let Some(from) = from {
-        select = select.filter(learn::Column::UpdateTime.gte(from));
+    let update_time = || time_key(backend, learn::Column::UpdateTime);
+    let mut select = learn::Entity::find()
+        .filter(learn::Column::Uid.eq(uid))
+        .filter(Expr::expr(update_time()).lte(time_value(backend, watermark)));
+    if let Some(since) = request.since {
+        select = select.filter(time_since(backend, learn::Column::UpdateTime, since - SYNC_OVERLAP));
+    }
+    if !request.knowledge_types.is_empty() {
+        select = select.filter(learn::Column::KnowledgeType.is_in(request.knowledge_types));
     }
+    if let Some(cursor) = request.cursor {
+        let cursor_time = || time_value(backend, cursor.update_time);
+        let knowledge = cursor.knowledge;
+        select = select.filter(
+            Condition::any()
+                .add(Expr::expr(update_time()).gt(cursor_time()))
+                .add(
+                    Condition::all()
+                        .add(Expr::expr(update_time()).eq(cursor_time()))
+                        .add(learn::Column::KnowledgeType.gt(knowledge.knowledge_type)),
+                )
+                .add(
+                    Condition::all()
+                        .add(Expr::expr(update_time()).eq(cursor_time()))
+                        .add(learn::Column::KnowledgeType.eq(knowledge.knowledge_type))
+                        .add(learn::Column::KnowledgeKey.gt(knowledge.key)),
+                ),
+        );
+    }
+
+    //多取一条判断是否还有下一页
+    let mut rows = select
+        .order_by(update_time(), Order::Asc)
+        .order_by_asc(learn::Column::KnowledgeType)
+        .order_by_asc(learn::Column::KnowledgeKey)
+        .limit(limit + 1)
+        .all(db)
+        .await?;
+    let has_next = rows.len() as u64 > limit;
+    rows.truncate(limit as usize);
 
-    let result = select.all(db).await.ok()?.into_iter().map(|it| {
-        (
-            Knowledge {
-                knowledge_type: it.knowledge_type,
-                key: it.knowledge_key,
-            },
-            LearnKnowledgeHistory {
-                history: it.history,
-                freeze_time: it.freeze_time,
-            },
-        )
+    let next = has_next.then(|| rows.last()).flatten().map(|last| RecordCursor {
+        update_time: last.update_time,
+        knowledge: Knowledge {
+            knowledge_type: last.knowledge_type,
+            key: last.knowledge_key.to_owned(),
+        },
+        watermark,
     });
+    Ok(GetRecordPage {
+        records: rows.into_iter().map(to_record).collect(),
+        next,
+        watermark,
+    })
+}
 
-    let mut rv = HashMap::with_capacity(result.len());
-    for (k, v) in result {
-        rv.insert(k, v);
-    }
+/// 用户的全部记录，用于导出账号数据
+pub(crate) async fn get_all_records(uid: i64) -> Result<LearnHistoryMap, Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let records = learn::Entity::find()
+        .filter(learn::Column::Uid.eq(uid))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(to_record)
+        .collect();
+    Ok(LearnHistoryMap::new(records))
+}
 
-    Some(LearnHistoryMap::new(rv))
+fn to_record(model: learn::Model) -> (Knowledge, LearnKnowledgeHistory) {
+    (
+        Knowledge {
+            knowledge_type: model.knowledge_type,
+            key: model.knowledge_key,
+        },
+        LearnKnowledgeHistory {
+            history: model.history,
+            freeze_time: model.freeze_time,
+        },
+    )
 }
@@ -1,99 +1,129 @@
-use std::default::Default;
+use std::collections::HashMap;
 
 use axum::Json;
-use sea_orm::{
-    ColumnTrait, EntityTrait, IntoSimpleExpr, ModelTrait, QueryFilter, TransactionTrait,
-};
-use sea_orm::ActiveModelTrait;
+use itertools::Itertools;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
 use sea_orm::ActiveValue::Set;
-use sea_orm::prelude::Expr;
+use sea_orm::sea_query::OnConflict;
 
-use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::learn::knowledge::KnowledgeType;
+use senyoshu_common::types::learn::learn_knowledge_history::LearnKnowledgeHistory;
 use senyoshu_common::types::learn::LearnHistoryMap;
 use senyoshu_common::util::time::UtcTimeStamp;
 
-use crate::api::account::get_user_info;
+use crate::api::account::session::now;
+use crate::api::ApiResponse;
+use crate::api::auth::AuthUser;
 use crate::database::database::GLOBAL_DATABASE;
 use crate::database::learn;
+use crate::push::publish_learn;
+
+/// 每条语句绑定的参数数量有上限（sqlite 32766，postgres 65535），按这个数量分批读写
+const CHUNK_LEN: usize = 1000;
 
 pub async fn post_learn_record_api(
-    Json((token, learn_record_increment_vec)): Json<(Token, LearnHistoryMap)>,
-) -> Json<bool> {
-    Json(
-        post_learn_record(token, learn_record_increment_vec)
-            .await
-            .is_some(),
-    )
+    user: AuthUser,
+    Json(learn_record_increment_vec): Json<LearnHistoryMap>,
+) -> ApiResponse<()> {
+    post_learn_record(&user, learn_record_increment_vec)
+        .await
+        .into()
 }
 
+/// 先批量读出已有的记录，在内存中合并后用 `INSERT ... ON CONFLICT DO UPDATE` 批量写回
+///
+/// 合并后的记录推送给同一个用户的其他 session
 pub(crate) async fn post_learn_record(
-    token: Token,
+    user: &AuthUser,
     learn_operate_vec: LearnHistoryMap,
-) -> Option<()> {
+) -> Result<(), Error> {
+    let uid = user.user_info.uid;
     let db = GLOBAL_DATABASE.get().unwrap();
-    let transaction = db.begin().await.ok()?;
-    let user_info = get_user_info(token, &transaction).await?;
+    let transaction = db.begin().await?;
+    //使用精确到毫秒的服务端时间，分页下载时与高水位比较
+    let update_time = now();
 
-    for (knowledge, mut operates) in learn_operate_vec.into_iter() {
-        let result = learn::Entity::find_by_id((
-            user_info.uid,
-            knowledge.knowledge_type,
-            knowledge.key.to_owned(),
-        ))
-            .one(&transaction)
-            .await
-            .ok()?;
+    let incoming = learn_operate_vec.into_iter().collect_vec();
+    let mut pushed = Vec::with_capacity(incoming.len());
+    for chunk in incoming.chunks(CHUNK_LEN) {
+        let keys = chunk.iter().map(|(knowledge, _)| knowledge.key.to_owned()).unique();
+        let mut stored: HashMap<(KnowledgeType, String), learn::Model> = learn::Entity::find()
+            .filter(learn::Column::Uid.eq(uid))
+            .filter(learn::Column::KnowledgeKey.is_in(keys))
+            .all(&transaction)
+            .await?
+            .into_iter()
+            .map(|it| ((it.knowledge_type, it.knowledge_key.to_owned()), it))
+            .collect();
 
-        let is_some = result.is_some();
-        let (mut history, freeze_time) = result
-            .map(|it| (it.history, it.freeze_time))
-            .unwrap_or_default();
-        //让后来的记录覆盖先前的
-        operates.history.append(&mut history);
-        let mut history = operates.history;
-        history.sort();
-        history.dedup();
+        let merged = chunk
+            .iter()
+            .map(|(knowledge, operates)| {
+                let stored = stored.remove(&(knowledge.knowledge_type, knowledge.key.to_owned()));
+                (knowledge.to_owned(), merge(stored, operates.to_owned()))
+            })
+            .collect_vec();
+        let models = merged.iter().map(|(knowledge, merged)| learn::ActiveModel {
+            uid: Set(uid),
+            knowledge_type: Set(knowledge.knowledge_type),
+            knowledge_key: Set(knowledge.key.to_owned()),
+            history: Set(merged.history.to_owned()),
+            update_time: Set(update_time),
+            freeze_time: Set(merged.freeze_time),
+        });
 
-        if is_some {
-            learn::Entity::update_many()
-                .set(learn::ActiveModel {
-                    freeze_time: if let Some(freeze_time) = freeze_time {
-                        let last_op = history.last().map(|op| op.operate_time).unwrap_or_default();
-                        if UtcTimeStamp::from(freeze_time.to_utc()) < last_op {
-                            Set(None)
-                        } else {
-                            Set(operates.freeze_time)
-                        }
-                    } else {
-                        Set(operates.freeze_time)
-                    },
-                    history: Set(history),
-                    ..Default::default()
-                })
-                .col_expr(
-                    learn::Column::UpdateTime,
-                    Expr::current_timestamp().into_simple_expr(),
-                )
-                .filter(learn::Column::Uid.eq(user_info.uid))
-                .filter(learn::Column::KnowledgeType.eq(knowledge.knowledge_type))
-                .filter(learn::Column::KnowledgeKey.eq(knowledge.key))
-                .exec(&transaction)
-                .await
-                .ok()?;
-        } else {
-            learn::ActiveModel {
-                uid: Set(user_info.uid),
-                knowledge_type: Set(knowledge.knowledge_type),
-                knowledge_key: Set(knowledge.key),
-                history: Set(history),
-                ..Default::default()
-            }
-                .insert(&transaction)
-                .await
-                .ok()?;
-        };
+        learn::Entity::insert_many(models)
+            .on_conflict(
+                OnConflict::columns([
+                    learn::Column::Uid,
+                    learn::Column::KnowledgeType,
+                    learn::Column::KnowledgeKey,
+                ])
+                    .update_columns([
+                        learn::Column::History,
+                        learn::Column::UpdateTime,
+                        learn::Column::FreezeTime,
+                    ])
+                    .to_owned(),
+            )
+            .exec_without_returning(&transaction)
+            .await?;
+        pushed.extend(merged);
     }
 
-    transaction.commit().await.ok()?;
-    Some(())
+    transaction.commit().await?;
+    publish_learn(&user.token, pushed);
+    Ok(())
+}
+
+/// 合并上传的记录和已保存的记录，新的知识点不接受上传的冻结时间
+fn merge(stored: Option<learn::Model>, mut operates: LearnKnowledgeHistory) -> LearnKnowledgeHistory {
+    let Some(stored) = stored else {
+        operates.history.sort();
+        operates.history.dedup();
+        return LearnKnowledgeHistory {
+            history: operates.history,
+            freeze_time: None,
+        };
+    };
+
+    //让后来的记录覆盖先前的
+    let mut history = operates.history;
+    history.extend(stored.history);
+    history.sort();
+    history.dedup();
+
+    let freeze_time = match stored.freeze_time {
+        Some(freeze_time) => {
+            let last_op = history.last().map(|op| op.operate_time).unwrap_or_default();
+            if UtcTimeStamp::from(freeze_time.to_utc()) < last_op {
+                None
+            } else {
+                operates.freeze_time
+            }
+        }
+        None => operates.freeze_time,
+    };
+    LearnKnowledgeHistory { history, freeze_time }
 }
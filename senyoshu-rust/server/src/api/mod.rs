@@ -1,17 +1,72 @@
 use std::future::Future;
 
 use axum::{Json, Router};
+use axum::extract::FromRequestParts;
 use axum::handler::Handler;
+use axum::response::{IntoResponse, Response};
 use axum::routing::post;
+use http::{HeaderMap, StatusCode};
+use http::header::{ACCEPT, CONTENT_TYPE};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-use senyoshu_common::types::api::API;
+use tracing::error;
+
+use senyoshu_common::types::api::{API, ApiResult, AuthAPI, CBOR_LZ4};
+use senyoshu_common::types::error::Error;
+use senyoshu_common::util::cbor_lz4;
 
 pub(crate) mod account;
+pub(crate) mod admin;
+pub mod auth;
+pub(crate) mod comment;
 pub(crate) mod dic;
 pub(crate) mod learn;
+pub(crate) mod push;
 pub(crate) mod get_surf_servers;
+pub(crate) mod sound;
+
+/// 所有 API 的响应，响应体为 `Result<RES, Error>` 的 json，状态码由 `Error::status_code` 决定
+pub struct ApiResponse<RES>(pub ApiResult<RES>);
+
+impl<RES> From<ApiResult<RES>> for ApiResponse<RES> {
+    fn from(value: ApiResult<RES>) -> Self {
+        Self(value)
+    }
+}
+
+impl<RES: Serialize> IntoResponse for ApiResponse<RES> {
+    fn into_response(self) -> Response {
+        let status = match &self.0 {
+            Ok(_) => StatusCode::OK,
+            Err(err) => StatusCode::from_u16(err.status_code())
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        };
+        (status, Json(self.0)).into_response()
+    }
+}
+
+/// `Accept` 请求头中是否包含 `media_type`
+pub(crate) fn accepts(headers: &HeaderMap, media_type: &str) -> bool {
+    headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|value| value.split(';').next().unwrap_or_default().trim() == media_type)
+}
+
+/// 以 [`CBOR_LZ4`] 编码的成功响应，出错时与其他 API 一样返回 json
+pub(crate) fn cbor_lz4_response<RES: Serialize>(result: ApiResult<RES>) -> Response {
+    match result.map(|value| cbor_lz4::encode(&value)) {
+        Ok(Ok(bytes)) => ([(CONTENT_TYPE, CBOR_LZ4)], bytes).into_response(),
+        Ok(Err(err)) => {
+            error!("failed to encode response: {err}");
+            ApiResponse::<()>(Err(Error::DatabaseErr)).into_response()
+        }
+        Err(err) => ApiResponse::<()>(Err(err)).into_response(),
+    }
+}
 
 pub trait AxumAPi<S> {
     fn set_api_handle<REQ, RES, H, Fut, T>(self, api: API<REQ, RES>, handle: H) -> Self
@@ -20,7 +75,27 @@ pub trait AxumAPi<S> {
             RES: Serialize + DeserializeOwned,
             H: Handler<T, S> + Fn(Json<REQ>) -> Fut,
             T: 'static,
-            Fut: Future<Output=Json<RES>>;
+            Fut: Future<Output=ApiResponse<RES>>;
+
+    /// 与 `set_api_handle` 相同，但处理函数在请求体之前还有一个提取器，例如 `HeaderMap`
+    fn set_api_handle_with<REQ, RES, E, H, Fut, T>(self, api: API<REQ, RES>, handle: H) -> Self
+        where
+            REQ: Serialize + DeserializeOwned,
+            RES: Serialize + DeserializeOwned,
+            E: FromRequestParts<S>,
+            H: Handler<T, S> + Fn(E, Json<REQ>) -> Fut,
+            T: 'static,
+            Fut: Future<Output=ApiResponse<RES>>;
+
+    /// 需要登录的 API，处理函数的第一个参数是 [`auth::AuthUser`] 或者某个权限检查，例如 [`auth::ContentMaintainer`]
+    fn set_auth_api_handle<REQ, RES, E, H, Fut, T>(self, api: AuthAPI<REQ, RES>, handle: H) -> Self
+        where
+            REQ: Serialize + DeserializeOwned,
+            RES: Serialize + DeserializeOwned,
+            E: FromRequestParts<S>,
+            H: Handler<T, S> + Fn(E, Json<REQ>) -> Fut,
+            T: 'static,
+            Fut: Future<Output=ApiResponse<RES>>;
 }
 
 impl<S> AxumAPi<S> for Router<S>
@@ -33,7 +108,31 @@ impl<S> AxumAPi<S> for Router<S>
             RES: Serialize + DeserializeOwned,
             H: Handler<T, S> + Fn(Json<REQ>) -> Fut,
             T: 'static,
-            Fut: Future<Output=Json<RES>>,
+            Fut: Future<Output=ApiResponse<RES>>,
+    {
+        self.route(api.path().as_str(), post(handle))
+    }
+
+    fn set_api_handle_with<REQ, RES, E, H, Fut, T>(self, api: API<REQ, RES>, handle: H) -> Self
+        where
+            REQ: Serialize + DeserializeOwned,
+            RES: Serialize + DeserializeOwned,
+            E: FromRequestParts<S>,
+            H: Handler<T, S> + Fn(E, Json<REQ>) -> Fut,
+            T: 'static,
+            Fut: Future<Output=ApiResponse<RES>>,
+    {
+        self.route(api.path().as_str(), post(handle))
+    }
+
+    fn set_auth_api_handle<REQ, RES, E, H, Fut, T>(self, api: AuthAPI<REQ, RES>, handle: H) -> Self
+        where
+            REQ: Serialize + DeserializeOwned,
+            RES: Serialize + DeserializeOwned,
+            E: FromRequestParts<S>,
+            H: Handler<T, S> + Fn(E, Json<REQ>) -> Fut,
+            T: 'static,
+            Fut: Future<Output=ApiResponse<RES>>,
     {
         self.route(api.path().as_str(), post(handle))
     }
@@ -0,0 +1,120 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
+use axum::extract::ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade};
+use axum::response::Response;
+use tokio::select;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{interval_at, Instant, timeout};
+use tracing::{debug, error, Instrument, Span};
+
+use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::api::push::{PUSH_CLOSE_NOT_AUTH, PushEvent};
+
+use crate::api::account::session::authenticate;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::metrics::METRICS;
+use crate::push;
+
+/// 等待客户端发送 token 的最长时间
+const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+/// 定期发送 ping 保持连接，同时重新校验 token，session 被撤销或过期后断开
+const HEARTBEAT: Duration = Duration::from_secs(30);
+
+/// 浏览器的 WebSocket 不能设置请求头，token 在连接后的第一条消息中发送
+pub async fn push_api(ws: WebSocketUpgrade) -> Response {
+    //连接在单独的任务中处理，沿用升级请求的 span
+    let span = Span::current();
+    ws.on_upgrade(move |socket| handle_socket(socket).instrument(span))
+}
+
+async fn handle_socket(mut socket: WebSocket) {
+    let Some(token) = receive_token(&mut socket).await else {
+        close_not_auth(socket).await;
+        return;
+    };
+    debug!("push connected: {}", token.uid);
+    METRICS.push_connections.inc();
+    serve_socket(socket, &token).await;
+    METRICS.push_connections.dec();
+    debug!("push disconnected: {}", token.uid);
+}
+
+async fn serve_socket(mut socket: WebSocket, token: &Token) {
+    //订阅之前的修改由客户端自己同步
+    let mut receiver = push::subscribe();
+    let mut shutdown = push::shutdown_receiver();
+    if !send_event(&mut socket, &PushEvent::Resync).await {
+        return;
+    }
+    let mut heartbeat = interval_at(Instant::now() + HEARTBEAT, HEARTBEAT);
+    loop {
+        select! {
+            push = receiver.recv() => {
+                let sent = match push {
+                    Ok(push) if push.is_for(token) => send_event(&mut socket, &push.event).await,
+                    Ok(_) => true,
+                    Err(RecvError::Lagged(_)) => send_event(&mut socket, &PushEvent::Resync).await,
+                    Err(RecvError::Closed) => false,
+                };
+                if !sent {
+                    break;
+                }
+            }
+            message = socket.recv() => match message {
+                //之后客户端发送的消息都被忽略
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            _ = heartbeat.tick() => {
+                if !is_valid(token).await {
+                    close_not_auth(socket).await;
+                    return;
+                }
+                if socket.send(Message::Ping(Vec::new())).await.is_err() {
+                    break;
+                }
+            }
+            _ = async { let _ = shutdown.wait_for(|closing| *closing).await; } => {
+                let frame = CloseFrame {
+                    code: close_code::AWAY,
+                    reason: Cow::from("ShuttingDown"),
+                };
+                let _ = socket.send(Message::Close(Some(frame))).await;
+                break;
+            }
+        }
+    }
+}
+
+async fn receive_token(socket: &mut WebSocket) -> Option<Token> {
+    let credential = match timeout(AUTH_TIMEOUT, socket.recv()).await {
+        Ok(Some(Ok(Message::Text(credential)))) => credential,
+        _ => return None,
+    };
+    let token = Token::from_credential(credential.trim())?;
+    is_valid(&token).await.then_some(token)
+}
+
+async fn is_valid(token: &Token) -> bool {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    authenticate(token, db).await.is_ok()
+}
+
+async fn send_event(socket: &mut WebSocket, event: &PushEvent) -> bool {
+    match serde_json::to_string(event) {
+        Ok(text) => socket.send(Message::Text(text)).await.is_ok(),
+        Err(err) => {
+            error!("failed to encode push event: {err}");
+            true
+        }
+    }
+}
+
+async fn close_not_auth(mut socket: WebSocket) {
+    let frame = CloseFrame {
+        code: PUSH_CLOSE_NOT_AUTH,
+        reason: Cow::from("NotAuth"),
+    };
+    let _ = socket.send(Message::Close(Some(frame))).await;
+}
@@ -0,0 +1,106 @@
+use axum::body::Body;
+use axum::extract::Path;
+use axum::response::{IntoResponse, Response};
+use http::{header, HeaderMap, HeaderValue, StatusCode};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use tracing::{error, instrument};
+
+use senyoshu_common::types::api::sound::SoundKey;
+use senyoshu_common::types::error::Error;
+
+use crate::api::ApiResponse;
+use crate::api::auth::ContentMaintainer;
+use crate::api::sound::validate_key;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::dic::sounds;
+
+/// 审核通过的音频很少变化，允许缓存一天，之后通过 ETag 确认
+const PUBLIC_CACHE_CONTROL: &str = "public, max-age=86400";
+/// 未审核的音频只给审核者试听，不能被缓存
+const PRIVATE_CACHE_CONTROL: &str = "private, no-store";
+
+/// `GET /api/sound/:katakana/:tone`，返回任意一个说话人的审核通过的音频
+pub async fn get_any_sound_api(
+    Path((katakana, tone)): Path<(String, u8)>,
+    headers: HeaderMap,
+) -> Response {
+    match get_any_sound(katakana, tone).await {
+        Ok(sound) => sound_response(sound, &headers),
+        Err(err) => ApiResponse::<()>(Err(err)).into_response(),
+    }
+}
+
+/// `GET /api/sound/:katakana/:tone/:role`，审核者可以获取尚未审核的音频
+pub async fn get_sound_api(
+    user: Option<ContentMaintainer>,
+    Path((katakana, tone, role)): Path<(String, u8, String)>,
+    headers: HeaderMap,
+) -> Response {
+    let key = SoundKey { katakana, tone, role };
+    match get_sound(key, user.is_some()).await {
+        Ok(sound) => sound_response(sound, &headers),
+        Err(err) => ApiResponse::<()>(Err(err)).into_response(),
+    }
+}
+
+#[instrument]
+async fn get_any_sound(katakana: String, tone: u8) -> Result<sounds::Model, Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+    sounds::Entity::find()
+        .filter(sounds::Column::Katakana.eq(katakana))
+        .filter(sounds::Column::Tone.eq(tone as i16))
+        .filter(sounds::Column::Checked.eq(true))
+        .order_by_asc(sounds::Column::Role)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound)
+}
+
+#[instrument]
+async fn get_sound(key: SoundKey, content_maintainer: bool) -> Result<sounds::Model, Error> {
+    validate_key(&key)?;
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let sound = sounds::Entity::find_by_id((key.katakana, key.tone as i16, key.role))
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound)?;
+    //对其他用户隐藏未审核和审核未通过的音频
+    if sound.checked != Some(true) && !content_maintainer {
+        return Err(Error::NotFound);
+    }
+    Ok(sound)
+}
+
+fn sound_response(sound: sounds::Model, headers: &HeaderMap) -> Response {
+    //上传时已经检查过，这里仍然不能因为数据库中的内容 panic
+    build_sound_response(sound, headers).unwrap_or_else(|err| {
+        error!("failed to build sound response: {err}");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })
+}
+
+fn build_sound_response(sound: sounds::Model, headers: &HeaderMap) -> Result<Response, http::Error> {
+    if sound.checked != Some(true) {
+        return Response::builder()
+            .header(header::CONTENT_TYPE, sound.content_type)
+            .header(header::CACHE_CONTROL, HeaderValue::from_static(PRIVATE_CACHE_CONTROL))
+            .body(Body::from(sound.sound));
+    }
+
+    let etag = format!("\"{}-{}\"", sound.update_date.timestamp_millis(), sound.sound.len());
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.split(',').any(|it| it.trim() == etag || it.trim() == "*"));
+
+    let builder = Response::builder()
+        .header(header::CACHE_CONTROL, HeaderValue::from_static(PUBLIC_CACHE_CONTROL))
+        .header(header::ETAG, etag);
+    if not_modified {
+        builder.status(StatusCode::NOT_MODIFIED).body(Body::empty())
+    } else {
+        builder
+            .header(header::CONTENT_TYPE, sound.content_type)
+            .body(Body::from(sound.sound))
+    }
+}
@@ -0,0 +1,66 @@
+use http::HeaderValue;
+
+use senyoshu_common::types::api::sound::{MAX_SOUND_SIZE, SoundKey};
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::sound::SoundMeta;
+
+use crate::database::dic::sounds;
+
+pub mod get_sound;
+pub mod review_sound;
+pub mod search_sounds;
+pub mod upload_sound;
+
+const MAX_KATAKANA_LEN: usize = 64;
+const MAX_ROLE_LEN: usize = 32;
+
+fn to_sound_meta(sound: sounds::Model) -> SoundMeta {
+    SoundMeta {
+        update_date: sound.update_date,
+        author: sound.author,
+        katakana: sound.katakana,
+        tone: sound.tone as u8,
+        role: sound.role,
+        content_type: sound.content_type,
+        checked: sound.checked,
+    }
+}
+
+pub(crate) fn validate_key(key: &SoundKey) -> Result<(), Error> {
+    let katakana_len = key.katakana.chars().count();
+    if katakana_len == 0 || katakana_len > MAX_KATAKANA_LEN {
+        return Err(Error::ValidationFailed(String::from("katakana length is invalid")));
+    }
+    //下降的位置不会超过读音的长度
+    if key.tone as usize > katakana_len {
+        return Err(Error::ValidationFailed(String::from("tone is invalid")));
+    }
+    if !is_valid_role(key.role.as_str()) {
+        return Err(Error::ValidationFailed(String::from("role is invalid")));
+    }
+    Ok(())
+}
+
+/// 说话人会出现在路径中，只允许简单的字符
+pub(crate) fn is_valid_role(role: &str) -> bool {
+    !role.is_empty()
+        && role.len() <= MAX_ROLE_LEN
+        && role
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+/// 会原样作为响应头返回，需要是合法的头部值
+pub(crate) fn is_audio_content_type(content_type: &str) -> bool {
+    content_type.starts_with("audio/") && HeaderValue::from_str(content_type).is_ok()
+}
+
+fn validate_sound(content_type: &str, sound: &[u8]) -> Result<(), Error> {
+    if !is_audio_content_type(content_type) {
+        return Err(Error::ValidationFailed(String::from("content type must be audio")));
+    }
+    if sound.is_empty() || sound.len() > MAX_SOUND_SIZE {
+        return Err(Error::ValidationFailed(String::from("sound size is invalid")));
+    }
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use axum::Json;
+use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, Set, TransactionTrait};
+use tracing::instrument;
+
+use senyoshu_common::types::api::sound::SoundKey;
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::sound::SoundMeta;
+
+use crate::api::ApiResponse;
+use crate::api::auth::ContentMaintainer;
+use crate::api::sound::{to_sound_meta, validate_key};
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::dic::sounds;
+
+pub async fn review_sound_api(
+    user: ContentMaintainer,
+    Json((key, pass)): Json<(SoundKey, bool)>,
+) -> ApiResponse<SoundMeta> {
+    review_sound(user.user_info.uid, key, pass).await.into()
+}
+
+#[instrument]
+async fn review_sound(uid: i64, key: SoundKey, pass: bool) -> Result<SoundMeta, Error> {
+    validate_key(&key)?;
+
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
+
+    let row = sounds::Entity::find_by_id((key.katakana, key.tone as i16, key.role))
+        .one(&transaction)
+        .await?
+        .ok_or(Error::NotFound)?;
+    //作者不能审核自己上传的音频
+    if row.author == uid {
+        return Err(Error::PermissionDenied);
+    }
+
+    //审核不修改更新时间，更新时间只随音频本身变化
+    let mut row = row.into_active_model();
+    row.checked = Set(Some(pass));
+    let row = row.update(&transaction).await?;
+
+    transaction.commit().await?;
+    Ok(to_sound_meta(row))
+}
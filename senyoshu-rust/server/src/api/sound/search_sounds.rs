@@ -0,0 +1,74 @@
+use axum::Json;
+use chrono::FixedOffset;
+use sea_orm::{ColumnTrait, EntityTrait, FromQueryResult, QueryFilter, QueryOrder, QuerySelect};
+use tracing::instrument;
+
+use senyoshu_common::types::api::sound::{MAX_SOUND_QUERY_LIMIT, SoundQuery};
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::sound::SoundMeta;
+
+use crate::api::ApiResponse;
+use crate::api::auth::ContentMaintainer;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::dic::sounds;
+
+/// 不查询音频本身
+#[derive(FromQueryResult)]
+struct SoundRow {
+    katakana: String,
+    tone: i16,
+    role: String,
+    content_type: String,
+    author: i64,
+    checked: Option<bool>,
+    update_date: chrono::DateTime<FixedOffset>,
+}
+
+pub async fn search_sounds_api(
+    _: ContentMaintainer,
+    Json(query): Json<SoundQuery>,
+) -> ApiResponse<Vec<SoundMeta>> {
+    search_sounds(query).await.into()
+}
+
+#[instrument]
+async fn search_sounds(query: SoundQuery) -> Result<Vec<SoundMeta>, Error> {
+    let db = GLOBAL_DATABASE.get().unwrap();
+
+    let mut select = sounds::Entity::find().select_only().columns([
+        sounds::Column::Katakana,
+        sounds::Column::Tone,
+        sounds::Column::Role,
+        sounds::Column::ContentType,
+        sounds::Column::Author,
+        sounds::Column::Checked,
+        sounds::Column::UpdateDate,
+    ]);
+    if let Some(katakana) = query.katakana.as_ref().map(|it| it.trim()).filter(|it| !it.is_empty()) {
+        select = select.filter(sounds::Column::Katakana.eq(katakana));
+    }
+    if query.unchecked_only {
+        select = select.filter(sounds::Column::Checked.is_null());
+    }
+
+    let rows = select
+        .order_by_desc(sounds::Column::UpdateDate)
+        .offset(query.offset)
+        .limit(query.limit.clamp(1, MAX_SOUND_QUERY_LIMIT))
+        .into_model::<SoundRow>()
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SoundMeta {
+            update_date: row.update_date,
+            author: row.author,
+            katakana: row.katakana,
+            tone: row.tone as u8,
+            role: row.role,
+            content_type: row.content_type,
+            checked: row.checked,
+        })
+        .collect())
+}
@@ -0,0 +1,64 @@
+use axum::Json;
+use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, Set, TransactionTrait};
+use tracing::instrument;
+
+use senyoshu_common::types::api::sound::SoundUpload;
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::sound::SoundMeta;
+
+use crate::api::account::session::now;
+use crate::api::ApiResponse;
+use crate::api::auth::ContentMaintainer;
+use crate::api::sound::{to_sound_meta, validate_key, validate_sound};
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::dic::sounds;
+
+pub async fn upload_sound_api(
+    user: ContentMaintainer,
+    Json(upload): Json<SoundUpload>,
+) -> ApiResponse<SoundMeta> {
+    upload_sound(user.user_info.uid, upload).await.into()
+}
+
+#[instrument(skip(upload), fields(key = ?upload.key, size = upload.sound.len()))]
+async fn upload_sound(uid: i64, upload: SoundUpload) -> Result<SoundMeta, Error> {
+    validate_key(&upload.key)?;
+    validate_sound(upload.content_type.as_str(), upload.sound.as_slice())?;
+
+    let SoundUpload { key, content_type, sound } = upload;
+    let db = GLOBAL_DATABASE.get().unwrap();
+    let transaction = db.begin().await?;
+
+    let existing = sounds::Entity::find_by_id((key.katakana.to_owned(), key.tone as i16, key.role.to_owned()))
+        .one(&transaction)
+        .await?;
+    //覆盖已有的音频后需要重新审核
+    let row = match existing {
+        Some(row) => {
+            let mut row = row.into_active_model();
+            row.sound = Set(sound);
+            row.content_type = Set(content_type);
+            row.author = Set(uid);
+            row.checked = Set(None);
+            row.update_date = Set(now());
+            row.update(&transaction).await?
+        }
+        None => {
+            sounds::ActiveModel {
+                katakana: Set(key.katakana),
+                tone: Set(key.tone as i16),
+                role: Set(key.role),
+                sound: Set(sound),
+                content_type: Set(content_type),
+                author: Set(uid),
+                checked: Set(None),
+                update_date: Set(now()),
+            }
+                .insert(&transaction)
+                .await?
+        }
+    };
+
+    transaction.commit().await?;
+    Ok(to_sound_meta(row))
+}
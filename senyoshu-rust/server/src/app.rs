@@ -0,0 +1,247 @@
+use axum::middleware::{from_fn, from_fn_with_state};
+use axum::{Extension, Router};
+use axum::routing::{get, post};
+use http::{HeaderValue, Request, Response};
+use http::header::CACHE_CONTROL;
+use tower::ServiceBuilder;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::services::{ServeDir, ServeFile};
+use tower_http::services::fs::ServeFileSystemResponseBody;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tower_http::trace::TraceLayer;
+use tracing::{info_span, Span};
+
+use senyoshu_common::types::api::account::{
+    DELETE_ACCOUNT_API, EXPORT_ACCOUNT_API, GET_OTHER_USER_INFO_API, GET_SESSIONS_API, LOGIN_API,
+    REGISTER_API, REQUEST_EMAIL_CODE_API, REQUEST_PASSWD_RESET_API, RESET_PASSWD_API,
+    REVOKE_OTHER_SESSIONS_API, REVOKE_SESSION_API, UPDATE_PASSWD_API, UPDATE_USER_STATE_API,
+    VERIFY_EMAIL_API,
+};
+use senyoshu_common::types::api::admin::{
+    GET_SURF_CREDENTIALS_API, GET_SURF_SERVER_CONFIGS_API, ROTATE_SURF_CREDENTIALS_API,
+    SAVE_SURF_SERVER_API, SEARCH_USERS_API, SET_RESTRICT_API, SET_USER_ROLE_API, SET_VIP_API,
+};
+use senyoshu_common::types::api::api::GET_SURF_SERVERS_API;
+use senyoshu_common::types::api::comment::{
+    CREATE_COMMENT_API, DELETE_COMMENT_API, EDIT_COMMENT_API, LIST_COMMENTS_API,
+};
+use senyoshu_common::types::api::dic::{CREATE_WORD_API, DIC_SNAPSHOT_MANIFEST, DIC_SNAPSHOT_PATH, DELETE_WORD_API, GET_CHANGE_REQUEST_API, GET_MODERATION_LOG_API, GET_MY_SUBMISSIONS_API, GET_WORD_BY_PID_API, GET_WORD_HISTORY_API, POST_WORD_API, SET_ADOPTED_API, SYNC_DIC_API, UPDATE_MANY_API};
+use senyoshu_common::types::api::learn::{GET_RECORD_API, POST_LEARN_RECORD_API};
+use senyoshu_common::types::api::push::PUSH_PATH;
+use senyoshu_common::types::api::sound::{
+    REVIEW_SOUND_API, SEARCH_SOUNDS_API, SOUND_PATH, UPLOAD_SOUND_API,
+};
+
+use crate::api::account::delete_account::delete_account_api;
+use crate::api::account::export_account::export_account_api;
+use crate::api::account::get_other_user_info::get_other_user_info_api;
+use crate::api::account::get_sessions::get_sessions_api;
+use crate::api::account::login::login_api;
+use crate::api::account::register::register_api;
+use crate::api::account::request_email_code::request_email_code_api;
+use crate::api::account::request_passwd_reset::request_passwd_reset_api;
+use crate::api::account::reset_passwd::reset_passwd_api;
+use crate::api::account::revoke_other_sessions::revoke_other_sessions_api;
+use crate::api::account::revoke_session::revoke_session_api;
+use crate::api::account::update_passwd::update_passwd_api;
+use crate::api::account::update_user_state::update_user_state_api;
+use crate::api::account::verify_email::verify_email_api;
+use crate::api::admin::get_surf_credentials::get_surf_credentials_api;
+use crate::api::admin::get_surf_server_configs::get_surf_server_configs_api;
+use crate::api::admin::rotate_surf_credentials::rotate_surf_credentials_api;
+use crate::api::admin::save_surf_server::save_surf_server_api;
+use crate::api::admin::search_users::search_users_api;
+use crate::api::admin::set_restrict::set_restrict_api;
+use crate::api::admin::set_user_role::set_user_role_api;
+use crate::api::admin::set_vip::set_vip_api;
+use crate::api::AxumAPi;
+use crate::api::comment::create_comment::create_comment_api;
+use crate::api::comment::delete_comment::delete_comment_api;
+use crate::api::comment::edit_comment::edit_comment_api;
+use crate::api::comment::list_comments::list_comments_api;
+use crate::api::dic::create_word::create_word_api;
+use crate::api::dic::delete_word::delete_word_api;
+use crate::api::dic::get_change_request::get_change_request_api;
+use crate::api::dic::get_moderation_log::get_moderation_log_api;
+use crate::api::dic::get_my_submissions::get_my_submissions_api;
+use crate::api::dic::get_word_by_pid::get_word_by_pid_api;
+use crate::api::dic::get_word_history::get_word_history_api;
+use crate::api::dic::post_word::post_word_api;
+use crate::api::dic::set_adopted::set_adopted_api;
+use crate::api::dic::sync_dic::sync_dic_api;
+use crate::api::dic::update_many::update_many_api;
+use crate::api::get_surf_servers::get_surf_servers_api;
+use crate::api::learn::get_record::get_record_api;
+use crate::api::learn::post_record::post_learn_record_api;
+use crate::api::push::push_api;
+use crate::api::sound::get_sound::{get_any_sound_api, get_sound_api};
+use crate::api::sound::review_sound::review_sound_api;
+use crate::api::sound::search_sounds::search_sounds_api;
+use crate::api::sound::upload_sound::upload_sound_api;
+use crate::config::{Config, CorsConfig, MetricsConfig, SnapshotConfig};
+use crate::health::{healthz, readyz};
+use crate::mail::MailService;
+use crate::metrics::{metrics_api, track_metrics};
+use crate::rate_limit::{limit_login, limit_mail, limit_register, RateLimiter};
+
+/// 服务端的完整路由，数据库需要先通过 `GlobalDatabase::init_database` 初始化
+///
+/// 限流需要客户端地址，使用 `into_make_service_with_connect_info::<SocketAddr>` 启动
+pub fn app(config: &Config) -> Router {
+    let static_dir = config.server.static_dir.to_owned();
+    let limiter = RateLimiter::new(config.rate_limit.to_owned());
+    //配置已经在 `Config::validate` 中检查过
+    let mail = MailService::new(&config.mail).unwrap();
+    Router::new()
+        .nest_service("", ServeDir::new(&static_dir).fallback(ServeFile::new(static_dir.join("index.html"))))
+        //account
+        .set_auth_api_handle(UPDATE_USER_STATE_API, update_user_state_api)
+        .set_api_handle(GET_OTHER_USER_INFO_API, get_other_user_info_api)
+        .merge(
+            Router::new()
+                .set_api_handle_with(LOGIN_API, login_api)
+                //需要旧密码，与登录共用失败锁定
+                .set_api_handle(UPDATE_PASSWD_API, update_passwd_api)
+                .route_layer(from_fn_with_state(limiter.to_owned(), limit_login)),
+        )
+        .merge(
+            Router::new()
+                .set_api_handle(REGISTER_API, register_api)
+                .route_layer(from_fn_with_state(limiter.to_owned(), limit_register)),
+        )
+        .merge(
+            Router::new()
+                .set_auth_api_handle(REQUEST_EMAIL_CODE_API, request_email_code_api)
+                .set_api_handle_with(REQUEST_PASSWD_RESET_API, request_passwd_reset_api)
+                .route_layer(from_fn_with_state(limiter, limit_mail)),
+        )
+        .set_auth_api_handle(VERIFY_EMAIL_API, verify_email_api)
+        .set_api_handle(RESET_PASSWD_API, reset_passwd_api)
+        //根据 Accept 请求头返回 json 或 CBOR，响应不一定是 `ApiResponse`
+        .route(EXPORT_ACCOUNT_API.path().as_str(), post(export_account_api))
+        .set_auth_api_handle(DELETE_ACCOUNT_API, delete_account_api)
+        .set_auth_api_handle(GET_SESSIONS_API, get_sessions_api)
+        .set_auth_api_handle(REVOKE_SESSION_API, revoke_session_api)
+        .set_auth_api_handle(REVOKE_OTHER_SESSIONS_API, revoke_other_sessions_api)
+        //admin
+        .set_auth_api_handle(SEARCH_USERS_API, search_users_api)
+        .set_auth_api_handle(SET_USER_ROLE_API, set_user_role_api)
+        .set_auth_api_handle(SET_VIP_API, set_vip_api)
+        .set_auth_api_handle(SET_RESTRICT_API, set_restrict_api)
+        .set_auth_api_handle(GET_SURF_SERVER_CONFIGS_API, get_surf_server_configs_api)
+        .set_auth_api_handle(SAVE_SURF_SERVER_API, save_surf_server_api)
+        .set_auth_api_handle(ROTATE_SURF_CREDENTIALS_API, rotate_surf_credentials_api)
+        .set_auth_api_handle(GET_SURF_CREDENTIALS_API, get_surf_credentials_api)
+        //dic
+        .set_auth_api_handle(CREATE_WORD_API, create_word_api)
+        .set_auth_api_handle(DELETE_WORD_API, delete_word_api)
+        //根据 Accept 请求头返回 json 或压缩的 CBOR
+        .route(SYNC_DIC_API.path().as_str(), post(sync_dic_api))
+        .set_auth_api_handle(GET_CHANGE_REQUEST_API, get_change_request_api)
+        .set_api_handle(GET_WORD_BY_PID_API, get_word_by_pid_api)
+        .set_api_handle(GET_WORD_HISTORY_API, get_word_history_api)
+        .set_auth_api_handle(POST_WORD_API, post_word_api)
+        .set_auth_api_handle(SET_ADOPTED_API, set_adopted_api)
+        .set_auth_api_handle(UPDATE_MANY_API, update_many_api)
+        .set_auth_api_handle(GET_MODERATION_LOG_API, get_moderation_log_api)
+        .set_auth_api_handle(GET_MY_SUBMISSIONS_API, get_my_submissions_api)
+        //comment
+        .set_api_handle(LIST_COMMENTS_API, list_comments_api)
+        .set_auth_api_handle(CREATE_COMMENT_API, create_comment_api)
+        .set_auth_api_handle(EDIT_COMMENT_API, edit_comment_api)
+        .set_auth_api_handle(DELETE_COMMENT_API, delete_comment_api)
+        //learn
+        .set_auth_api_handle(POST_LEARN_RECORD_API, post_learn_record_api)
+        .set_auth_api_handle(GET_RECORD_API, get_record_api)
+        //push
+        .route(PUSH_PATH, get(push_api))
+        //sound
+        .set_auth_api_handle(UPLOAD_SOUND_API, upload_sound_api)
+        .set_auth_api_handle(REVIEW_SOUND_API, review_sound_api)
+        .set_auth_api_handle(SEARCH_SOUNDS_API, search_sounds_api)
+        //音频直接返回二进制，可以被浏览器和 CDN 缓存
+        .route(format!("{SOUND_PATH}/:katakana/:tone").as_str(), get(get_any_sound_api))
+        .route(format!("{SOUND_PATH}/:katakana/:tone/:role").as_str(), get(get_sound_api))
+        //surf
+        .set_auth_api_handle(GET_SURF_SERVERS_API, get_surf_servers_api)
+        //snapshot
+        .merge(snapshot_router(&config.snapshot))
+        //health
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .merge(metrics_router(&config.metrics))
+        //other settings
+        .layer(from_fn(track_metrics))
+        .layer(Extension(mail))
+        .layer(cors_layer(&config.cors))
+        //请求 ID 记录在每个请求的 span 中，处理函数中 `#[instrument]` 的 span 都在它之下
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+                .layer(TraceLayer::new_for_http().make_span_with(request_span))
+                .layer(PropagateRequestIdLayer::x_request_id()),
+        )
+}
+
+/// 反向代理已经设置了 `x-request-id` 时沿用，否则生成新的
+fn request_span<B>(request: &Request<B>) -> Span {
+    let request_id = request
+        .headers()
+        .get("x-request-id")
+        .and_then(|it| it.to_str().ok())
+        .unwrap_or_default();
+    info_span!(
+        "request",
+        request_id,
+        method = %request.method(),
+        path = request.uri().path(),
+    )
+}
+
+/// 快照文件不会变化，可以一直缓存，清单每次都需要向服务器确认
+fn snapshot_router(snapshot: &SnapshotConfig) -> Router {
+    if !snapshot.enabled {
+        return Router::new();
+    }
+    let manifest = ServiceBuilder::new()
+        .layer(SetResponseHeaderLayer::overriding(CACHE_CONTROL, HeaderValue::from_static("no-cache")))
+        .service(ServeFile::new(snapshot.dir.join(DIC_SNAPSHOT_MANIFEST)));
+    //不存在的快照不能被缓存
+    let immutable = |response: &Response<ServeFileSystemResponseBody>| {
+        response
+            .status()
+            .is_success()
+            .then(|| HeaderValue::from_static("public, max-age=31536000, immutable"))
+    };
+    let snapshots = ServiceBuilder::new()
+        .layer(SetResponseHeaderLayer::overriding(CACHE_CONTROL, immutable))
+        .service(ServeDir::new(&snapshot.dir));
+    Router::new()
+        .route_service(format!("{DIC_SNAPSHOT_PATH}/{DIC_SNAPSHOT_MANIFEST}").as_str(), manifest)
+        .nest_service(DIC_SNAPSHOT_PATH, snapshots)
+}
+
+fn metrics_router(metrics: &MetricsConfig) -> Router {
+    if !metrics.enabled {
+        return Router::new();
+    }
+    let token = metrics.token.to_owned();
+    Router::new().route("/metrics", get(move |headers| metrics_api(token.to_owned(), headers)))
+}
+
+fn cors_layer(cors: &CorsConfig) -> CorsLayer {
+    if cors.is_permissive() {
+        CorsLayer::permissive()
+    } else {
+        let origins = cors
+            .origins
+            .iter()
+            .filter_map(|origin| HeaderValue::from_str(origin).ok())
+            .collect::<Vec<_>>();
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::list(origins))
+            .allow_methods(AllowMethods::mirror_request())
+            .allow_headers(AllowHeaders::mirror_request())
+    }
+}
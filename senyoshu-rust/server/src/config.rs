@@ -0,0 +1,565 @@
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use http::HeaderValue;
+use serde::{Deserialize, Serialize};
+use tracing_subscriber::EnvFilter;
+
+use crate::api::sound::is_valid_role;
+use crate::database::database::is_sqlite_memory;
+use crate::mail::build_mailer;
+use crate::tts::build_engine;
+
+pub const DEFAULT_CONFIG_FILE: &str = "senyoshu.toml";
+
+const ENV_PREFIX: &str = "SENYOSHU_";
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub database: DatabaseConfig,
+    pub server: ServerConfig,
+    pub log: LogConfig,
+    pub cors: CorsConfig,
+    pub rate_limit: RateLimitConfig,
+    pub mail: MailConfig,
+    pub tts: TtsConfig,
+    pub snapshot: SnapshotConfig,
+    pub metrics: MetricsConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    pub url: String,
+    /// 启动时执行尚未应用的迁移，关闭时需要手动执行 `migrate up`
+    pub auto_migrate: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub bind: String,
+    pub static_dir: PathBuf,
+    /// 收到 SIGTERM 后等待连接关闭的最长时间
+    pub shutdown_timeout_secs: u64,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind: String::from("0.0.0.0:8000"),
+            static_dir: PathBuf::from("../target/dist"),
+            shutdown_timeout_secs: 30,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+    /// `EnvFilter` 语法，例如 `info,sqlx=warn`
+    pub level: String,
+    pub format: LogFormat,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            level: String::from("info"),
+            format: LogFormat::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Pretty,
+    Json,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorsConfig {
+    /// 包含 `*` 时允许任意来源
+    pub origins: Vec<String>,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            origins: Vec::from([String::from("*")]),
+        }
+    }
+}
+
+impl CorsConfig {
+    pub fn is_permissive(&self) -> bool {
+        self.origins.iter().any(|it| it == "*")
+    }
+}
+
+/// 登录和注册的限流，计数保存在进程内
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    /// 部署在反向代理之后时，从 `X-Forwarded-For` 的最后一项读取客户端地址
+    pub trust_forwarded_for: bool,
+    pub login_per_ip: Quota,
+    pub login_per_username: Quota,
+    pub register_per_ip: Quota,
+    /// 未登录时请求发送邮件，例如重置密码
+    pub mail_per_ip: Quota,
+    /// 同一用户名连续登录失败多少次后开始锁定
+    pub lockout_threshold: u32,
+    /// 第一次锁定的时长，之后每失败一次翻倍
+    pub lockout_base_secs: u64,
+    pub lockout_max_secs: u64,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            trust_forwarded_for: false,
+            login_per_ip: Quota { requests: 30, per_secs: 60 },
+            login_per_username: Quota { requests: 10, per_secs: 60 },
+            register_per_ip: Quota { requests: 5, per_secs: 60 * 60 },
+            mail_per_ip: Quota { requests: 5, per_secs: 60 * 60 },
+            lockout_threshold: 5,
+            lockout_base_secs: 30,
+            lockout_max_secs: 60 * 60,
+        }
+    }
+}
+
+/// `per_secs` 秒内最多 `requests` 次
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Quota {
+    pub requests: u32,
+    pub per_secs: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct MailConfig {
+    pub transport: MailTransport,
+    /// 发件人，例如 `senyoshu <noreply@example.com>`
+    pub from: String,
+    /// 客户端的地址，用于重置密码邮件中的链接，为空时邮件中只有 token
+    pub public_url: String,
+    /// `transport = "file"` 时邮件写入的目录
+    pub dir: PathBuf,
+    pub smtp: SmtpConfig,
+}
+
+impl Default for MailConfig {
+    fn default() -> Self {
+        Self {
+            transport: MailTransport::default(),
+            from: String::from("senyoshu <noreply@localhost>"),
+            public_url: String::new(),
+            dir: PathBuf::from("mails"),
+            smtp: SmtpConfig::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MailTransport {
+    /// 只写入日志，用于本地开发，正文只在 debug 级别输出
+    #[default]
+    Log,
+    /// 每封邮件写入 `dir` 中的一个文件，用于本地开发和测试
+    File,
+    Smtp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct SmtpConfig {
+    pub host: String,
+    /// 默认根据 `tls` 选择 587、465 或 25
+    pub port: Option<u16>,
+    pub username: String,
+    pub password: String,
+    pub tls: SmtpTls,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    #[default]
+    Starttls,
+    Tls,
+    /// 不加密，只用于本机的中继
+    None,
+}
+
+/// 后台为没有音频的词生成语音，生成的音频需要审核者审核
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct TtsConfig {
+    pub engine: TtsEngineKind,
+    /// 生成的音频使用的说话人
+    pub role: String,
+    /// 两次扫描之间的间隔
+    pub interval_secs: u64,
+    /// 每次扫描最多生成多少个音频
+    pub batch_size: usize,
+    pub command: TtsCommandConfig,
+}
+
+impl Default for TtsConfig {
+    fn default() -> Self {
+        Self {
+            engine: TtsEngineKind::default(),
+            role: String::from("tts"),
+            interval_secs: 10 * 60,
+            batch_size: 100,
+            command: TtsCommandConfig::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TtsEngineKind {
+    /// 不生成
+    #[default]
+    None,
+    /// 调用外部命令，音频从标准输出读取
+    Command,
+    /// 根据读音和声调生成确定的提示音，用于本地开发和测试
+    Test,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct TtsCommandConfig {
+    pub program: String,
+    /// 参数中的 `{katakana}`、`{tone}` 和 `{role}` 会被替换
+    pub args: Vec<String>,
+    /// 标准输出的音频格式
+    pub content_type: String,
+    pub timeout_secs: u64,
+}
+
+impl Default for TtsCommandConfig {
+    fn default() -> Self {
+        Self {
+            program: String::new(),
+            args: Vec::new(),
+            content_type: String::from("audio/wav"),
+            timeout_secs: 30,
+        }
+    }
+}
+
+/// 定期生成词典快照，通过 `/snapshot` 提供给新的客户端
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct SnapshotConfig {
+    pub enabled: bool,
+    /// 快照和清单保存的目录
+    pub dir: PathBuf,
+    pub interval_secs: u64,
+    /// 保留最近几个快照，下载到一半的客户端仍然可以完成下载
+    pub keep: usize,
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: PathBuf::from("snapshots"),
+            interval_secs: 60 * 60,
+            keep: 3,
+        }
+    }
+}
+
+/// `/metrics` 以 Prometheus 的文本格式输出，其中有各 API 的流量和待审核的数量，默认关闭
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    /// 不为空时需要 `Authorization: Bearer <token>`
+    pub token: String,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, toml::de::Error),
+    Env(String, String),
+    Invalid(Vec<String>),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(path, err) => write!(f, "can not read {}: {err}", path.display()),
+            ConfigError::Parse(path, err) => write!(f, "can not parse {}: {err}", path.display()),
+            ConfigError::Env(key, value) => write!(f, "illegal value of {key}: {value}"),
+            ConfigError::Invalid(problems) => {
+                write!(f, "invalid config:")?;
+                for problem in problems {
+                    write!(f, "\n  - {problem}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// 读取配置文件并应用环境变量，`path` 为 `None` 时若当前目录下存在 [`DEFAULT_CONFIG_FILE`] 则读取它
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let path = match path {
+            Some(path) => Some(path.to_path_buf()),
+            None => {
+                let default_path = PathBuf::from(DEFAULT_CONFIG_FILE);
+                default_path.exists().then_some(default_path)
+            }
+        };
+
+        let mut config = if let Some(path) = path {
+            let content =
+                std::fs::read_to_string(&path).map_err(|err| ConfigError::Io(path.clone(), err))?;
+            toml::from_str::<Config>(content.as_str()).map_err(|err| ConfigError::Parse(path, err))?
+        } else {
+            Config::default()
+        };
+
+        config.apply_env()?;
+        Ok(config)
+    }
+
+    fn apply_env(&mut self) -> Result<(), ConfigError> {
+        if let Some(url) = get_env("DATABASE_URL") {
+            self.database.url = url;
+        }
+        if let Some(auto_migrate) = get_env("DATABASE_AUTO_MIGRATE") {
+            self.database.auto_migrate = parse_bool("DATABASE_AUTO_MIGRATE", auto_migrate)?;
+        }
+        if let Some(bind) = get_env("BIND") {
+            self.server.bind = bind;
+        }
+        if let Some(static_dir) = get_env("STATIC_DIR") {
+            self.server.static_dir = PathBuf::from(static_dir);
+        }
+        if let Some(level) = get_env("LOG_LEVEL") {
+            self.log.level = level;
+        }
+        if let Some(format) = get_env("LOG_FORMAT") {
+            self.log.format = match format.to_lowercase().as_str() {
+                "pretty" => LogFormat::Pretty,
+                "json" => LogFormat::Json,
+                _ => return Err(ConfigError::Env(format!("{ENV_PREFIX}LOG_FORMAT"), format)),
+            };
+        }
+        if let Some(origins) = get_env("CORS_ORIGINS") {
+            self.cors.origins = origins
+                .split(',')
+                .map(|it| it.trim().to_string())
+                .filter(|it| !it.is_empty())
+                .collect();
+        }
+        if let Some(enabled) = get_env("RATE_LIMIT_ENABLED") {
+            self.rate_limit.enabled = parse_bool("RATE_LIMIT_ENABLED", enabled)?;
+        }
+        if let Some(trust) = get_env("RATE_LIMIT_TRUST_FORWARDED_FOR") {
+            self.rate_limit.trust_forwarded_for = parse_bool("RATE_LIMIT_TRUST_FORWARDED_FOR", trust)?;
+        }
+        if let Some(transport) = get_env("MAIL_TRANSPORT") {
+            self.mail.transport = match transport.to_lowercase().as_str() {
+                "log" => MailTransport::Log,
+                "file" => MailTransport::File,
+                "smtp" => MailTransport::Smtp,
+                _ => return Err(ConfigError::Env(format!("{ENV_PREFIX}MAIL_TRANSPORT"), transport)),
+            };
+        }
+        if let Some(engine) = get_env("TTS_ENGINE") {
+            self.tts.engine = match engine.to_lowercase().as_str() {
+                "none" => TtsEngineKind::None,
+                "command" => TtsEngineKind::Command,
+                "test" => TtsEngineKind::Test,
+                _ => return Err(ConfigError::Env(format!("{ENV_PREFIX}TTS_ENGINE"), engine)),
+            };
+        }
+        if let Some(enabled) = get_env("SNAPSHOT_ENABLED") {
+            self.snapshot.enabled = parse_bool("SNAPSHOT_ENABLED", enabled)?;
+        }
+        if let Some(dir) = get_env("SNAPSHOT_DIR") {
+            self.snapshot.dir = PathBuf::from(dir);
+        }
+        if let Some(enabled) = get_env("METRICS_ENABLED") {
+            self.metrics.enabled = parse_bool("METRICS_ENABLED", enabled)?;
+        }
+        if let Some(token) = get_env("METRICS_TOKEN") {
+            self.metrics.token = token;
+        }
+        if let Some(password) = get_env("SMTP_PASSWORD") {
+            self.mail.smtp.password = password;
+        }
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+
+        if self.database.url.is_empty() {
+            problems.push(format!(
+                "database.url is empty, set it in config file or {ENV_PREFIX}DATABASE_URL"
+            ));
+        } else if self.database.url.starts_with("sqlite:") {
+            if !cfg!(feature = "sqlite") {
+                problems.push(String::from(
+                    "database.url is sqlite, but the server is built without feature `sqlite`",
+                ));
+            }
+            if is_sqlite_memory(self.database.url.as_str()) && !self.database.auto_migrate {
+                problems.push(String::from(
+                    "sqlite memory database requires database.auto_migrate = true",
+                ));
+            }
+        } else if self.database.url.starts_with("postgres") {
+            if !cfg!(feature = "postgres") {
+                problems.push(String::from(
+                    "database.url is postgres, but the server is built without feature `postgres`",
+                ));
+            }
+        } else {
+            problems.push(String::from(
+                "database.url should start with `postgres://` or `sqlite:`",
+            ));
+        }
+        if self.server.bind.parse::<SocketAddr>().is_err() {
+            problems.push(format!(
+                "server.bind `{}` is not a socket address",
+                self.server.bind
+            ));
+        }
+        if let Err(err) = EnvFilter::try_new(self.log.level.as_str()) {
+            problems.push(format!("log.level `{}` is illegal: {err}", self.log.level));
+        }
+        if !self.cors.is_permissive() {
+            for origin in self.cors.origins.iter() {
+                if HeaderValue::from_str(origin).is_err() {
+                    problems.push(format!("cors.origins `{origin}` is illegal"));
+                }
+            }
+        }
+
+        for (name, quota) in [
+            ("login_per_ip", self.rate_limit.login_per_ip),
+            ("login_per_username", self.rate_limit.login_per_username),
+            ("register_per_ip", self.rate_limit.register_per_ip),
+            ("mail_per_ip", self.rate_limit.mail_per_ip),
+        ] {
+            if quota.requests == 0 || quota.per_secs == 0 {
+                problems.push(format!(
+                    "rate_limit.{name} should have positive requests and per_secs"
+                ));
+            }
+        }
+        if self.rate_limit.lockout_threshold == 0 {
+            problems.push(String::from("rate_limit.lockout_threshold should be positive"));
+        }
+        if self.rate_limit.lockout_base_secs > self.rate_limit.lockout_max_secs {
+            problems.push(String::from(
+                "rate_limit.lockout_base_secs is greater than rate_limit.lockout_max_secs",
+            ));
+        }
+        if let Err(err) = build_mailer(&self.mail) {
+            problems.push(format!("mail is illegal: {err}"));
+        }
+        if let Err(err) = build_engine(&self.tts) {
+            problems.push(format!("tts is illegal: {err}"));
+        }
+        if self.tts.engine != TtsEngineKind::None {
+            if !is_valid_role(self.tts.role.as_str()) {
+                problems.push(format!("tts.role `{}` is illegal", self.tts.role));
+            }
+            if self.tts.interval_secs == 0 || self.tts.batch_size == 0 {
+                problems.push(String::from(
+                    "tts.interval_secs and tts.batch_size should be positive",
+                ));
+            }
+        }
+        if self.snapshot.enabled && (self.snapshot.interval_secs == 0 || self.snapshot.keep == 0) {
+            problems.push(String::from(
+                "snapshot.interval_secs and snapshot.keep should be positive",
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(problems))
+        }
+    }
+
+    /// 不影响启动但可能是配置错误的项，初始化日志之后输出
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        //只提供 API 的部署没有静态文件
+        if !self.server.static_dir.is_dir() {
+            warnings.push(format!(
+                "server.static_dir `{}` is not a directory, only the api is served",
+                self.server.static_dir.display()
+            ));
+        }
+        if self.metrics.enabled && self.metrics.token.is_empty() {
+            warnings.push(String::from(
+                "metrics.token is empty, /metrics is readable by anyone",
+            ));
+        }
+        warnings
+    }
+
+    /// 用于 `--check-config` 的输出，隐藏数据库和 SMTP 的密码以及 metrics 的 token
+    pub fn to_redacted_toml(&self) -> String {
+        let mut config = self.to_owned();
+        config.database.url = redact_url(config.database.url.as_str());
+        if !config.mail.smtp.password.is_empty() {
+            config.mail.smtp.password = String::from("***");
+        }
+        if !config.metrics.token.is_empty() {
+            config.metrics.token = String::from("***");
+        }
+        toml::to_string_pretty(&config).unwrap_or_default()
+    }
+}
+
+fn get_env(key: &str) -> Option<String> {
+    env::var(format!("{ENV_PREFIX}{key}")).ok()
+}
+
+fn parse_bool(key: &str, value: String) -> Result<bool, ConfigError> {
+    match value.to_lowercase().as_str() {
+        "1" | "true" | "yes" => Ok(true),
+        "0" | "false" | "no" => Ok(false),
+        _ => Err(ConfigError::Env(format!("{ENV_PREFIX}{key}"), value)),
+    }
+}
+
+fn redact_url(url: &str) -> String {
+    if let Some((scheme, rest)) = url.split_once("://") {
+        if let Some((user_info, host)) = rest.rsplit_once('@') {
+            let user = user_info.split(':').next().unwrap_or_default();
+            return format!("{scheme}://{user}:***@{host}");
+        }
+    }
+    url.to_string()
+}
@@ -1,13 +1,20 @@
+use std::fmt::{Debug, Formatter};
+
 use chrono::{DateTime, FixedOffset};
-use sea_orm::{ActiveModelBehavior, DeriveEntityModel, DeriveRelation, EnumIter};
+use sea_orm::{
+    ActiveModelBehavior, ColumnTrait, ConnectionTrait, DbErr, DeriveActiveEnum, DeriveEntityModel,
+    DeriveRelation, EnumIter, QueryFilter,
+};
+use sea_orm::prelude::Expr;
 use sea_orm::DerivePrimaryKey;
 use sea_orm::EntityTrait;
 use sea_orm::PrimaryKeyTrait;
 use serde::Serialize;
 
 use senyoshu_common::types::api::session::SessionVec;
+use senyoshu_common::util::redact::Redacted;
 
-#[derive(Serialize, Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[derive(Serialize, Clone, PartialEq, Eq, DeriveEntityModel)]
 #[sea_orm(table_name = "account")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -19,14 +26,19 @@ pub struct Model {
     pub e_mail: Option<String>,
     #[serde(skip)]
     pub passwd_hash2: String,
+    #[serde(skip)]
+    pub passwd_hash_version: PasswdHashVersion,
 
     //permission
     #[sea_orm(default_value = true)]
     pub post_permission: bool,
     #[sea_orm(default_value = false)]
     pub restrict_user: bool,
+    pub restrict_reason: Option<String>,
     #[sea_orm(default_value = false)]
     pub content_maintainer: bool,
+    #[sea_orm(default_value = false)]
+    pub admin: bool,
 
     pub vip: Option<DateTime<FixedOffset>>,
 
@@ -34,8 +46,50 @@ pub struct Model {
     pub sessions: Option<SessionVec>,
 }
 
+/// `passwd_hash2` 的格式
+#[derive(EnumIter, DeriveActiveEnum, Clone, Copy, Debug, PartialEq, Eq)]
+#[sea_orm(rs_type = "i16", db_type = "SmallInteger")]
+pub enum PasswdHashVersion {
+    /// 固定盐的 Blake2b，只用于校验旧账号，登录成功后会升级
+    Legacy = 0,
+    /// Argon2id 的 PHC 字符串，包含每个用户独立的盐和参数
+    Argon2id = 1,
+}
+
+impl Debug for Model {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Model")
+            .field("uid", &self.uid)
+            .field("register_date", &self.register_date)
+            .field("username", &self.username)
+            .field("e_mail", &self.e_mail)
+            .field("passwd_hash2", &Redacted)
+            .field("passwd_hash_version", &self.passwd_hash_version)
+            .field("post_permission", &self.post_permission)
+            .field("restrict_user", &self.restrict_user)
+            .field("restrict_reason", &self.restrict_reason)
+            .field("content_maintainer", &self.content_maintainer)
+            .field("admin", &self.admin)
+            .field("vip", &self.vip)
+            .field("sessions", &self.sessions)
+            .finish()
+    }
+}
+
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {}
 
 impl ActiveModelBehavior for ActiveModel {}
 
+
+/// 设置或撤销管理员，返回该用户是否存在
+///
+/// 第一个管理员只能通过命令行 `senyoshu-server set-admin` 设置
+pub async fn set_admin<C: ConnectionTrait>(username: &str, admin: bool, db: &C) -> Result<bool, DbErr> {
+    let result = Entity::update_many()
+        .filter(Column::Username.eq(username))
+        .col_expr(Column::Admin, Expr::value(admin))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
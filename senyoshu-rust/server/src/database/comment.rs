@@ -0,0 +1,25 @@
+use chrono::FixedOffset;
+use sea_orm::entity::prelude::*;
+
+/// 词条或修改请求下的评论，`wid` 和 `pid` 有且只有一个
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "comment")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub wid: Option<i64>,
+    pub pid: Option<i64>,
+    pub parent: Option<i64>,
+    pub author: i64,
+    #[sea_orm(column_type = "Text")]
+    pub content: String,
+    pub deleted: bool,
+    #[sea_orm(default_value = "now()")]
+    pub create_time: chrono::DateTime<FixedOffset>,
+    pub edit_time: Option<chrono::DateTime<FixedOffset>>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
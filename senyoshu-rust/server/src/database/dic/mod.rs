@@ -1,3 +1,4 @@
+pub mod moderation_log;
 pub mod sounds;
 pub mod word_history;
 pub mod words;
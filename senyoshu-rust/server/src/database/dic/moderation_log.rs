@@ -0,0 +1,45 @@
+use chrono::FixedOffset;
+use sea_orm::entity::prelude::*;
+use sea_orm::ActiveValue::{NotSet, Set};
+
+use senyoshu_common::types::moderation::ModerationAction;
+use senyoshu_common::types::word::wid::WordIdentity;
+
+/// 审核操作的记录，只追加，不提供修改和删除
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "moderation_log")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub actor: i64,
+    pub action: ModerationAction,
+    pub pid: Option<i64>,
+    /// `WordIdentity` 没有实现 `Nullable`，存为 i64
+    pub wid: Option<i64>,
+    pub comment: Option<String>,
+    #[sea_orm(default_value = "now()")]
+    pub create_time: chrono::DateTime<FixedOffset>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// 与对应的修改在同一个事务中插入
+pub fn entry(
+    actor: i64,
+    action: ModerationAction,
+    pid: Option<i64>,
+    wid: Option<WordIdentity>,
+) -> ActiveModel {
+    ActiveModel {
+        id: NotSet,
+        actor: Set(actor),
+        action: Set(action),
+        pid: Set(pid),
+        wid: Set(wid.map(|it| it.0)),
+        comment: Set(None),
+        create_time: NotSet,
+    }
+}
@@ -11,6 +11,10 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub role: String,
     pub sound: Vec<u8>,
+    pub content_type: String,
+    pub author: i64,
+    /// `None` 为尚未审核
+    pub checked: Option<bool>,
     #[sea_orm(default_value = "now()")]
     pub update_date: chrono::DateTime<FixedOffset>,
 }
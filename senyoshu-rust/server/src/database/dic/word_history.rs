@@ -19,6 +19,10 @@ pub struct Model {
     pub state: State,
     #[sea_orm(default_value = "now()")]
     pub update_date: chrono::DateTime<FixedOffset>,
+    /// 通过或驳回这个请求的审核者
+    pub reviewer: Option<i64>,
+    pub review_date: Option<chrono::DateTime<FixedOffset>>,
+    pub review_comment: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,33 @@
+use chrono::FixedOffset;
+use sea_orm::entity::prelude::*;
+
+/// 发送到邮箱的一次性验证码和重置密码的 token，只保存哈希
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "mail_token")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub uid: i64,
+    pub purpose: MailPurpose,
+    /// 收件地址，验证邮箱时即为待绑定的地址
+    pub e_mail: String,
+    pub token_hash: String,
+    /// 验证码输错的次数
+    pub attempts: i16,
+    pub used: bool,
+    #[sea_orm(default_value = "now()")]
+    pub create_time: chrono::DateTime<FixedOffset>,
+    pub expire_time: chrono::DateTime<FixedOffset>,
+}
+
+#[derive(EnumIter, DeriveActiveEnum, Clone, Copy, Debug, PartialEq, Eq)]
+#[sea_orm(rs_type = "i16", db_type = "SmallInteger")]
+pub enum MailPurpose {
+    VerifyEmail = 0,
+    ResetPasswd = 1,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
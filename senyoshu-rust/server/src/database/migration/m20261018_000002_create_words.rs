@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Words::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Words::Wid)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Words::WordDefine).json_binary().null())
+                    .col(
+                        ColumnDef::new(Words::UpdateDate)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Words::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Words {
+    Table,
+    Wid,
+    WordDefine,
+    UpdateDate,
+}
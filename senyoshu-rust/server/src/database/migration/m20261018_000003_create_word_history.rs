@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WordHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WordHistory::Pid)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WordHistory::PostDate)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(WordHistory::Author).big_integer().not_null())
+                    .col(ColumnDef::new(WordHistory::Wid).big_integer().not_null())
+                    .col(ColumnDef::new(WordHistory::WordDefine).json_binary().not_null())
+                    .col(ColumnDef::new(WordHistory::State).small_integer().not_null())
+                    .col(
+                        ColumnDef::new(WordHistory::UpdateDate)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WordHistory::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum WordHistory {
+    Table,
+    Pid,
+    PostDate,
+    Author,
+    Wid,
+    WordDefine,
+    State,
+    UpdateDate,
+}
@@ -0,0 +1,52 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Learn::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Learn::Uid).big_integer().not_null())
+                    .col(ColumnDef::new(Learn::KnowledgeType).integer().not_null())
+                    .col(ColumnDef::new(Learn::KnowledgeKey).string().not_null())
+                    .col(ColumnDef::new(Learn::History).json_binary().not_null())
+                    .col(
+                        ColumnDef::new(Learn::UpdateTime)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(Learn::FreezeTime).timestamp_with_time_zone().null())
+                    .primary_key(
+                        Index::create()
+                            .col(Learn::Uid)
+                            .col(Learn::KnowledgeType)
+                            .col(Learn::KnowledgeKey),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Learn::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Learn {
+    Table,
+    Uid,
+    KnowledgeType,
+    KnowledgeKey,
+    History,
+    UpdateTime,
+    FreezeTime,
+}
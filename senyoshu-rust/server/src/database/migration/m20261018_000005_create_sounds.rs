@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Sounds::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Sounds::Katakana).string().not_null())
+                    .col(ColumnDef::new(Sounds::Tone).small_integer().not_null())
+                    .col(ColumnDef::new(Sounds::Role).string().not_null())
+                    .col(ColumnDef::new(Sounds::Sound).binary().not_null())
+                    .col(
+                        ColumnDef::new(Sounds::UpdateDate)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(Sounds::Katakana)
+                            .col(Sounds::Tone)
+                            .col(Sounds::Role),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Sounds::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Sounds {
+    Table,
+    Katakana,
+    Tone,
+    Role,
+    Sound,
+    UpdateDate,
+}
@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DataModel::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DataModel::Dmid)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(DataModel::Author).big_integer().not_null())
+                    .col(ColumnDef::new(DataModel::History).json_binary().not_null())
+                    .col(
+                        ColumnDef::new(DataModel::UpdateTime)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(DataModel::Extend).big_integer().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DataModel::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DataModel {
+    Table,
+    Dmid,
+    Author,
+    History,
+    UpdateTime,
+    Extend,
+}
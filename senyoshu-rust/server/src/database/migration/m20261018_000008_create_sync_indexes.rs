@@ -0,0 +1,67 @@
+use sea_orm_migration::prelude::*;
+
+const IDX_WORDS_UPDATE_DATE: &str = "idx_words_update_date";
+const IDX_LEARN_UID_UPDATE_TIME: &str = "idx_learn_uid_update_time";
+
+/// `sync_dic` 按 `update_date` 增量同步，`get_record` 按 `(uid, update_time)` 增量同步
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name(IDX_WORDS_UPDATE_DATE)
+                    .table(Words::Table)
+                    .col(Words::UpdateDate)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name(IDX_LEARN_UID_UPDATE_TIME)
+                    .table(Learn::Table)
+                    .col(Learn::Uid)
+                    .col(Learn::UpdateTime)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(IDX_LEARN_UID_UPDATE_TIME)
+                    .table(Learn::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(IDX_WORDS_UPDATE_DATE)
+                    .table(Words::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Words {
+    Table,
+    UpdateDate,
+}
+
+#[derive(DeriveIden)]
+enum Learn {
+    Table,
+    Uid,
+    UpdateTime,
+}
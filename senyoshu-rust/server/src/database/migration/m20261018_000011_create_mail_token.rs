@@ -0,0 +1,124 @@
+use sea_orm_migration::prelude::*;
+
+const IDX_MAIL_TOKEN_UID_PURPOSE: &str = "idx_mail_token_uid_purpose";
+const IDX_MAIL_TOKEN_TOKEN_HASH: &str = "idx_mail_token_token_hash";
+const IDX_ACCOUNT_E_MAIL: &str = "idx_account_e_mail";
+
+/// 邮箱验证和重置密码，同一个邮箱只能绑定一个账号
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MailToken::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MailToken::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(MailToken::Uid).big_integer().not_null())
+                    .col(ColumnDef::new(MailToken::Purpose).small_integer().not_null())
+                    .col(ColumnDef::new(MailToken::EMail).string().not_null())
+                    .col(ColumnDef::new(MailToken::TokenHash).string().not_null())
+                    .col(
+                        ColumnDef::new(MailToken::Attempts)
+                            .small_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(MailToken::Used)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(MailToken::CreateTime)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(MailToken::ExpireTime)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name(IDX_MAIL_TOKEN_UID_PURPOSE)
+                    .table(MailToken::Table)
+                    .col(MailToken::Uid)
+                    .col(MailToken::Purpose)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name(IDX_MAIL_TOKEN_TOKEN_HASH)
+                    .table(MailToken::Table)
+                    .col(MailToken::TokenHash)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        //此前没有接口可以设置 e_mail，已有的数据都是 null
+        manager
+            .create_index(
+                Index::create()
+                    .name(IDX_ACCOUNT_E_MAIL)
+                    .table(Account::Table)
+                    .col(Account::EMail)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(IDX_ACCOUNT_E_MAIL)
+                    .table(Account::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(MailToken::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum MailToken {
+    Table,
+    Id,
+    Uid,
+    Purpose,
+    EMail,
+    TokenHash,
+    Attempts,
+    Used,
+    CreateTime,
+    ExpireTime,
+}
+
+#[derive(DeriveIden)]
+enum Account {
+    Table,
+    EMail,
+}
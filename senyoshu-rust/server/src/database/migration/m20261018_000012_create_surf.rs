@@ -0,0 +1,139 @@
+use sea_orm_migration::prelude::*;
+
+const IDX_SURF_CREDENTIAL_SERVER_ID: &str = "idx_surf_credential_server_id";
+const IDX_SURF_CREDENTIAL_UID: &str = "idx_surf_credential_uid";
+
+/// 代理服务器和每个用户的密码，原先写死在代码中的服务器需要由管理员重新添加
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SurfServer::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SurfServer::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SurfServer::Name).string().not_null())
+                    .col(ColumnDef::new(SurfServer::Region).string().not_null().default(""))
+                    .col(ColumnDef::new(SurfServer::Server).string().not_null())
+                    .col(ColumnDef::new(SurfServer::ServerPort).integer().not_null())
+                    .col(ColumnDef::new(SurfServer::Method).string().not_null())
+                    .col(ColumnDef::new(SurfServer::Capacity).integer().not_null().default(0))
+                    .col(ColumnDef::new(SurfServer::Enabled).boolean().not_null().default(true))
+                    .col(
+                        ColumnDef::new(SurfServer::CreateTime)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SurfServer::UpdateTime)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(SurfCredential::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SurfCredential::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SurfCredential::ServerId).big_integer().not_null())
+                    .col(ColumnDef::new(SurfCredential::Uid).big_integer().not_null())
+                    .col(ColumnDef::new(SurfCredential::Password).string().not_null())
+                    .col(
+                        ColumnDef::new(SurfCredential::ExpireTime)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SurfCredential::Revoked)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(SurfCredential::CreateTime)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name(IDX_SURF_CREDENTIAL_SERVER_ID)
+                    .table(SurfCredential::Table)
+                    .col(SurfCredential::ServerId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name(IDX_SURF_CREDENTIAL_UID)
+                    .table(SurfCredential::Table)
+                    .col(SurfCredential::Uid)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SurfCredential::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(SurfServer::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SurfServer {
+    Table,
+    Id,
+    Name,
+    Region,
+    Server,
+    ServerPort,
+    Method,
+    Capacity,
+    Enabled,
+    CreateTime,
+    UpdateTime,
+}
+
+#[derive(DeriveIden)]
+enum SurfCredential {
+    Table,
+    Id,
+    ServerId,
+    Uid,
+    Password,
+    ExpireTime,
+    Revoked,
+    CreateTime,
+}
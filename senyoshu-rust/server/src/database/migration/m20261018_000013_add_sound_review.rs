@@ -0,0 +1,76 @@
+use sea_orm_migration::prelude::*;
+
+/// 上传的音频需要审核，已有的音频视为审核通过
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        //sqlite 的 alter table 一次只能添加一列
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Sounds::Table)
+                    .add_column(
+                        ColumnDef::new(Sounds::ContentType)
+                            .string()
+                            .not_null()
+                            .default("audio/mpeg"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Sounds::Table)
+                    .add_column(
+                        ColumnDef::new(Sounds::Author)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Sounds::Table)
+                    .add_column(ColumnDef::new(Sounds::Checked).boolean().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Sounds::Table)
+                    .value(Sounds::Checked, true)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [Sounds::ContentType, Sounds::Author, Sounds::Checked] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Sounds::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Sounds {
+    Table,
+    ContentType,
+    Author,
+    Checked,
+}
@@ -0,0 +1,78 @@
+use sea_orm_migration::prelude::*;
+
+const IDX_MODERATION_LOG_ACTOR: &str = "idx_moderation_log_actor";
+const IDX_MODERATION_LOG_WID: &str = "idx_moderation_log_wid";
+
+/// 审核操作的记录，通过、驳回、撤回修改请求以及批量修改和删除词条
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModerationLog::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModerationLog::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ModerationLog::Actor).big_integer().not_null())
+                    .col(ColumnDef::new(ModerationLog::Action).small_integer().not_null())
+                    .col(ColumnDef::new(ModerationLog::Pid).big_integer().null())
+                    .col(ColumnDef::new(ModerationLog::Wid).big_integer().null())
+                    .col(ColumnDef::new(ModerationLog::Comment).text().null())
+                    .col(
+                        ColumnDef::new(ModerationLog::CreateTime)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name(IDX_MODERATION_LOG_ACTOR)
+                    .table(ModerationLog::Table)
+                    .col(ModerationLog::Actor)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name(IDX_MODERATION_LOG_WID)
+                    .table(ModerationLog::Table)
+                    .col(ModerationLog::Wid)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ModerationLog::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ModerationLog {
+    Table,
+    Id,
+    Actor,
+    Action,
+    Pid,
+    Wid,
+    Comment,
+    CreateTime,
+}
@@ -0,0 +1,62 @@
+use sea_orm_migration::prelude::*;
+
+/// 修改请求的审核者、审核时间和审核意见，已有的请求都为空
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        //sqlite 的 alter table 一次只能添加一列
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WordHistory::Table)
+                    .add_column(ColumnDef::new(WordHistory::Reviewer).big_integer().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WordHistory::Table)
+                    .add_column(
+                        ColumnDef::new(WordHistory::ReviewDate)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WordHistory::Table)
+                    .add_column(ColumnDef::new(WordHistory::ReviewComment).text().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [WordHistory::Reviewer, WordHistory::ReviewDate, WordHistory::ReviewComment] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(WordHistory::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum WordHistory {
+    Table,
+    Reviewer,
+    ReviewDate,
+    ReviewComment,
+}
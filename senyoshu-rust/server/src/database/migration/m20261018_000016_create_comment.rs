@@ -0,0 +1,91 @@
+use sea_orm_migration::prelude::*;
+
+const IDX_COMMENT_WID: &str = "idx_comment_wid";
+const IDX_COMMENT_PID: &str = "idx_comment_pid";
+
+/// 词条和修改请求下的讨论
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Comment::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Comment::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Comment::Wid).big_integer().null())
+                    .col(ColumnDef::new(Comment::Pid).big_integer().null())
+                    .col(ColumnDef::new(Comment::Parent).big_integer().null())
+                    .col(ColumnDef::new(Comment::Author).big_integer().not_null())
+                    .col(ColumnDef::new(Comment::Content).text().not_null())
+                    .col(
+                        ColumnDef::new(Comment::Deleted)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(Comment::CreateTime)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Comment::EditTime)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name(IDX_COMMENT_WID)
+                    .table(Comment::Table)
+                    .col(Comment::Wid)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name(IDX_COMMENT_PID)
+                    .table(Comment::Table)
+                    .col(Comment::Pid)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Comment::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Comment {
+    Table,
+    Id,
+    Wid,
+    Pid,
+    Parent,
+    Author,
+    Content,
+    Deleted,
+    CreateTime,
+    EditTime,
+}
@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+
+const IDX_SURF_CREDENTIAL_SERVER_ID_UID: &str = "idx_surf_credential_server_id_uid";
+
+/// 每个用户在每台服务器上只有一条密码，撤销后重新生成时复用这一条
+///
+/// 建立唯一索引前只保留每组中最新的一条，较早的都是已撤销或同时请求时重复插入的
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let latest = Query::select()
+            .expr(Func::max(Expr::col(SurfCredential::Id)))
+            .from(SurfCredential::Table)
+            .group_by_columns([SurfCredential::ServerId, SurfCredential::Uid])
+            .to_owned();
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(SurfCredential::Table)
+                    .and_where(Expr::col(SurfCredential::Id).not_in_subquery(latest))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name(IDX_SURF_CREDENTIAL_SERVER_ID_UID)
+                    .table(SurfCredential::Table)
+                    .col(SurfCredential::ServerId)
+                    .col(SurfCredential::Uid)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(IDX_SURF_CREDENTIAL_SERVER_ID_UID)
+                    .table(SurfCredential::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SurfCredential {
+    Table,
+    Id,
+    ServerId,
+    Uid,
+}
@@ -0,0 +1,91 @@
+use clap::Subcommand;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::MigrationStatus;
+
+use crate::database::database::GlobalDatabase;
+
+mod m20261018_000001_create_account;
+mod m20261018_000002_create_words;
+mod m20261018_000003_create_word_history;
+mod m20261018_000004_create_learn;
+mod m20261018_000005_create_sounds;
+mod m20261018_000006_create_data_model;
+mod m20261018_000007_create_learn_model;
+mod m20261018_000008_create_sync_indexes;
+mod m20261018_000009_add_passwd_hash_version;
+mod m20261018_000010_add_admin;
+mod m20261018_000011_create_mail_token;
+mod m20261018_000012_create_surf;
+mod m20261018_000013_add_sound_review;
+mod m20261018_000014_create_moderation_log;
+mod m20261018_000015_add_review_fields;
+mod m20261018_000016_create_comment;
+mod m20261018_000017_add_surf_credential_unique;
+
+/// 已发布的迁移不要再修改，表结构的变更请追加新的迁移
+///
+/// 建表迁移使用 `if_not_exists`，因此可以直接用于此前由 `create_table_from_entity` 建立的数据库
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(m20261018_000001_create_account::Migration),
+            Box::new(m20261018_000002_create_words::Migration),
+            Box::new(m20261018_000003_create_word_history::Migration),
+            Box::new(m20261018_000004_create_learn::Migration),
+            Box::new(m20261018_000005_create_sounds::Migration),
+            Box::new(m20261018_000006_create_data_model::Migration),
+            Box::new(m20261018_000007_create_learn_model::Migration),
+            Box::new(m20261018_000008_create_sync_indexes::Migration),
+            Box::new(m20261018_000009_add_passwd_hash_version::Migration),
+            Box::new(m20261018_000010_add_admin::Migration),
+            Box::new(m20261018_000011_create_mail_token::Migration),
+            Box::new(m20261018_000012_create_surf::Migration),
+            Box::new(m20261018_000013_add_sound_review::Migration),
+            Box::new(m20261018_000014_create_moderation_log::Migration),
+            Box::new(m20261018_000015_add_review_fields::Migration),
+            Box::new(m20261018_000016_create_comment::Migration),
+            Box::new(m20261018_000017_add_surf_credential_unique::Migration),
+        ]
+    }
+}
+
+#[derive(Subcommand, Debug, Clone, Copy)]
+pub enum MigrateAction {
+    /// 执行尚未应用的迁移
+    Up {
+        /// 最多执行的迁移数量，默认全部执行
+        #[arg(short, long)]
+        num: Option<u32>,
+    },
+    /// 回滚已应用的迁移
+    Down {
+        /// 回滚的迁移数量
+        #[arg(short, long, default_value_t = 1)]
+        num: u32,
+    },
+    /// 列出所有迁移及其状态
+    Status,
+}
+
+impl MigrateAction {
+    pub async fn run(self, url: &str) -> Result<(), DbErr> {
+        let db = GlobalDatabase::connect(url).await?;
+        match self {
+            MigrateAction::Up { num } => Migrator::up(&db, num).await?,
+            MigrateAction::Down { num } => Migrator::down(&db, Some(num)).await?,
+            MigrateAction::Status => {}
+        }
+
+        for migration in Migrator::get_migration_with_status(&db).await? {
+            let status = match migration.status() {
+                MigrationStatus::Applied => "applied",
+                MigrationStatus::Pending => "pending",
+            };
+            println!("{status:>8}  {}", migration.name());
+        }
+        Ok(())
+    }
+}
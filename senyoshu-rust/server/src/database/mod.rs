@@ -1,5 +1,9 @@
 pub mod account;
+pub mod comment;
 pub mod database;
 pub mod dic;
 pub mod learn;
+pub mod mail_token;
+pub mod migration;
 pub mod model;
+pub mod surf;
@@ -1,2 +1,2 @@
-mod data_model;
-mod learn_model;
+pub mod data_model;
+pub mod learn_model;
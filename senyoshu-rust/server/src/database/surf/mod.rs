@@ -0,0 +1,2 @@
+pub mod surf_credential;
+pub mod surf_server;
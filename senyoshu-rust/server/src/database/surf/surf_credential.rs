@@ -0,0 +1,59 @@
+use std::fmt::{Debug, Formatter};
+
+use chrono::FixedOffset;
+use sea_orm::entity::prelude::*;
+use sea_orm::QueryFilter;
+
+use senyoshu_common::util::redact::Redacted;
+
+/// 某个用户在某台代理服务器上的密码，代理服务器需要明文，因此不做哈希
+#[derive(Clone, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "surf_credential")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub server_id: i64,
+    pub uid: i64,
+    pub password: String,
+    /// 与会员的到期时间保持一致
+    pub expire_time: chrono::DateTime<FixedOffset>,
+    pub revoked: bool,
+    #[sea_orm(default_value = "now()")]
+    pub create_time: chrono::DateTime<FixedOffset>,
+}
+
+impl Debug for Model {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Model")
+            .field("id", &self.id)
+            .field("server_id", &self.server_id)
+            .field("uid", &self.uid)
+            .field("password", &Redacted)
+            .field("expire_time", &self.expire_time)
+            .field("revoked", &self.revoked)
+            .field("create_time", &self.create_time)
+            .finish()
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// 会员的到期时间变化后同步到该用户所有有效的密码，撤销会员时作废这些密码
+pub async fn sync_vip<C: ConnectionTrait>(
+    uid: i64,
+    vip: Option<chrono::DateTime<FixedOffset>>,
+    db: &C,
+) -> Result<(), DbErr> {
+    let update = Entity::update_many()
+        .filter(Column::Uid.eq(uid))
+        .filter(Column::Revoked.eq(false));
+    let update = match vip {
+        Some(vip) => update.col_expr(Column::ExpireTime, Expr::value(vip)),
+        None => update.col_expr(Column::Revoked, Expr::value(true)),
+    };
+    update.exec(db).await?;
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use chrono::FixedOffset;
+use sea_orm::entity::prelude::*;
+
+/// 代理服务器，由管理员维护
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "surf_server")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub name: String,
+    pub region: String,
+    pub server: String,
+    pub server_port: i32,
+    pub method: String,
+    /// 0 表示不限制
+    pub capacity: i32,
+    pub enabled: bool,
+    #[sea_orm(default_value = "now()")]
+    pub create_time: chrono::DateTime<FixedOffset>,
+    #[sea_orm(default_value = "now()")]
+    pub update_time: chrono::DateTime<FixedOffset>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
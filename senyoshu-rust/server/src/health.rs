@@ -0,0 +1,36 @@
+//! 存活和就绪检查，只看状态码，不经过 `ApiResponse`
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use http::StatusCode;
+use tracing::warn;
+
+use crate::database::database::GLOBAL_DATABASE;
+
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// 开始关闭后不再接收新的流量，已有的请求仍然会完成
+pub fn begin_shutdown() {
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+}
+
+/// 进程还能处理请求
+pub async fn healthz() -> &'static str {
+    "ok"
+}
+
+/// 数据库可以访问，且没有开始关闭
+pub async fn readyz() -> (StatusCode, &'static str) {
+    if SHUTTING_DOWN.load(Ordering::SeqCst) {
+        return (StatusCode::SERVICE_UNAVAILABLE, "shutting down");
+    }
+    let Some(db) = GLOBAL_DATABASE.get() else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "database is not initialized");
+    };
+    match db.ping().await {
+        Ok(()) => (StatusCode::OK, "ready"),
+        Err(err) => {
+            warn!("database is not ready: {err}");
+            (StatusCode::SERVICE_UNAVAILABLE, "database unavailable")
+        }
+    }
+}
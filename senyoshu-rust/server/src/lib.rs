@@ -0,0 +1,11 @@
+pub mod api;
+pub mod app;
+pub mod config;
+pub mod database;
+pub mod health;
+pub mod mail;
+pub mod metrics;
+pub mod push;
+pub mod rate_limit;
+pub mod snapshot;
+pub mod tts;
@@ -0,0 +1,203 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use axum::async_trait;
+use chrono::Utc;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use tracing::{debug, error, info};
+
+use senyoshu_common::types::error::Error;
+
+use crate::config::{MailConfig, MailTransport, SmtpTls};
+
+/// 纯文本邮件
+#[derive(Clone, Debug)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug)]
+pub struct MailError(pub String);
+
+impl Display for MailError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for MailError {}
+
+/// 发送邮件的方式，由配置中的 `mail.transport` 决定
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, mail: Mail) -> Result<(), MailError>;
+}
+
+/// 处理函数通过 `Extension<MailService>` 获取
+#[derive(Clone)]
+pub struct MailService {
+    mailer: Arc<dyn Mailer>,
+    public_url: String,
+}
+
+impl MailService {
+    pub fn new(config: &MailConfig) -> Result<Self, MailError> {
+        Ok(Self {
+            mailer: build_mailer(config)?,
+            public_url: config.public_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// 客户端的地址，没有配置时为空
+    pub fn public_url(&self) -> &str {
+        self.public_url.as_str()
+    }
+
+    /// 发送失败时只记录日志，返回 [`Error::MailUnavailable`]
+    pub async fn send(&self, mail: Mail) -> Result<(), Error> {
+        let to = mail.to.to_owned();
+        self.mailer.send(mail).await.map_err(|err| {
+            error!("failed to send mail to {to}: {err}");
+            Error::MailUnavailable
+        })
+    }
+}
+
+pub fn build_mailer(config: &MailConfig) -> Result<Arc<dyn Mailer>, MailError> {
+    let from = config
+        .from
+        .parse::<Mailbox>()
+        .map_err(|err| MailError(format!("from `{}` is illegal: {err}", config.from)))?;
+    Ok(match config.transport {
+        MailTransport::Log => Arc::new(LogMailer { from }),
+        MailTransport::File => Arc::new(FileMailer {
+            from,
+            dir: config.dir.to_owned(),
+        }),
+        MailTransport::Smtp => Arc::new(SmtpMailer::new(config, from)?),
+    })
+}
+
+/// 只把邮件写入日志，正文中有验证码和重置密码的 token，只在 debug 级别输出
+pub struct LogMailer {
+    from: Mailbox,
+}
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, mail: Mail) -> Result<(), MailError> {
+        info!(from = %self.from, to = mail.to, subject = mail.subject, "mail is logged");
+        debug!(to = mail.to, "mail:\n{}", mail.body);
+        Ok(())
+    }
+}
+
+/// 每封邮件写入目录中的一个文件，文件名以 [`file_name_prefix`] 开头
+pub struct FileMailer {
+    from: Mailbox,
+    dir: PathBuf,
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, mail: Mail) -> Result<(), MailError> {
+        //同一毫秒内的多封邮件按序号区分
+        static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+        let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        let file_name = format!(
+            "{}-{}-{sequence}.txt",
+            file_name_prefix(mail.to.as_str()),
+            Utc::now().timestamp_millis()
+        );
+        let content = format!(
+            "From: {}\nTo: {}\nSubject: {}\n\n{}\n",
+            self.from, mail.to, mail.subject, mail.body
+        );
+
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|err| MailError(err.to_string()))?;
+        tokio::fs::write(self.dir.join(file_name), content)
+            .await
+            .map_err(|err| MailError(err.to_string()))
+    }
+}
+
+/// 收件人由用户填写，可能包含 `/`，只保留文件名中安全的字符，其余替换为 `_`
+pub fn file_name_prefix(to: &str) -> String {
+    to.chars()
+        .map(|c| match c {
+            'A'..='Z' | 'a'..='z' | '0'..='9' | '@' | '.' | '_' | '-' => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+pub struct SmtpMailer {
+    from: Mailbox,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpMailer {
+    fn new(config: &MailConfig, from: Mailbox) -> Result<Self, MailError> {
+        let smtp = &config.smtp;
+        if smtp.host.is_empty() {
+            return Err(MailError(String::from("smtp.host is empty")));
+        }
+
+        let (builder, default_port) = match smtp.tls {
+            SmtpTls::Starttls => (
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(smtp.host.as_str()),
+                587,
+            ),
+            SmtpTls::Tls => (AsyncSmtpTransport::<Tokio1Executor>::relay(smtp.host.as_str()), 465),
+            SmtpTls::None => (
+                Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(smtp.host.as_str())),
+                25,
+            ),
+        };
+        let mut builder = builder
+            .map_err(|err| MailError(err.to_string()))?
+            .port(smtp.port.unwrap_or(default_port));
+        if !smtp.username.is_empty() {
+            builder = builder.credentials(Credentials::new(
+                smtp.username.to_owned(),
+                smtp.password.to_owned(),
+            ));
+        }
+
+        Ok(Self {
+            from,
+            transport: builder.build(),
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, mail: Mail) -> Result<(), MailError> {
+        let to = mail
+            .to
+            .parse::<Mailbox>()
+            .map_err(|err| MailError(err.to_string()))?;
+        let message = Message::builder()
+            .from(self.from.to_owned())
+            .to(to)
+            .subject(mail.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(mail.body)
+            .map_err(|err| MailError(err.to_string()))?;
+        self.transport
+            .send(message)
+            .await
+            .map(|_| ())
+            .map_err(|err| MailError(err.to_string()))
+    }
+}
@@ -1,83 +1,207 @@
-use std::io::Read;
-
-use axum::Router;
-use tokio::io::AsyncReadExt;
-use tower_http::cors::CorsLayer;
-use tower_http::services::{ServeDir, ServeFile};
-use tower_http::trace::TraceLayer;
-use tracing::Level;
-use tracing_subscriber::fmt::writer::MakeWriterExt;
-use tracing_subscriber::util::SubscriberInitExt;
-
-use senyoshu_common::types::api::account::{
-    GET_OTHER_USER_INFO_API, LOGIN_API, REGISTER_API, UPDATE_PASSWD_API, UPDATE_USER_STATE_API,
-};
-use senyoshu_common::types::api::api::GET_SURF_SERVERS_API;
-use senyoshu_common::types::api::dic::{CREATE_WORD_API, DELETE_WORD_API, GET_CHANGE_REQUEST_API, GET_WORD_BY_PID_API, GET_WORD_HISTORY_API, POST_WORD_API, SET_ADOPTED_API, SYNC_DIC_API, UPDATE_MANY_API};
-use senyoshu_common::types::api::learn::{GET_RECORD_API, POST_LEARN_RECORD_API};
-
-use crate::api::account::get_other_user_info::get_other_user_info_api;
-use crate::api::account::login::login_api;
-use crate::api::account::register::register_api;
-use crate::api::account::update_passwd::update_passwd_api;
-use crate::api::account::update_user_state::update_user_state_api;
-use crate::api::AxumAPi;
-use crate::api::dic::create_word::create_word_api;
-use crate::api::dic::delete_word::delete_word_api;
-use crate::api::dic::get_change_request::get_change_request_api;
-use crate::api::dic::get_word_by_pid::get_word_by_pid_api;
-use crate::api::dic::get_word_history::get_word_history_api;
-use crate::api::dic::post_word::post_word_api;
-use crate::api::dic::set_adopted::set_adopted_api;
-use crate::api::dic::sync_dic::sync_dic_api;
-use crate::api::dic::update_many::update_many_api;
-use crate::api::get_surf_servers::get_surf_servers_api;
-use crate::api::learn::get_record::get_record_api;
-use crate::api::learn::post_record::post_learn_record_api;
-use crate::database::database::{GlobalDatabase, TEST_DATABASE};
-
-mod api;
-mod database;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::future::{IntoFuture, pending};
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+use tokio::signal;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+use tracing_subscriber::EnvFilter;
+
+use senyoshu_server::app::app;
+use senyoshu_server::config::{Config, LogConfig, LogFormat};
+use senyoshu_server::database::account::set_admin;
+use senyoshu_server::database::database::GlobalDatabase;
+use senyoshu_server::database::migration::MigrateAction;
+use senyoshu_server::health::begin_shutdown;
+use senyoshu_server::push::close_all;
+use senyoshu_server::snapshot::SnapshotJob;
+use senyoshu_server::tts::job::TtsJob;
+
+#[derive(Parser, Debug)]
+#[command(version, about = "senyoshu server")]
+struct Cli {
+    /// 配置文件路径，默认读取当前目录下的 senyoshu.toml（如果存在）
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+    /// 检查配置（含环境变量）并打印最终生效的配置，不启动服务
+    #[arg(long)]
+    check_config: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// 数据库迁移
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+    /// 设置管理员，需要先执行迁移
+    SetAdmin {
+        username: String,
+        /// 撤销管理员
+        #[arg(long)]
+        revoke: bool,
+    },
+}
 
 #[tokio::main]
-async fn main() {
-    tracing_subscriber::fmt()
-        .with_max_level(Level::DEBUG)
-        .init();
-
-    GlobalDatabase::init_database(TEST_DATABASE, false).await;
-
-    let cors = CorsLayer::permissive();
-    let app = Router::new();
-    let app = app
-        .nest_service("", ServeDir::new("../target/dist").fallback(ServeFile::new("../target/dist/index.html")))
-        //account
-        .set_api_handle(UPDATE_USER_STATE_API, update_user_state_api)
-        .set_api_handle(GET_OTHER_USER_INFO_API, get_other_user_info_api)
-        .set_api_handle(LOGIN_API, login_api)
-        .set_api_handle(REGISTER_API, register_api)
-        .set_api_handle(UPDATE_PASSWD_API, update_passwd_api)
-        //dic
-        .set_api_handle(CREATE_WORD_API, create_word_api)
-        .set_api_handle(DELETE_WORD_API, delete_word_api)
-        .set_api_handle(SYNC_DIC_API, sync_dic_api)
-        .set_api_handle(GET_CHANGE_REQUEST_API, get_change_request_api)
-        .set_api_handle(GET_WORD_BY_PID_API, get_word_by_pid_api)
-        .set_api_handle(GET_WORD_HISTORY_API, get_word_history_api)
-        .set_api_handle(POST_WORD_API, post_word_api)
-        .set_api_handle(SET_ADOPTED_API, set_adopted_api)
-        .set_api_handle(UPDATE_MANY_API, update_many_api)
-        //learn
-        .set_api_handle(POST_LEARN_RECORD_API, post_learn_record_api)
-        .set_api_handle(GET_RECORD_API, get_record_api)
-        //surf
-        .set_api_handle(GET_SURF_SERVERS_API, get_surf_servers_api)
-        //other settings
-        .layer(cors)
-        .layer(TraceLayer::new_for_http())
-        .into_make_service();
-
-
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:8000").await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let config = match Config::load(cli.config.as_deref()) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(command) = cli.command {
+        //子命令只需要数据库配置
+        if config.database.url.is_empty() {
+            eprintln!("database.url is empty");
+            return ExitCode::FAILURE;
+        }
+        init_tracing(&config.log);
+        return run_command(command, config.database.url.as_str()).await;
+    }
+
+    if let Err(err) = config.validate() {
+        eprintln!("{err}");
+        return ExitCode::FAILURE;
+    }
+
+    if cli.check_config {
+        println!("{}", config.to_redacted_toml());
+        for warning in config.warnings() {
+            println!("warning: {warning}");
+        }
+        println!("config is ok");
+        return ExitCode::SUCCESS;
+    }
+
+    init_tracing(&config.log);
+    for warning in config.warnings() {
+        warn!("{warning}");
+    }
+
+    if let Err(err) =
+        GlobalDatabase::init_database(config.database.url.as_str(), config.database.auto_migrate).await
+    {
+        error!("init database failed: {err}");
+        return ExitCode::FAILURE;
+    }
+
+    //配置已经在 `Config::validate` 中检查过
+    if let Some(job) = TtsJob::from_config(&config.tts).unwrap() {
+        job.spawn(GlobalDatabase::get().unwrap());
+    }
+    if config.snapshot.enabled {
+        SnapshotJob::new(&config.snapshot).spawn(GlobalDatabase::get().unwrap());
+    }
+
+    let app = app(&config).into_make_service_with_connect_info::<SocketAddr>();
+
+    let listener = tokio::net::TcpListener::bind(config.server.bind.as_str()).await.unwrap();
+    let (signaled, on_signal) = tokio::sync::oneshot::channel::<()>();
+    let serve = axum::serve(listener, app).with_graceful_shutdown(async move {
+        shutdown_signal().await;
+        //不再接收新的连接，等待已有的请求完成
+        begin_shutdown();
+        close_all();
+        let _ = signaled.send(());
+    });
+    let grace = Duration::from_secs(config.server.shutdown_timeout_secs);
+    let force = async {
+        match on_signal.await {
+            Ok(()) => sleep(grace).await,
+            Err(_) => pending().await,
+        }
+    };
+    tokio::select! {
+        result = serve.into_future() => match result {
+            Ok(()) => {
+                info!("server stopped");
+                ExitCode::SUCCESS
+            }
+            Err(err) => {
+                error!("server error: {err}");
+                ExitCode::FAILURE
+            }
+        },
+        _ = force => {
+            warn!("requests did not finish in {grace:?}, exit anyway");
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+/// 等待 SIGTERM 或 Ctrl+C
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = signal::ctrl_c().await;
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(err) => {
+                error!("failed to listen for SIGTERM: {err}");
+                pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = pending::<()>();
+    tokio::select! {
+        _ = ctrl_c => info!("received Ctrl+C, shutting down"),
+        _ = terminate => info!("received SIGTERM, shutting down"),
+    }
+}
+
+async fn run_command(command: Command, url: &str) -> ExitCode {
+    match command {
+        Command::Migrate { action } => match action.run(url).await {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("migrate failed: {err}");
+                ExitCode::FAILURE
+            }
+        },
+        Command::SetAdmin { username, revoke } => {
+            let result = match GlobalDatabase::connect(url).await {
+                Ok(db) => set_admin(username.as_str(), !revoke, &db).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(true) => ExitCode::SUCCESS,
+                Ok(false) => {
+                    eprintln!("user {username} not found");
+                    ExitCode::FAILURE
+                }
+                Err(err) => {
+                    eprintln!("set admin failed: {err}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+    }
+}
+
+fn init_tracing(log: &LogConfig) {
+    //已经在 Config::validate 中检查过
+    let filter = EnvFilter::new(log.level.as_str());
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    match log.format {
+        LogFormat::Pretty => builder.init(),
+        //每条日志带上所在的 span，包括请求的 `request_id`
+        LogFormat::Json => builder.json().with_current_span(true).with_span_list(true).init(),
+    }
 }
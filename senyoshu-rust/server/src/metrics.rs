@@ -0,0 +1,201 @@
+//! Prometheus 指标，通过 `/metrics` 以文本格式输出
+//!
+//! 请求相关的指标在中间件中记录，数据库相关的指标在抓取时计算
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use axum::body::HttpBody;
+use axum::extract::{MatchedPath, Request};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http::{HeaderMap, StatusCode};
+use http::header::{AUTHORIZATION, CONTENT_TYPE};
+use once_cell::sync::Lazy;
+use prometheus::{
+    Encoder, exponential_buckets, HistogramOpts, HistogramVec, IntCounterVec, IntGauge,
+    IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QuerySelect};
+use tracing::error;
+
+use senyoshu_common::types::api::session::SessionVec;
+use senyoshu_common::types::state::State;
+
+use crate::api::account::session::{is_alive, now};
+use crate::database::account;
+use crate::database::database::GLOBAL_DATABASE;
+use crate::database::dic::word_history;
+
+/// 需要扫描表的指标最多这么久刷新一次，避免频繁抓取给数据库带来压力
+const DATABASE_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::new);
+
+pub struct Metrics {
+    registry: Registry,
+    requests: IntCounterVec,
+    latency: HistogramVec,
+    response_bytes: HistogramVec,
+    db_connections: IntGaugeVec,
+    pending_change_requests: IntGauge,
+    active_sessions: IntGauge,
+    pub(crate) push_connections: IntGauge,
+    refreshed: Mutex<Option<Instant>>,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some(String::from("senyoshu")), None).unwrap();
+        let requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests by API name and status"),
+            &["api", "status"],
+        )
+            .unwrap();
+        let latency = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTP request latency by API name"),
+            &["api"],
+        )
+            .unwrap();
+        //从 256B 到 64MB，同步和导出的响应可能很大
+        let response_bytes = HistogramVec::new(
+            HistogramOpts::new("http_response_bytes", "HTTP response body size by API name")
+                .buckets(exponential_buckets(256.0, 4.0, 10).unwrap()),
+            &["api"],
+        )
+            .unwrap();
+        let db_connections = IntGaugeVec::new(
+            Opts::new("db_pool_connections", "Database pool connections by state"),
+            &["state"],
+        )
+            .unwrap();
+        let pending_change_requests =
+            IntGauge::new("pending_change_requests", "Change requests waiting for review").unwrap();
+        let active_sessions =
+            IntGauge::new("active_sessions", "Sessions that are neither revoked nor expired").unwrap();
+        let push_connections =
+            IntGauge::new("push_connections", "Open push WebSocket connections").unwrap();
+
+        registry.register(Box::new(requests.to_owned())).unwrap();
+        registry.register(Box::new(latency.to_owned())).unwrap();
+        registry.register(Box::new(response_bytes.to_owned())).unwrap();
+        registry.register(Box::new(db_connections.to_owned())).unwrap();
+        registry.register(Box::new(pending_change_requests.to_owned())).unwrap();
+        registry.register(Box::new(active_sessions.to_owned())).unwrap();
+        registry.register(Box::new(push_connections.to_owned())).unwrap();
+
+        Self {
+            registry,
+            requests,
+            latency,
+            response_bytes,
+            db_connections,
+            pending_change_requests,
+            active_sessions,
+            push_connections,
+            refreshed: Mutex::new(None),
+        }
+    }
+
+    fn observe(&self, api: &str, status: StatusCode, elapsed: Duration, bytes: Option<u64>) {
+        self.requests.with_label_values(&[api, status.as_str()]).inc();
+        self.latency.with_label_values(&[api]).observe(elapsed.as_secs_f64());
+        if let Some(bytes) = bytes {
+            self.response_bytes.with_label_values(&[api]).observe(bytes as f64);
+        }
+    }
+
+    async fn refresh(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
+        if let Some((size, idle)) = pool_stats(db) {
+            self.db_connections.with_label_values(&["active"]).set((size as usize).saturating_sub(idle) as i64);
+            self.db_connections.with_label_values(&["idle"]).set(idle as i64);
+        }
+
+        {
+            let mut refreshed = self.refreshed.lock().unwrap();
+            if refreshed.is_some_and(|it| it.elapsed() < DATABASE_REFRESH_INTERVAL) {
+                return Ok(());
+            }
+            *refreshed = Some(Instant::now());
+        }
+        let pending = word_history::Entity::find()
+            .filter(word_history::Column::State.eq(State::Pending))
+            .count(db)
+            .await?;
+        self.pending_change_requests.set(pending as i64);
+
+        let now = now();
+        let sessions = account::Entity::find()
+            .select_only()
+            .column(account::Column::Sessions)
+            .filter(account::Column::Sessions.is_not_null())
+            .into_tuple::<Option<SessionVec>>()
+            .all(db)
+            .await?
+            .into_iter()
+            .flatten()
+            .map(|sessions| sessions.0.iter().filter(|session| is_alive(session, now)).count())
+            .sum::<usize>();
+        self.active_sessions.set(sessions as i64);
+        Ok(())
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, prometheus::Error> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+/// `(size, idle)`
+#[allow(unused_variables)]
+fn pool_stats(db: &DatabaseConnection) -> Option<(u32, usize)> {
+    match db {
+        #[cfg(feature = "postgres")]
+        DatabaseConnection::SqlxPostgresPoolConnection(_) => {
+            let pool = db.get_postgres_connection_pool();
+            Some((pool.size(), pool.num_idle()))
+        }
+        #[cfg(feature = "sqlite")]
+        DatabaseConnection::SqlxSqlitePoolConnection(_) => {
+            let pool = db.get_sqlite_connection_pool();
+            Some((pool.size(), pool.num_idle()))
+        }
+        _ => None,
+    }
+}
+
+/// 按 API 的名称记录请求，`/api/` 之外的路由使用路由的路径，没有匹配的路由记为 `unmatched`
+pub async fn track_metrics(request: Request, next: Next) -> Response {
+    let api = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str())
+        .map(|path| path.strip_prefix("/api/").unwrap_or(path).to_string())
+        .unwrap_or_else(|| String::from("unmatched"));
+    let start = Instant::now();
+    let response = next.run(request).await;
+    METRICS.observe(api.as_str(), response.status(), start.elapsed(), response.body().size_hint().exact());
+    response
+}
+
+/// 配置了 token 时需要 `Authorization: Bearer <token>`
+pub async fn metrics_api(token: String, headers: HeaderMap) -> Response {
+    let credential = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if !token.is_empty() && credential != Some(token.as_str()) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    let db = GLOBAL_DATABASE.get().unwrap();
+    if let Err(err) = METRICS.refresh(db).await {
+        error!("failed to refresh metrics: {err}");
+    }
+    match METRICS.encode() {
+        Ok(buffer) => ([(CONTENT_TYPE, TextEncoder::new().format_type().to_string())], buffer).into_response(),
+        Err(err) => {
+            error!("failed to encode metrics: {err}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
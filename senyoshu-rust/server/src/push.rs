@@ -0,0 +1,85 @@
+//! 推送给已连接客户端的通知，连接的处理见 [`crate::api::push`]
+//!
+//! 只在当前进程内广播，部署多个实例时连接到其他实例的客户端收不到通知，仍然依靠定期同步
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use tokio::sync::{broadcast, watch};
+
+use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::api::push::PushEvent;
+use senyoshu_common::types::learn::knowledge::Knowledge;
+use senyoshu_common::types::learn::learn_knowledge_history::LearnKnowledgeHistory;
+use senyoshu_common::types::word::wid::WordIdentity;
+use senyoshu_common::types::word::word_entry::WordDefine;
+
+/// 连接处理不过来时最多积压的通知数量，超过后客户端会收到 [`PushEvent::Resync`]
+const PUSH_CAPACITY: usize = 1024;
+
+static PUSH_HUB: Lazy<broadcast::Sender<Arc<Push>>> = Lazy::new(|| broadcast::channel(PUSH_CAPACITY).0);
+
+/// 关闭服务时通知所有连接断开，客户端会在稍后重连到其他实例
+static SHUTDOWN: Lazy<watch::Sender<bool>> = Lazy::new(|| watch::channel(false).0);
+
+#[derive(Debug)]
+pub struct Push {
+    pub target: PushTarget,
+    pub event: PushEvent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PushTarget {
+    All,
+    /// 用户的其他 session，不发给产生这个通知的 session
+    User { uid: i64, except: String },
+}
+
+impl Push {
+    pub fn is_for(&self, token: &Token) -> bool {
+        match &self.target {
+            PushTarget::All => true,
+            PushTarget::User { uid, except } => *uid == token.uid && *except != token.token,
+        }
+    }
+}
+
+pub fn subscribe() -> broadcast::Receiver<Arc<Push>> {
+    PUSH_HUB.subscribe()
+}
+
+pub fn shutdown_receiver() -> watch::Receiver<bool> {
+    SHUTDOWN.subscribe()
+}
+
+/// 断开所有的推送连接，WebSocket 连接不会在 graceful shutdown 中自己结束
+pub fn close_all() {
+    SHUTDOWN.send_replace(true);
+}
+
+/// 没有连接时直接丢弃
+fn publish(push: Push) {
+    let _ = PUSH_HUB.send(Arc::new(push));
+}
+
+pub(crate) fn publish_learn(origin: &Token, records: Vec<(Knowledge, LearnKnowledgeHistory)>) {
+    if records.is_empty() {
+        return;
+    }
+    publish(Push {
+        target: PushTarget::User {
+            uid: origin.uid,
+            except: origin.token.to_owned(),
+        },
+        event: PushEvent::Learn(records),
+    });
+}
+
+pub(crate) fn publish_dic(words: Vec<(WordIdentity, Option<WordDefine>)>) {
+    if words.is_empty() {
+        return;
+    }
+    publish(Push {
+        target: PushTarget::All,
+        event: PushEvent::Dic(words),
+    });
+}
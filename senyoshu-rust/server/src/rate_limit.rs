@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::body::{Body, to_bytes};
+use axum::extract::{ConnectInfo, Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http::header::RETRY_AFTER;
+use http::{HeaderMap, HeaderValue, StatusCode};
+use tracing::warn;
+
+use senyoshu_common::types::error::Error;
+
+use crate::api::ApiResponse;
+use crate::config::{Quota, RateLimitConfig};
+
+/// 读取请求体中的用户名时允许的最大长度
+const MAX_BODY_SIZE: usize = 64 * 1024;
+/// 清理过期记录的间隔
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 进程内的限流器，多个实例之间不共享计数
+///
+/// 通过 [`limit_login`]、[`limit_register`] 和 [`limit_mail`] 作为路由的中间件使用
+#[derive(Clone)]
+pub struct RateLimiter {
+    config: Arc<RateLimitConfig>,
+    state: Arc<Mutex<LimiterState>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Bucket {
+    LoginPerIp,
+    LoginPerUsername,
+    RegisterPerIp,
+    MailPerIp,
+}
+
+struct Window {
+    start: Instant,
+    count: u32,
+}
+
+/// 某个用户名连续登录失败的记录
+struct Failure {
+    count: u32,
+    last_failure: Instant,
+    locked_until: Option<Instant>,
+}
+
+struct LimiterState {
+    windows: HashMap<(Bucket, String), Window>,
+    failures: HashMap<String, Failure>,
+    last_cleanup: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            state: Arc::new(Mutex::new(LimiterState {
+                windows: HashMap::new(),
+                failures: HashMap::new(),
+                last_cleanup: Instant::now(),
+            })),
+        }
+    }
+
+    fn quota(&self, bucket: Bucket) -> Quota {
+        match bucket {
+            Bucket::LoginPerIp => self.config.login_per_ip,
+            Bucket::LoginPerUsername => self.config.login_per_username,
+            Bucket::RegisterPerIp => self.config.register_per_ip,
+            Bucket::MailPerIp => self.config.mail_per_ip,
+        }
+    }
+
+    /// 计数并检查是否超出限制，超出时返回需要等待的时间
+    fn acquire(&self, checks: &[(Bucket, &str)]) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        state.cleanup(now, self.config.lockout_max_secs);
+
+        for (_, username) in checks.iter().filter(|(bucket, _)| *bucket == Bucket::LoginPerUsername) {
+            if let Some(locked_until) = state.failures.get(*username).and_then(|it| it.locked_until) {
+                if locked_until > now {
+                    return Err(locked_until - now);
+                }
+            }
+        }
+
+        //全部检查通过之后才计数，避免一个限制拒绝时消耗另一个的次数
+        for (bucket, key) in checks {
+            let quota = self.quota(*bucket);
+            let period = Duration::from_secs(quota.per_secs);
+            if let Some(window) = state.windows.get(&(*bucket, key.to_string())) {
+                let elapsed = now - window.start;
+                if elapsed < period && window.count >= quota.requests {
+                    return Err(period - elapsed);
+                }
+            }
+        }
+        for (bucket, key) in checks {
+            let period = Duration::from_secs(self.quota(*bucket).per_secs);
+            let window = state
+                .windows
+                .entry((*bucket, key.to_string()))
+                .or_insert(Window { start: now, count: 0 });
+            if now - window.start >= period {
+                window.start = now;
+                window.count = 0;
+            }
+            window.count += 1;
+        }
+        Ok(())
+    }
+
+    /// 登录失败时延长锁定时间，成功时清除记录
+    fn record_login(&self, username: &str, success: bool) {
+        let mut state = self.state.lock().unwrap();
+        if success {
+            state.failures.remove(username);
+            return;
+        }
+
+        let now = Instant::now();
+        let failure = state.failures.entry(username.to_string()).or_insert(Failure {
+            count: 0,
+            last_failure: now,
+            locked_until: None,
+        });
+        failure.count += 1;
+        failure.last_failure = now;
+        if failure.count >= self.config.lockout_threshold {
+            //每多失败一次锁定时间翻倍
+            let exponent = (failure.count - self.config.lockout_threshold).min(31);
+            let secs = self
+                .config
+                .lockout_base_secs
+                .saturating_mul(1 << exponent)
+                .min(self.config.lockout_max_secs);
+            failure.locked_until = Some(now + Duration::from_secs(secs));
+            warn!("username {username} is locked for {secs}s after {} failed logins", failure.count);
+        }
+    }
+
+    fn client_ip(&self, request: &Request) -> String {
+        if self.config.trust_forwarded_for {
+            if let Some(ip) = forwarded_for(request.headers()) {
+                return ip.to_string();
+            }
+        }
+        request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip().to_string())
+            .unwrap_or_default()
+    }
+}
+
+impl LimiterState {
+    fn cleanup(&mut self, now: Instant, lockout_max_secs: u64) {
+        if now - self.last_cleanup < CLEANUP_INTERVAL {
+            return;
+        }
+        self.last_cleanup = now;
+        //窗口的长度各不相同，这里只清理一天以前的，其余的在下次计数时重置
+        self.windows
+            .retain(|_, window| now - window.start < Duration::from_secs(24 * 60 * 60));
+        self.failures.retain(|_, failure| {
+            failure.locked_until.is_some_and(|it| it > now)
+                || now - failure.last_failure < Duration::from_secs(lockout_max_secs)
+        });
+    }
+}
+
+/// 反向代理追加的最后一个地址
+fn forwarded_for(headers: &HeaderMap) -> Option<IpAddr> {
+    let value = headers.get("x-forwarded-for")?.to_str().ok()?;
+    value.rsplit(',').next()?.trim().parse().ok()
+}
+
+fn rate_limited(wait: Duration) -> Response {
+    //不足一秒的按一秒计算
+    let retry_after_secs = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+    let mut response = ApiResponse::<()>(Err(Error::RateLimited { retry_after_secs })).into_response();
+    if let Ok(value) = HeaderValue::from_str(retry_after_secs.to_string().as_str()) {
+        response.headers_mut().insert(RETRY_AFTER, value);
+    }
+    response
+}
+
+/// 请求体是以用户名开头的 json 数组，例如登录的 `(username, passwd_hash, device)`
+async fn split_username(request: Request) -> Result<(Request, String), Response> {
+    let (parts, body) = request.into_parts();
+    let bytes = to_bytes(body, MAX_BODY_SIZE)
+        .await
+        .map_err(|_| StatusCode::PAYLOAD_TOO_LARGE.into_response())?;
+    let username = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .ok()
+        .and_then(|value| value.get(0)?.as_str().map(str::to_string))
+        .unwrap_or_default();
+    Ok((Request::from_parts(parts, Body::from(bytes)), username))
+}
+
+pub async fn limit_login(State(limiter): State<RateLimiter>, request: Request, next: Next) -> Response {
+    if !limiter.config.enabled {
+        return next.run(request).await;
+    }
+
+    let ip = limiter.client_ip(&request);
+    let (request, username) = match split_username(request).await {
+        Ok(it) => it,
+        Err(response) => return response,
+    };
+    let checks = [
+        (Bucket::LoginPerIp, ip.as_str()),
+        (Bucket::LoginPerUsername, username.as_str()),
+    ];
+    if let Err(wait) = limiter.acquire(&checks) {
+        return rate_limited(wait);
+    }
+
+    let response = next.run(request).await;
+    match response.status() {
+        StatusCode::OK => limiter.record_login(username.as_str(), true),
+        StatusCode::UNAUTHORIZED => limiter.record_login(username.as_str(), false),
+        _ => {}
+    }
+    response
+}
+
+pub async fn limit_register(State(limiter): State<RateLimiter>, request: Request, next: Next) -> Response {
+    if !limiter.config.enabled {
+        return next.run(request).await;
+    }
+
+    let ip = limiter.client_ip(&request);
+    if let Err(wait) = limiter.acquire(&[(Bucket::RegisterPerIp, ip.as_str())]) {
+        return rate_limited(wait);
+    }
+    next.run(request).await
+}
+
+/// 限制会发送邮件的接口
+pub async fn limit_mail(State(limiter): State<RateLimiter>, request: Request, next: Next) -> Response {
+    if !limiter.config.enabled {
+        return next.run(request).await;
+    }
+
+    let ip = limiter.client_ip(&request);
+    if let Err(wait) = limiter.acquire(&[(Bucket::MailPerIp, ip.as_str())]) {
+        return rate_limited(wait);
+    }
+    next.run(request).await
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use sea_orm::{ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use sea_orm::prelude::Expr;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use senyoshu_common::types::api::dic::{
+    DIC_SNAPSHOT_MANIFEST, DIC_SNAPSHOT_VERSION, DicSnapshotManifest, snapshot_hash,
+};
+use senyoshu_common::types::word::word_entry::WordDefine;
+use senyoshu_common::util::cbor_lz4;
+
+use crate::api::account::session::now;
+use crate::config::SnapshotConfig;
+use crate::database::database::{time_key, time_value};
+use crate::database::dic::words;
+
+const SNAPSHOT_PREFIX: &str = "dic-";
+const SNAPSHOT_SUFFIX: &str = ".cbor.lz4";
+
+#[derive(Debug)]
+pub struct SnapshotError(pub String);
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<DbErr> for SnapshotError {
+    fn from(value: DbErr) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl From<std::io::Error> for SnapshotError {
+    fn from(value: std::io::Error) -> Self {
+        Self(value.to_string())
+    }
+}
+
+/// 把词典写成不可变的快照文件，文件名包含内容的哈希，再更新清单
+pub struct SnapshotJob {
+    dir: PathBuf,
+    keep: usize,
+    interval: Duration,
+}
+
+impl SnapshotJob {
+    pub fn new(config: &SnapshotConfig) -> Self {
+        Self {
+            dir: config.dir.to_owned(),
+            keep: config.keep.max(1),
+            interval: Duration::from_secs(config.interval_secs),
+        }
+    }
+
+    /// 每隔 `interval_secs` 生成一次，启动时立即生成
+    pub fn spawn(self, db: &'static DatabaseConnection) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            loop {
+                interval.tick().await;
+                match self.run_once(db).await {
+                    Ok(manifest) => info!(file = manifest.file, words = manifest.words, "dictionary snapshot is ready"),
+                    Err(err) => error!("failed to build dictionary snapshot: {err}"),
+                }
+            }
+        })
+    }
+
+    pub async fn run_once<C: ConnectionTrait>(&self, db: &C) -> Result<DicSnapshotManifest, SnapshotError> {
+        //与同步词典一样，快照只包含高水位之前的修改
+        let watermark = now();
+        let backend = db.get_database_backend();
+        let rows = words::Entity::find()
+            .filter(words::Column::WordDefine.is_not_null())
+            .filter(Expr::expr(time_key(backend, words::Column::UpdateDate)).lte(time_value(backend, watermark)))
+            .all(db)
+            .await?;
+        //按 wid 排序，相同的词典总是得到相同的文件
+        let dic = rows
+            .into_iter()
+            .filter_map(|row| Some((row.wid.0, row.word_define?)))
+            .collect::<BTreeMap<i64, WordDefine>>();
+
+        let bytes = cbor_lz4::encode(&dic).map_err(SnapshotError)?;
+        let hash = snapshot_hash(bytes.as_slice());
+        let file = format!("{SNAPSHOT_PREFIX}{hash}{SNAPSHOT_SUFFIX}");
+
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let path = self.dir.join(file.as_str());
+        if !tokio::fs::try_exists(&path).await? {
+            write_atomically(path.as_path(), bytes.as_slice()).await?;
+        }
+
+        let manifest = DicSnapshotManifest {
+            version: DIC_SNAPSHOT_VERSION,
+            file,
+            hash,
+            watermark,
+            words: dic.len() as u64,
+            size: bytes.len() as u64,
+            create_time: now(),
+        };
+        let json = serde_json::to_vec_pretty(&manifest).map_err(|err| SnapshotError(err.to_string()))?;
+        write_atomically(self.dir.join(DIC_SNAPSHOT_MANIFEST).as_path(), json.as_slice()).await?;
+
+        self.prune(manifest.file.as_str()).await?;
+        Ok(manifest)
+    }
+
+    /// 删除旧的快照，当前的快照总是保留
+    async fn prune(&self, current: &str) -> Result<(), SnapshotError> {
+        let mut snapshots: Vec<(SystemTime, PathBuf)> = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name == current || !name.starts_with(SNAPSHOT_PREFIX) || !name.ends_with(SNAPSHOT_SUFFIX) {
+                continue;
+            }
+            snapshots.push((entry.metadata().await?.modified()?, entry.path()));
+        }
+
+        snapshots.sort_unstable_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+        for (_, path) in snapshots.into_iter().skip(self.keep - 1) {
+            tokio::fs::remove_file(path).await?;
+        }
+        Ok(())
+    }
+}
+
+/// 先写入临时文件再重命名，正在下载的客户端不会读到写了一半的文件
+async fn write_atomically(path: &Path, bytes: &[u8]) -> Result<(), SnapshotError> {
+    let tmp = path.with_extension("tmp");
+    tokio::fs::write(&tmp, bytes).await?;
+    tokio::fs::rename(&tmp, path).await?;
+    Ok(())
+}
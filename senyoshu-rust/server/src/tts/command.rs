@@ -0,0 +1,95 @@
+use std::process::Stdio;
+use std::time::Duration;
+
+use axum::async_trait;
+use tokio::process::Command;
+
+use senyoshu_common::types::api::sound::MAX_SOUND_SIZE;
+
+use crate::api::sound::is_audio_content_type;
+use crate::config::TtsCommandConfig;
+use crate::tts::{TtsAudio, TtsEngine, TtsError, TtsRequest};
+
+/// 错误信息中最多保留的标准错误输出
+const MAX_STDERR_LEN: usize = 512;
+
+/// 调用外部命令生成语音，不经过 shell，音频从标准输出读取
+pub struct CommandEngine {
+    program: String,
+    args: Vec<String>,
+    content_type: String,
+    timeout: Duration,
+}
+
+impl CommandEngine {
+    pub fn new(config: &TtsCommandConfig) -> Result<Self, TtsError> {
+        if config.program.is_empty() {
+            return Err(TtsError(String::from("command.program is empty")));
+        }
+        if !is_audio_content_type(config.content_type.as_str()) {
+            return Err(TtsError(format!(
+                "command.content_type `{}` is not audio",
+                config.content_type
+            )));
+        }
+        if config.timeout_secs == 0 {
+            return Err(TtsError(String::from("command.timeout_secs should be positive")));
+        }
+        Ok(Self {
+            program: config.program.to_owned(),
+            args: config.args.to_owned(),
+            content_type: config.content_type.to_owned(),
+            timeout: Duration::from_secs(config.timeout_secs),
+        })
+    }
+
+    fn args(&self, request: &TtsRequest) -> Vec<String> {
+        let tone = request.tone.to_string();
+        self.args
+            .iter()
+            .map(|arg| {
+                arg.replace("{katakana}", request.katakana.as_str())
+                    .replace("{tone}", tone.as_str())
+                    .replace("{role}", request.role.as_str())
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl TtsEngine for CommandEngine {
+    async fn synthesize(&self, request: &TtsRequest) -> Result<TtsAudio, TtsError> {
+        let child = Command::new(self.program.as_str())
+            .args(self.args(request))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            //超时后 future 被丢弃，同时结束进程
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| TtsError(format!("can not start {}: {err}", self.program)))?;
+
+        let output = tokio::time::timeout(self.timeout, child.wait_with_output())
+            .await
+            .map_err(|_| TtsError(format!("{} timed out", self.program)))?
+            .map_err(|err| TtsError(err.to_string()))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(output.stderr.as_slice());
+            let stderr = stderr.trim().chars().take(MAX_STDERR_LEN).collect::<String>();
+            return Err(TtsError(format!("{} exited with {}: {stderr}", self.program, output.status)));
+        }
+        if output.stdout.is_empty() || output.stdout.len() > MAX_SOUND_SIZE {
+            return Err(TtsError(format!(
+                "{} wrote {} bytes, expected 1 to {MAX_SOUND_SIZE}",
+                self.program,
+                output.stdout.len()
+            )));
+        }
+
+        Ok(TtsAudio {
+            content_type: self.content_type.to_owned(),
+            sound: output.stdout,
+        })
+    }
+}
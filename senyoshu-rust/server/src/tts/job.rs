@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use sea_orm::sea_query::OnConflict;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use senyoshu_common::types::api::account::DELETED_AUTHOR;
+use senyoshu_common::types::api::sound::SoundKey;
+
+use crate::api::account::session::now;
+use crate::api::sound::validate_key;
+use crate::config::TtsConfig;
+use crate::database::dic::{sounds, words};
+use crate::tts::{build_engine, TtsEngine, TtsError, TtsRequest};
+
+/// 生成的音频没有作者，和已删除用户的内容一样使用 0
+pub const TTS_AUTHOR: i64 = DELETED_AUTHOR;
+/// 每次扫描最多读取的词条数量，下次扫描从中断的位置继续，到达末尾后从头开始
+const SCAN_WORDS: u64 = 1000;
+
+/// 一次扫描的结果
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TtsReport {
+    pub generated: usize,
+    pub failed: usize,
+    /// 本次读取的词条中超出 `batch_size` 留给下次扫描的数量
+    pub remaining: usize,
+}
+
+/// 找出词典中还没有音频的读音和声调，通过 [`TtsEngine`] 生成后保存为未审核的音频
+pub struct TtsJob {
+    engine: Arc<dyn TtsEngine>,
+    role: String,
+    batch_size: usize,
+    interval: Duration,
+    /// 生成失败的读音在重启之前不再重试
+    failed: Mutex<HashSet<(String, u8)>>,
+    /// 下次扫描开始的 wid
+    cursor: Mutex<i64>,
+}
+
+impl TtsJob {
+    pub fn new(engine: Arc<dyn TtsEngine>, config: &TtsConfig) -> Self {
+        Self {
+            engine,
+            role: config.role.to_owned(),
+            batch_size: config.batch_size,
+            interval: Duration::from_secs(config.interval_secs),
+            failed: Mutex::new(HashSet::new()),
+            cursor: Mutex::new(i64::MIN),
+        }
+    }
+
+    /// `tts.engine = "none"` 时返回 `None`
+    pub fn from_config(config: &TtsConfig) -> Result<Option<Self>, TtsError> {
+        Ok(build_engine(config)?.map(|engine| Self::new(engine, config)))
+    }
+
+    /// 每隔 `interval_secs` 扫描一次
+    pub fn spawn(self, db: &'static DatabaseConnection) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            loop {
+                interval.tick().await;
+                match self.run_once(db).await {
+                    Ok(report) if report == TtsReport::default() => {}
+                    Ok(report) => info!(?report, "tts job finished"),
+                    Err(err) => error!("tts job failed: {err}"),
+                }
+            }
+        })
+    }
+
+    pub async fn run_once<C: ConnectionTrait>(&self, db: &C) -> Result<TtsReport, DbErr> {
+        let (missing, next) = self.missing(db).await?;
+        let mut report = TtsReport {
+            remaining: missing.len().saturating_sub(self.batch_size),
+            ..Default::default()
+        };
+        //没有处理完的从第一个剩下的词条继续
+        *self.cursor.lock().unwrap() = match missing.get(self.batch_size) {
+            Some((wid, _, _)) => *wid,
+            None => next,
+        };
+
+        for (_, katakana, tone) in missing.into_iter().take(self.batch_size) {
+            let request = TtsRequest {
+                katakana,
+                tone,
+                role: self.role.to_owned(),
+            };
+            let audio = match self.engine.synthesize(&request).await {
+                Ok(audio) => audio,
+                Err(err) => {
+                    warn!(?request, "tts failed: {err}");
+                    self.failed.lock().unwrap().insert((request.katakana, request.tone));
+                    report.failed += 1;
+                    continue;
+                }
+            };
+
+            //扫描之后审核者可能已经上传了同一个音频，不覆盖
+            let inserted = sounds::Entity::insert(sounds::ActiveModel {
+                katakana: Set(request.katakana),
+                tone: Set(request.tone as i16),
+                role: Set(request.role),
+                sound: Set(audio.sound),
+                content_type: Set(audio.content_type),
+                author: Set(TTS_AUTHOR),
+                checked: Set(None),
+                update_date: Set(now()),
+            })
+                .on_conflict(
+                    OnConflict::columns([
+                        sounds::Column::Katakana,
+                        sounds::Column::Tone,
+                        sounds::Column::Role,
+                    ])
+                        .do_nothing()
+                        .to_owned(),
+                )
+                .exec_without_returning(db)
+                .await?;
+            report.generated += inserted as usize;
+        }
+
+        Ok(report)
+    }
+
+    /// 从游标开始按 wid 顺序读取一部分词条，返回其中还没有音频的读音，以及读完后下次开始的 wid
+    async fn missing<C: ConnectionTrait>(&self, db: &C) -> Result<(Vec<(i64, String, u8)>, i64), DbErr> {
+        let start = *self.cursor.lock().unwrap();
+        let words = words::Entity::find()
+            .filter(words::Column::Wid.gte(start))
+            .filter(words::Column::WordDefine.is_not_null())
+            .order_by_asc(words::Column::Wid)
+            .limit(SCAN_WORDS)
+            .all(db)
+            .await?;
+        let next = match words.last() {
+            Some(last) if words.len() as u64 == SCAN_WORDS => last.wid.0.saturating_add(1),
+            _ => i64::MIN,
+        };
+
+        let candidates = words
+            .into_iter()
+            .filter_map(|row| Some((row.wid.0, row.word_define?)))
+            .flat_map(|(wid, word_define)| {
+                let katakana = word_define.word.get_katakana();
+                word_define
+                    .word
+                    .tones
+                    .iter()
+                    .map(move |tone| (wid, katakana.to_owned(), tone.index() as u8))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|(_, katakana, tone)| {
+                let key = SoundKey {
+                    katakana: katakana.to_owned(),
+                    tone: *tone,
+                    role: self.role.to_owned(),
+                };
+                validate_key(&key).is_ok()
+            })
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return Ok((Vec::new(), next));
+        }
+
+        //只查询这些读音已有的音频，任意说话人的音频都算已有
+        let katakanas = candidates
+            .iter()
+            .map(|(_, katakana, _)| katakana.to_owned())
+            .collect::<HashSet<_>>();
+        let existing = sounds::Entity::find()
+            .select_only()
+            .columns([sounds::Column::Katakana, sounds::Column::Tone])
+            .filter(sounds::Column::Katakana.is_in(katakanas))
+            .into_tuple::<(String, i16)>()
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|(katakana, tone)| (katakana, tone as u8))
+            .collect::<HashSet<_>>();
+        let failed = self.failed.lock().unwrap().to_owned();
+
+        //同一个读音可能出现在多个词条中，只保留第一次出现的
+        let mut seen = HashSet::new();
+        let missing = candidates
+            .into_iter()
+            .filter(|(_, katakana, tone)| {
+                let key = (katakana.to_owned(), *tone);
+                !existing.contains(&key) && !failed.contains(&key) && seen.insert(key)
+            })
+            .collect();
+        Ok((missing, next))
+    }
+}
@@ -0,0 +1,54 @@
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use crate::config::{TtsConfig, TtsEngineKind};
+use crate::tts::command::CommandEngine;
+use crate::tts::test_engine::TestEngine;
+
+pub mod command;
+pub mod job;
+pub mod test_engine;
+
+/// 需要生成的读音和声调
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TtsRequest {
+    pub katakana: String,
+    /// 第几拍之后下降，0 为平板型
+    pub tone: u8,
+    pub role: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct TtsAudio {
+    /// 例如 `audio/wav`
+    pub content_type: String,
+    pub sound: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct TtsError(pub String);
+
+impl Display for TtsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for TtsError {}
+
+/// 生成带声调的语音，由配置中的 `tts.engine` 决定
+#[async_trait]
+pub trait TtsEngine: Send + Sync {
+    async fn synthesize(&self, request: &TtsRequest) -> Result<TtsAudio, TtsError>;
+}
+
+/// `tts.engine = "none"` 时返回 `None`
+pub fn build_engine(config: &TtsConfig) -> Result<Option<Arc<dyn TtsEngine>>, TtsError> {
+    Ok(match config.engine {
+        TtsEngineKind::None => None,
+        TtsEngineKind::Command => Some(Arc::new(CommandEngine::new(&config.command)?)),
+        TtsEngineKind::Test => Some(Arc::new(TestEngine)),
+    })
+}
@@ -0,0 +1,129 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+
+use senyoshu_common::types::api::account::{LOGIN_API, REGISTER_API, UPDATE_PASSWD_API, UPDATE_USER_STATE_API, UserState};
+use senyoshu_common::types::error::Error;
+use senyoshu_common::util::passwd_hasher::get_passwd_hash;
+use senyoshu_server::database::account::{self, PasswdHashVersion};
+
+use crate::common::{host, login, login_again, PASSWORD_HASH, with_db};
+
+mod common;
+
+#[tokio::test]
+async fn login_returns_valid_token() {
+    let token = login("accountlogin").await;
+
+    let state = UPDATE_USER_STATE_API.call_with_host(host(), &token, &()).await.unwrap();
+    let UserState::Valid(user_info) = state else {
+        panic!("token should be valid: {state:?}");
+    };
+    assert_eq!(user_info.uid, token.uid);
+    assert_eq!(user_info.username, "accountlogin");
+    assert!(!user_info.content_maintainer);
+}
+
+#[tokio::test]
+async fn login_with_wrong_password_is_not_auth() {
+    login("accountwrongpassword").await;
+
+    let result = LOGIN_API
+        .call_with_host(host(), &(String::from("accountwrongpassword"), String::from("wrong"), String::new()))
+        .await;
+    assert_eq!(result, Err(Error::NotAuth));
+
+    let result = LOGIN_API
+        .call_with_host(host(), &(String::from("accountnotexist"), PASSWORD_HASH.to_string(), String::new()))
+        .await;
+    assert_eq!(result, Err(Error::NotAuth));
+}
+
+#[tokio::test]
+async fn register_twice_is_conflict() {
+    login("accountregistertwice").await;
+
+    let result = REGISTER_API
+        .call_with_host(host(), &(String::from("accountregistertwice"), PASSWORD_HASH.to_string()))
+        .await;
+    assert_eq!(result, Err(Error::Conflict));
+}
+
+async fn find_account(username: &'static str) -> account::Model {
+    with_db(move |db| async move {
+        account::Entity::find()
+            .filter(account::Column::Username.eq(username))
+            .one(db)
+            .await
+            .unwrap()
+            .unwrap()
+    })
+        .await
+}
+
+#[tokio::test]
+async fn register_uses_per_user_salt() {
+    login("accountsalta").await;
+    login("accountsaltb").await;
+
+    let a = find_account("accountsalta").await;
+    let b = find_account("accountsaltb").await;
+    assert_eq!(a.passwd_hash_version, PasswdHashVersion::Argon2id);
+    assert!(a.passwd_hash2.starts_with("$argon2id$"));
+    assert_ne!(a.passwd_hash2, b.passwd_hash2);
+}
+
+#[tokio::test]
+async fn legacy_hash_is_upgraded_on_login() {
+    with_db(|db| async move {
+        account::ActiveModel {
+            username: Set(String::from("accountlegacy")),
+            passwd_hash2: Set(get_passwd_hash(PASSWORD_HASH)),
+            passwd_hash_version: Set(PasswdHashVersion::Legacy),
+            ..Default::default()
+        }
+            .insert(db)
+            .await
+            .unwrap();
+    })
+        .await;
+    let request = (String::from("accountlegacy"), PASSWORD_HASH.to_string(), String::new());
+
+    let result = LOGIN_API
+        .call_with_host(host(), &(String::from("accountlegacy"), String::from("wrong"), String::new()))
+        .await;
+    assert_eq!(result, Err(Error::NotAuth));
+    assert_eq!(find_account("accountlegacy").await.passwd_hash_version, PasswdHashVersion::Legacy);
+
+    LOGIN_API.call_with_host(host(), &request).await.unwrap();
+    let upgraded = find_account("accountlegacy").await;
+    assert_eq!(upgraded.passwd_hash_version, PasswdHashVersion::Argon2id);
+    assert!(upgraded.passwd_hash2.starts_with("$argon2id$"));
+
+    LOGIN_API.call_with_host(host(), &request).await.unwrap();
+}
+
+#[tokio::test]
+async fn update_passwd_requires_old_passwd() {
+    login("accountupdatepasswd").await;
+    let token = login_again("accountupdatepasswd", "pc").await;
+    let username = String::from("accountupdatepasswd");
+
+    let result = UPDATE_PASSWD_API
+        .call_with_host(host(), &(username.to_owned(), String::from("new"), String::from("wrong")))
+        .await;
+    assert_eq!(result, Err(Error::NotAuth));
+
+    UPDATE_PASSWD_API
+        .call_with_host(host(), &(username.to_owned(), String::from("new"), PASSWORD_HASH.to_string()))
+        .await
+        .unwrap();
+
+    let result = LOGIN_API
+        .call_with_host(host(), &(username.to_owned(), PASSWORD_HASH.to_string(), String::new()))
+        .await;
+    assert_eq!(result, Err(Error::NotAuth));
+    LOGIN_API.call_with_host(host(), &(username, String::from("new"), String::new())).await.unwrap();
+
+    //修改密码后已登录的设备需要重新登录
+    let state = UPDATE_USER_STATE_API.call_with_host(host(), &token, &()).await.unwrap();
+    assert_eq!(state, UserState::TokenRevoked);
+}
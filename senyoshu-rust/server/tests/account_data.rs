@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use http::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+
+use senyoshu_common::types::api::account::{
+    ACCOUNT_EXPORT_VERSION, AccountExport, DELETE_ACCOUNT_API, DELETED_AUTHOR, EXPORT_ACCOUNT_API,
+    LOGIN_API, UPDATE_USER_STATE_API, UserState,
+};
+use senyoshu_common::types::api::comment::{
+    CommentQuery, CommentTarget, CREATE_COMMENT_API, DELETE_COMMENT_API, LIST_COMMENTS_API, NewComment,
+};
+use senyoshu_common::types::api::dic::{
+    GET_MODERATION_LOG_API, GET_MY_SUBMISSIONS_API, ModerationLogQuery, POST_WORD_API, SET_ADOPTED_API,
+};
+use senyoshu_common::types::api::learn::POST_LEARN_RECORD_API;
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::learn::knowledge::{Knowledge, KnowledgeType};
+use senyoshu_common::types::learn::learn_knowledge_history::{LearnKnowledgeHistory, OperateRecord, OperateType};
+use senyoshu_common::types::learn::LearnHistoryMap;
+use senyoshu_common::types::moderation::ModerationAction;
+use senyoshu_common::types::state::State;
+use senyoshu_common::types::word::word_entry::WordEntry;
+use senyoshu_common::util::time::UtcTimeStamp;
+
+use crate::common::{
+    host, latest_request, login, login_content_maintainer, PASSWORD_HASH, seed_word, word_define,
+};
+
+mod common;
+
+fn learn_record(key: &str) -> LearnHistoryMap {
+    LearnHistoryMap::new(HashMap::from([(
+        Knowledge {
+            knowledge_type: KnowledgeType::Kanji,
+            key: key.to_string(),
+        },
+        LearnKnowledgeHistory {
+            history: Vec::from([OperateRecord {
+                operate_type: OperateType::Seen,
+                operate_time: UtcTimeStamp(1000),
+            }]),
+            freeze_time: None,
+        },
+    )]))
+}
+
+#[tokio::test]
+async fn export_contains_profile_learn_and_contributions() {
+    let token = login("exportall").await;
+    POST_LEARN_RECORD_API.call_with_host(host(), &token, &learn_record("山")).await.unwrap();
+    let wid = seed_word(word_define("export")).await;
+    let entry = WordEntry { id: wid, word_define: word_define("export changed") };
+    POST_WORD_API.call_with_host(host(), &token, &entry).await.unwrap();
+    let target = CommentTarget::Word(wid);
+    let comment = NewComment { target, parent: None, content: String::from("exported") };
+    let kept = CREATE_COMMENT_API.call_with_host(host(), &token, &comment).await.unwrap();
+    let removed = CREATE_COMMENT_API.call_with_host(host(), &token, &comment).await.unwrap();
+    DELETE_COMMENT_API.call_with_host(host(), &token, &removed.id).await.unwrap();
+
+    let export = EXPORT_ACCOUNT_API.call_with_host(host(), &token, &()).await.unwrap();
+    assert_eq!(export.version, ACCOUNT_EXPORT_VERSION);
+    assert_eq!(export.profile.username, "exportall");
+    assert_eq!(export.learn.len(), 1);
+    assert_eq!(export.learn[0].0.key, "山");
+    assert_eq!(export.contributions.len(), 1);
+    assert_eq!(export.contributions[0].wid, wid);
+    assert_eq!(export.contributions[0].word_define, word_define("export changed"));
+    //删除的评论不导出
+    assert_eq!(export.comments, Vec::from([kept]));
+}
+
+#[tokio::test]
+async fn export_is_encoded_as_cbor_on_request() {
+    let token = login("exportcbor").await;
+    POST_LEARN_RECORD_API.call_with_host(host(), &token, &learn_record("川")).await.unwrap();
+
+    let response = reqwest::Client::new()
+        .post(format!("{}{}", host(), EXPORT_ACCOUNT_API.path().trim_start_matches('/')))
+        .header(AUTHORIZATION, format!("Bearer {}", token.credential()))
+        .header(ACCEPT, "application/cbor")
+        .json(&())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/cbor");
+    let bytes = response.bytes().await.unwrap();
+    let export: AccountExport = ciborium::from_reader(bytes.as_ref()).unwrap();
+    assert_eq!(export.profile.uid, token.uid);
+    assert_eq!(export.learn[0].0.key, "川");
+}
+
+#[tokio::test]
+async fn deleted_account_keeps_anonymous_contributions() {
+    let token = login("deleteme").await;
+    POST_LEARN_RECORD_API.call_with_host(host(), &token, &learn_record("木")).await.unwrap();
+    let wid = seed_word(word_define("delete")).await;
+    let entry = WordEntry { id: wid, word_define: word_define("delete withdrawn") };
+    POST_WORD_API.call_with_host(host(), &token, &entry).await.unwrap();
+    let (withdrawn, _) = latest_request(token.uid, wid).await;
+    SET_ADOPTED_API.call_with_host(host(), &token, &(withdrawn, State::Withdraw, None)).await.unwrap();
+    let entry = WordEntry { id: wid, word_define: word_define("delete changed") };
+    POST_WORD_API.call_with_host(host(), &token, &entry).await.unwrap();
+    let (pid, _) = latest_request(token.uid, wid).await;
+    let target = CommentTarget::Word(wid);
+    let replier = login("deletemereplier").await;
+    let comment = NewComment { target, parent: None, content: String::from("replied") };
+    let replied = CREATE_COMMENT_API.call_with_host(host(), &token, &comment).await.unwrap();
+    let comment = NewComment { target, parent: Some(replied.id), content: String::from("reply") };
+    let reply = CREATE_COMMENT_API.call_with_host(host(), &replier, &comment).await.unwrap();
+    let comment = NewComment { target, parent: None, content: String::from("alone") };
+    CREATE_COMMENT_API.call_with_host(host(), &token, &comment).await.unwrap();
+
+    let result = DELETE_ACCOUNT_API.call_with_host(host(), &token, &String::from("wrong")).await;
+    assert_eq!(result, Err(Error::NotAuth));
+    DELETE_ACCOUNT_API
+        .call_with_host(host(), &token, &PASSWORD_HASH.to_string())
+        .await
+        .unwrap();
+
+    let state = UPDATE_USER_STATE_API.call_with_host(host(), &token, &()).await;
+    assert!(!matches!(state, Ok(UserState::Valid(_))));
+    let request = (String::from("deleteme"), PASSWORD_HASH.to_string(), String::from("test"));
+    assert_eq!(LOGIN_API.call_with_host(host(), &request).await.map(|_| ()), Err(Error::NotAuth));
+
+    //修改请求还在，但已经不属于任何账号
+    assert_eq!(latest_request(DELETED_AUTHOR, wid).await, (pid, State::Pending));
+    //审核日志中的操作者也被去掉
+    let maintainer = login_content_maintainer("deletemelog").await;
+    let query = ModerationLogQuery { wid: Some(wid), ..Default::default() };
+    let logs = GET_MODERATION_LOG_API.call_with_host(host(), &maintainer, &query).await.unwrap();
+    let actions = logs.iter().map(|it| (it.action, it.actor, it.pid)).collect::<Vec<_>>();
+    assert_eq!(actions, Vec::from([(ModerationAction::Withdraw, DELETED_AUTHOR, Some(withdrawn))]));
+    assert_eq!(logs[0].actor_name, "");
+    //评论按删除处理，有回复的只保留位置
+    let query = CommentQuery { target, after: None, limit: 0 };
+    let comments = LIST_COMMENTS_API.call_with_host(host(), &query).await.unwrap();
+    assert_eq!(comments.len(), 2);
+    assert_eq!((comments[0].id, comments[0].author, comments[0].deleted), (replied.id, DELETED_AUTHOR, true));
+    assert_eq!((comments[0].content.as_str(), comments[0].author_name.as_str()), ("", ""));
+    assert_eq!(comments[1], reply);
+
+    //用户名可以重新注册，新账号没有旧数据
+    let token = login("deleteme").await;
+    let export = EXPORT_ACCOUNT_API.call_with_host(host(), &token, &()).await.unwrap();
+    assert!(export.learn.is_empty());
+    assert!(export.contributions.is_empty());
+    assert!(export.comments.is_empty());
+}
+
+#[tokio::test]
+async fn deleted_reviewer_is_removed_from_reviews() {
+    let author = login("deletedreviewauthor").await;
+    let reviewer = login_content_maintainer("deletedreviewer").await;
+    let wid = seed_word(word_define("deleted review")).await;
+    let entry = WordEntry { id: wid, word_define: word_define("deleted review changed") };
+    POST_WORD_API.call_with_host(host(), &author, &entry).await.unwrap();
+    let (pid, _) = latest_request(author.uid, wid).await;
+    SET_ADOPTED_API
+        .call_with_host(host(), &reviewer, &(pid, State::Cancel, Some(String::from("not a word"))))
+        .await
+        .unwrap();
+
+    DELETE_ACCOUNT_API
+        .call_with_host(host(), &reviewer, &PASSWORD_HASH.to_string())
+        .await
+        .unwrap();
+
+    //审核结果还在，审核者和审核意见被去掉
+    let submissions = GET_MY_SUBMISSIONS_API.call_with_host(host(), &author, &()).await.unwrap();
+    assert_eq!(submissions[0].state, State::Cancel);
+    assert_eq!(submissions[0].reviewer, Some(DELETED_AUTHOR));
+    assert_eq!(submissions[0].review_comment, None);
+
+    let maintainer = login_content_maintainer("deletedreviewlog").await;
+    let query = ModerationLogQuery { wid: Some(wid), ..Default::default() };
+    let logs = GET_MODERATION_LOG_API.call_with_host(host(), &maintainer, &query).await.unwrap();
+    let actions = logs.iter().map(|it| (it.action, it.actor, it.comment.to_owned())).collect::<Vec<_>>();
+    assert_eq!(actions, Vec::from([(ModerationAction::Cancel, DELETED_AUTHOR, None)]));
+}
@@ -0,0 +1,133 @@
+use chrono::{TimeDelta, Utc};
+
+use senyoshu_common::types::api::account::{UPDATE_USER_STATE_API, UserState};
+use senyoshu_common::types::api::admin::{
+    Role, SEARCH_USERS_API, SET_RESTRICT_API, SET_USER_ROLE_API, SET_VIP_API, UserQuery, VipChange,
+};
+use senyoshu_common::types::api::dic::{CREATE_WORD_API, DELETE_WORD_API, POST_WORD_API};
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::word::word_entry::WordEntry;
+
+use crate::common::{host, login, login_admin, login_content_maintainer, seed_word, word_define};
+
+mod common;
+
+fn query(keyword: &str) -> UserQuery {
+    UserQuery {
+        keyword: keyword.to_string(),
+        offset: 0,
+        limit: 10,
+    }
+}
+
+#[tokio::test]
+async fn only_admin_can_manage_users() {
+    let user = login("adminonlyuser").await;
+    let admin = login_admin("adminonlyadmin").await;
+
+    let result = SEARCH_USERS_API.call_with_host(host(), &user, &query("adminonly")).await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+    let result = SET_USER_ROLE_API
+        .call_with_host(host(), &user, &(user.uid, Role::Admin, true))
+        .await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+
+    let users = SEARCH_USERS_API.call_with_host(host(), &admin, &query("adminonly")).await.unwrap();
+    assert_eq!(
+        users.iter().map(|it| it.username.as_str()).collect::<Vec<_>>(),
+        ["adminonlyuser", "adminonlyadmin"]
+    );
+    let users = SEARCH_USERS_API
+        .call_with_host(host(), &admin, &query(user.uid.to_string().as_str()))
+        .await
+        .unwrap();
+    assert!(users.iter().any(|it| it.uid == user.uid));
+
+    //通配符按普通字符匹配
+    for keyword in ["%", "_", "adminonly_ser", "adminonly%"] {
+        let users = SEARCH_USERS_API.call_with_host(host(), &admin, &query(keyword)).await.unwrap();
+        assert!(users.is_empty(), "{keyword}");
+    }
+}
+
+#[tokio::test]
+async fn set_role_and_vip() {
+    let user = login("adminroleuser").await;
+    let admin = login_admin("adminroleadmin").await;
+
+    let info = SET_USER_ROLE_API
+        .call_with_host(host(), &admin, &(user.uid, Role::ContentMaintainer, true))
+        .await
+        .unwrap();
+    assert!(info.content_maintainer);
+    let state = UPDATE_USER_STATE_API.call_with_host(host(), &user, &()).await.unwrap();
+    assert!(matches!(state, UserState::Valid(info) if info.content_maintainer));
+
+    //不能撤销自己的管理员
+    let result = SET_USER_ROLE_API
+        .call_with_host(host(), &admin, &(admin.uid, Role::Admin, false))
+        .await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+
+    let before = Utc::now();
+    let info = SET_VIP_API
+        .call_with_host(host(), &admin, &(user.uid, VipChange::ExtendDays(30)))
+        .await
+        .unwrap();
+    let first = info.vip.unwrap();
+    assert!(first >= before + TimeDelta::days(30));
+    let info = SET_VIP_API
+        .call_with_host(host(), &admin, &(user.uid, VipChange::ExtendDays(30)))
+        .await
+        .unwrap();
+    assert_eq!(info.vip.unwrap(), first + TimeDelta::days(30));
+    let info = SET_VIP_API
+        .call_with_host(host(), &admin, &(user.uid, VipChange::Revoke))
+        .await
+        .unwrap();
+    assert_eq!(info.vip, None);
+
+    let result = SET_VIP_API.call_with_host(host(), &admin, &(-1, VipChange::Revoke)).await;
+    assert_eq!(result, Err(Error::NotFound));
+}
+
+#[tokio::test]
+async fn restricted_user_can_not_edit_words() {
+    let user = login("adminrestrictuser").await;
+    let maintainer = login_content_maintainer("adminrestrictmaintainer").await;
+    let admin = login_admin("adminrestrictadmin").await;
+    let wid = seed_word(word_define("restrict")).await;
+
+    let result = SET_RESTRICT_API
+        .call_with_host(host(), &admin, &(admin.uid, Some(String::from("self"))))
+        .await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+    let result = SET_RESTRICT_API
+        .call_with_host(host(), &admin, &(user.uid, Some(String::from("  "))))
+        .await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+
+    for uid in [user.uid, maintainer.uid] {
+        let info = SET_RESTRICT_API
+            .call_with_host(host(), &admin, &(uid, Some(String::from("spam"))))
+            .await
+            .unwrap();
+        assert!(info.restrict_user);
+        assert_eq!(info.restrict_reason.as_deref(), Some("spam"));
+    }
+    let state = UPDATE_USER_STATE_API.call_with_host(host(), &user, &()).await.unwrap();
+    assert!(matches!(state, UserState::Valid(info) if info.restrict_reason.as_deref() == Some("spam")));
+
+    let entry = WordEntry { id: wid, word_define: word_define("restrict changed") };
+    let result = POST_WORD_API.call_with_host(host(), &user, &entry).await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+    let result = CREATE_WORD_API.call_with_host(host(), &user, &word_define("restrict new")).await;
+    assert_eq!(result.map(|_| ()), Err(Error::PermissionDenied));
+    let result = DELETE_WORD_API.call_with_host(host(), &maintainer, &(wid.0, None)).await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+
+    let info = SET_RESTRICT_API.call_with_host(host(), &admin, &(user.uid, None)).await.unwrap();
+    assert!(!info.restrict_user);
+    assert_eq!(info.restrict_reason, None);
+    POST_WORD_API.call_with_host(host(), &user, &entry).await.unwrap();
+}
@@ -0,0 +1,76 @@
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm::prelude::Expr;
+
+use senyoshu_common::types::api::{ApiResult, AuthAPI};
+use senyoshu_common::types::api::dic::{GET_CHANGE_REQUEST_API, POST_WORD_API};
+use senyoshu_common::types::api::learn::{GET_RECORD_API, GetRecordRequest};
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::word::word_entry::WordEntry;
+use senyoshu_server::database::account;
+
+use crate::common::{host, login, login_content_maintainer, seed_word, with_db, word_define};
+
+mod common;
+
+/// 不经过 `AuthAPI::call`，直接发送请求
+async fn raw_call<REQ, RES>(api: AuthAPI<REQ, RES>, authorization: Option<&str>, body: &REQ) -> (u16, ApiResult<RES>)
+    where
+        REQ: serde::Serialize + serde::de::DeserializeOwned,
+        RES: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let url = format!("{}{}", host().trim_end_matches('/'), api.path());
+    let mut request = reqwest::Client::new().post(url).json(body);
+    if let Some(authorization) = authorization {
+        request = request.header(reqwest::header::AUTHORIZATION, authorization);
+    }
+    let response = request.send().await.unwrap();
+    let status = response.status().as_u16();
+    (status, response.json().await.unwrap())
+}
+
+#[tokio::test]
+async fn missing_or_malformed_authorization_is_not_auth() {
+    let token = login("authheader").await;
+
+    let (status, result) = raw_call(GET_RECORD_API, None, &GetRecordRequest::default()).await;
+    assert_eq!((status, result.map(|it| it.records.len())), (401, Err(Error::NotAuth)));
+
+    for authorization in [token.token.to_owned(), format!("Basic {}", token.credential()), String::from("Bearer abc")] {
+        let (status, result) = raw_call(GET_RECORD_API, Some(authorization.as_str()), &GetRecordRequest::default()).await;
+        assert_eq!((status, result.map(|it| it.records.len())), (401, Err(Error::NotAuth)));
+    }
+
+    let authorization = format!("Bearer {}", token.credential());
+    let (status, result) = raw_call(GET_RECORD_API, Some(authorization.as_str()), &GetRecordRequest::default()).await;
+    assert_eq!((status, result.map(|it| it.records.len())), (200, Ok(0)));
+}
+
+#[tokio::test]
+async fn content_maintainer_guard() {
+    let user = login("authuser").await;
+    let maintainer = login_content_maintainer("authmaintainer").await;
+
+    let result = GET_CHANGE_REQUEST_API.call_with_host(host(), &user, &()).await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+    GET_CHANGE_REQUEST_API.call_with_host(host(), &maintainer, &()).await.unwrap();
+}
+
+#[tokio::test]
+async fn post_permission_guard() {
+    let user = login("authnopost").await;
+    let uid = user.uid;
+    with_db(move |db| async move {
+        account::Entity::update_many()
+            .filter(account::Column::Uid.eq(uid))
+            .col_expr(account::Column::PostPermission, Expr::value(false))
+            .exec(db)
+            .await
+            .unwrap();
+    })
+        .await;
+    let wid = seed_word(word_define("no post")).await;
+
+    let entry = WordEntry { id: wid, word_define: word_define("no post changed") };
+    let result = POST_WORD_API.call_with_host(host(), &user, &entry).await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+}
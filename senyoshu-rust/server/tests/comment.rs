@@ -0,0 +1,210 @@
+use senyoshu_common::types::api::admin::SET_RESTRICT_API;
+use senyoshu_common::types::api::comment::{
+    CommentQuery, CommentTarget, CREATE_COMMENT_API, DELETE_COMMENT_API, EDIT_COMMENT_API,
+    LIST_COMMENTS_API, NewComment,
+};
+use senyoshu_common::types::api::dic::{DELETE_WORD_API, POST_WORD_API};
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::word::word_entry::WordEntry;
+
+use crate::common::{
+    host, latest_request, login, login_admin, login_content_maintainer, seed_word, word_define,
+};
+
+mod common;
+
+fn new_comment(target: CommentTarget, parent: Option<i64>, content: &str) -> NewComment {
+    NewComment { target, parent, content: content.to_string() }
+}
+
+fn first_page(target: CommentTarget) -> CommentQuery {
+    CommentQuery { target, after: None, limit: 0 }
+}
+
+#[tokio::test]
+async fn comments_form_threads() {
+    let author = login("commentauthor").await;
+    let replier = login("commentreplier").await;
+    let wid = seed_word(word_define("comment")).await;
+    let target = CommentTarget::Word(wid);
+
+    let root = CREATE_COMMENT_API
+        .call_with_host(host(), &author, &new_comment(target, None, "  why this reading?  "))
+        .await
+        .unwrap();
+    assert_eq!(root.content, "why this reading?");
+    assert_eq!(root.author_name, "commentauthor");
+    let reply = CREATE_COMMENT_API
+        .call_with_host(host(), &replier, &new_comment(target, Some(root.id), "see the dictionary"))
+        .await
+        .unwrap();
+    assert_eq!(reply.parent, Some(root.id));
+
+    let comments = LIST_COMMENTS_API.call_with_host(host(), &first_page(target)).await.unwrap();
+    assert_eq!(comments, Vec::from([root.clone(), reply]));
+
+    let result = CREATE_COMMENT_API
+        .call_with_host(host(), &author, &new_comment(target, None, "   "))
+        .await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+
+    //回复需要属于同一个词条
+    let entry = WordEntry { id: wid, word_define: word_define("comment changed") };
+    POST_WORD_API.call_with_host(host(), &author, &entry).await.unwrap();
+    let (pid, _) = latest_request(author.uid, wid).await;
+    let request_target = CommentTarget::ChangeRequest(pid);
+    let result = CREATE_COMMENT_API
+        .call_with_host(host(), &replier, &new_comment(request_target, Some(root.id), "wrong thread"))
+        .await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+    CREATE_COMMENT_API
+        .call_with_host(host(), &replier, &new_comment(request_target, None, "on the request"))
+        .await
+        .unwrap();
+    let comments = LIST_COMMENTS_API.call_with_host(host(), &first_page(request_target)).await.unwrap();
+    assert_eq!(comments.len(), 1);
+
+    let result = CREATE_COMMENT_API
+        .call_with_host(host(), &author, &new_comment(CommentTarget::ChangeRequest(i64::MAX), None, "missing"))
+        .await;
+    assert_eq!(result.map(|_| ()), Err(Error::NotFound));
+}
+
+#[tokio::test]
+async fn only_author_edits_comment() {
+    let author = login("commenteditor").await;
+    let other = login("commentother").await;
+    let wid = seed_word(word_define("comment edit")).await;
+
+    let comment = CREATE_COMMENT_API
+        .call_with_host(host(), &author, &new_comment(CommentTarget::Word(wid), None, "first"))
+        .await
+        .unwrap();
+    let result = EDIT_COMMENT_API
+        .call_with_host(host(), &other, &(comment.id, String::from("hijacked")))
+        .await;
+    assert_eq!(result.map(|_| ()), Err(Error::PermissionDenied));
+
+    let edited = EDIT_COMMENT_API
+        .call_with_host(host(), &author, &(comment.id, String::from("second")))
+        .await
+        .unwrap();
+    assert_eq!(edited.content, "second");
+    assert!(edited.edit_time.is_some());
+}
+
+#[tokio::test]
+async fn deleted_comment_keeps_replies() {
+    let author = login("commentdeleted").await;
+    let replier = login("commentkept").await;
+    let maintainer = login_content_maintainer("commentmaintainer").await;
+    let wid = seed_word(word_define("comment delete")).await;
+    let target = CommentTarget::Word(wid);
+
+    let root = CREATE_COMMENT_API
+        .call_with_host(host(), &author, &new_comment(target, None, "root"))
+        .await
+        .unwrap();
+    let reply = CREATE_COMMENT_API
+        .call_with_host(host(), &replier, &new_comment(target, Some(root.id), "reply"))
+        .await
+        .unwrap();
+    let lonely = CREATE_COMMENT_API
+        .call_with_host(host(), &author, &new_comment(target, None, "lonely"))
+        .await
+        .unwrap();
+
+    let result = DELETE_COMMENT_API.call_with_host(host(), &replier, &root.id).await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+    DELETE_COMMENT_API.call_with_host(host(), &maintainer, &root.id).await.unwrap();
+    DELETE_COMMENT_API.call_with_host(host(), &author, &lonely.id).await.unwrap();
+    let result = EDIT_COMMENT_API
+        .call_with_host(host(), &author, &(root.id, String::from("revived")))
+        .await;
+    assert_eq!(result.map(|_| ()), Err(Error::NotFound));
+
+    //有回复的评论只清空内容，没有回复的直接隐藏
+    let comments = LIST_COMMENTS_API.call_with_host(host(), &first_page(target)).await.unwrap();
+    assert_eq!(comments.iter().map(|it| it.id).collect::<Vec<_>>(), Vec::from([root.id, reply.id]));
+    assert!(comments[0].deleted);
+    assert_eq!(comments[0].content, "");
+    assert_eq!(comments[1].content, "reply");
+
+    //回复也都删除之后整条讨论隐藏
+    let nested = CREATE_COMMENT_API
+        .call_with_host(host(), &author, &new_comment(target, Some(reply.id), "nested"))
+        .await
+        .unwrap();
+    DELETE_COMMENT_API.call_with_host(host(), &replier, &reply.id).await.unwrap();
+    let comments = LIST_COMMENTS_API.call_with_host(host(), &first_page(target)).await.unwrap();
+    assert_eq!(comments.iter().map(|it| it.id).collect::<Vec<_>>(), Vec::from([root.id, reply.id, nested.id]));
+    DELETE_COMMENT_API.call_with_host(host(), &author, &nested.id).await.unwrap();
+    let comments = LIST_COMMENTS_API.call_with_host(host(), &first_page(target)).await.unwrap();
+    assert!(comments.is_empty());
+}
+
+#[tokio::test]
+async fn comments_are_paged() {
+    let author = login("commentpager").await;
+    let wid = seed_word(word_define("comment page")).await;
+    let target = CommentTarget::Word(wid);
+    let mut ids = Vec::new();
+    for index in 0..3 {
+        let comment = CREATE_COMMENT_API
+            .call_with_host(host(), &author, &new_comment(target, None, format!("page {index}").as_str()))
+            .await
+            .unwrap();
+        ids.push(comment.id);
+    }
+    DELETE_COMMENT_API.call_with_host(host(), &author, &ids[1]).await.unwrap();
+
+    //删除的评论不占用页的数量
+    let query = CommentQuery { limit: 1, ..first_page(target) };
+    let first = LIST_COMMENTS_API.call_with_host(host(), &query).await.unwrap();
+    assert_eq!(first.iter().map(|it| it.id).collect::<Vec<_>>(), Vec::from([ids[0]]));
+    let query = CommentQuery { after: Some(first[0].id), ..query };
+    let second = LIST_COMMENTS_API.call_with_host(host(), &query).await.unwrap();
+    assert_eq!(second.iter().map(|it| it.id).collect::<Vec<_>>(), Vec::from([ids[2]]));
+    let query = CommentQuery { after: Some(second[0].id), ..query };
+    assert!(LIST_COMMENTS_API.call_with_host(host(), &query).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn deleted_word_cannot_be_commented() {
+    let author = login("commentdeletedword").await;
+    let maintainer = login_content_maintainer("commentwordremover").await;
+    let wid = seed_word(word_define("comment removed word")).await;
+    DELETE_WORD_API.call_with_host(host(), &maintainer, &(wid.0, None)).await.unwrap();
+
+    let result = CREATE_COMMENT_API
+        .call_with_host(host(), &author, &new_comment(CommentTarget::Word(wid), None, "too late"))
+        .await;
+    assert_eq!(result.map(|_| ()), Err(Error::WordIsNotExist));
+}
+
+#[tokio::test]
+async fn restricted_user_cannot_comment() {
+    let admin = login_admin("commentadmin").await;
+    let user = login("commentrestricted").await;
+    let wid = seed_word(word_define("comment restrict")).await;
+    let target = CommentTarget::Word(wid);
+
+    let comment = CREATE_COMMENT_API
+        .call_with_host(host(), &user, &new_comment(target, None, "before"))
+        .await
+        .unwrap();
+    SET_RESTRICT_API
+        .call_with_host(host(), &admin, &(user.uid, Some(String::from("spam"))))
+        .await
+        .unwrap();
+
+    let result = CREATE_COMMENT_API
+        .call_with_host(host(), &user, &new_comment(target, None, "after"))
+        .await;
+    assert_eq!(result.map(|_| ()), Err(Error::PermissionDenied));
+    let result = EDIT_COMMENT_API
+        .call_with_host(host(), &user, &(comment.id, String::from("after")))
+        .await;
+    assert_eq!(result.map(|_| ()), Err(Error::PermissionDenied));
+    DELETE_COMMENT_API.call_with_host(host(), &user, &comment.id).await.unwrap();
+}
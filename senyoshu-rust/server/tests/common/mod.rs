@@ -0,0 +1,221 @@
+//! 集成测试共用的服务端：进程内只启动一次，使用 sqlite 内存数据库
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::OnceLock;
+
+use chrono::{DateTime, FixedOffset};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
+use sea_orm::prelude::Expr;
+use tokio::net::TcpListener;
+use tokio::runtime::Handle;
+
+use senyoshu_common::types::api::account::{LOGIN_API, REGISTER_API, Token};
+use senyoshu_common::types::api::dic::{SYNC_DIC_API, SyncDicRequest};
+use senyoshu_common::types::state::State;
+use senyoshu_common::types::word::wid::WordIdentity;
+use senyoshu_common::types::word::word_entry::WordDefine;
+use senyoshu_server::app::app;
+use senyoshu_server::config::{Config, DatabaseConfig, MailConfig, MailTransport, RateLimitConfig};
+use senyoshu_server::database::account;
+use senyoshu_server::database::database::GlobalDatabase;
+use senyoshu_server::database::dic::{word_history, words};
+use senyoshu_server::mail::file_name_prefix;
+
+pub const PASSWORD_HASH: &str = "test-password-hash";
+
+struct TestServer {
+    host: String,
+    handle: Handle,
+}
+
+static TEST_SERVER: OnceLock<TestServer> = OnceLock::new();
+
+fn server() -> &'static TestServer {
+    TEST_SERVER.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        //服务端使用独立的运行时，避免内存数据库的唯一连接随某个测试的运行时一起被关闭
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(async move {
+                let config = Config {
+                    database: DatabaseConfig {
+                        url: String::from("sqlite::memory:"),
+                        auto_migrate: true,
+                    },
+                    //大部分测试都从同一个地址注册大量用户，限流由 rate_limit.rs 单独测试
+                    rate_limit: RateLimitConfig {
+                        enabled: false,
+                        ..Default::default()
+                    },
+                    mail: MailConfig {
+                        transport: MailTransport::File,
+                        dir: mail_dir(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                };
+                GlobalDatabase::init_database(config.database.url.as_str(), true)
+                    .await
+                    .unwrap();
+
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                let host = format!("http://{}/", listener.local_addr().unwrap());
+                sender.send(TestServer { host, handle: Handle::current() }).unwrap();
+
+                serve(listener, config).await;
+            });
+        });
+        receiver.recv().unwrap()
+    })
+}
+
+async fn serve(listener: TcpListener, config: Config) {
+    let service = app(&config).into_make_service_with_connect_info::<SocketAddr>();
+    axum::serve(listener, service).await.unwrap();
+}
+
+/// 使用另一份配置在同一个数据库上再启动一个服务，返回它的地址
+pub async fn spawn_app(config: Config) -> String {
+    server()
+        .handle
+        .spawn(async move {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let host = format!("http://{}/", listener.local_addr().unwrap());
+            tokio::spawn(serve(listener, config));
+            host
+        })
+        .await
+        .unwrap()
+}
+
+/// 测试服务端发出的邮件写入这个目录
+pub fn mail_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("senyoshu-test-mail-{}", std::process::id()))
+}
+
+/// 最近一封发给 `to` 的邮件，没有时返回 None
+pub fn read_mail(to: &str) -> Option<String> {
+    let prefix = format!("{}-", file_name_prefix(to));
+    std::fs::read_dir(mail_dir())
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|name| {
+            //文件名是 `{prefix}-{millis}-{sequence}.txt`
+            let order = name.strip_prefix(prefix.as_str())?.strip_suffix(".txt")?;
+            let (millis, sequence) = order.split_once('-')?;
+            Some(((millis.parse::<i64>().ok()?, sequence.parse::<u64>().ok()?), name))
+        })
+        .max()
+        .and_then(|(_, name)| std::fs::read_to_string(mail_dir().join(name)).ok())
+}
+
+pub fn host() -> &'static str {
+    server().host.as_str()
+}
+
+/// 在服务端的运行时中直接操作数据库，用于准备数据
+pub async fn with_db<F, Fut, T>(f: F) -> T
+    where
+        F: FnOnce(&'static DatabaseConnection) -> Fut + Send + 'static,
+        Fut: Future<Output=T> + Send + 'static,
+        T: Send + 'static,
+{
+    let handle = &server().handle;
+    let db = GlobalDatabase::get().unwrap();
+    handle.spawn(f(db)).await.unwrap()
+}
+
+/// 注册并登录，用户名只能包含小写字母和数字，且在同一个测试文件中需要唯一
+pub async fn login(username: &str) -> Token {
+    let host = host();
+    let request = (username.to_string(), PASSWORD_HASH.to_string());
+    REGISTER_API.call_with_host(host, &request).await.unwrap();
+    login_again(username, "test").await
+}
+
+/// 已注册的用户在另一个设备上登录
+pub async fn login_again(username: &str, device: &str) -> Token {
+    let request = (username.to_string(), PASSWORD_HASH.to_string(), device.to_string());
+    LOGIN_API.call_with_host(host(), &request).await.unwrap()
+}
+
+pub async fn login_content_maintainer(username: &str) -> Token {
+    let token = login(username).await;
+    let uid = token.uid;
+    with_db(move |db| async move {
+        account::Entity::update_many()
+            .filter(account::Column::Uid.eq(uid))
+            .col_expr(account::Column::ContentMaintainer, Expr::value(true))
+            .exec(db)
+            .await
+            .unwrap();
+    })
+        .await;
+    token
+}
+
+pub async fn login_admin(username: &str) -> Token {
+    let token = login(username).await;
+    let username = username.to_string();
+    with_db(move |db| async move {
+        assert!(account::set_admin(username.as_str(), true, db).await.unwrap());
+    })
+        .await;
+    token
+}
+
+pub fn word_define(detailed: &str) -> WordDefine {
+    WordDefine {
+        detailed: detailed.to_string(),
+        ..WordDefine::template()
+    }
+}
+
+pub async fn seed_word(word_define: WordDefine) -> WordIdentity {
+    with_db(move |db| async move {
+        words::ActiveModel {
+            word_define: Set(Some(word_define)),
+            ..Default::default()
+        }
+            .insert(db)
+            .await
+            .unwrap()
+            .wid
+    })
+        .await
+}
+
+/// 同步 `since` 之后的所有页
+pub async fn sync_dic(since: Option<DateTime<FixedOffset>>) -> HashMap<WordIdentity, Option<WordDefine>> {
+    let mut request = SyncDicRequest { since, ..Default::default() };
+    let mut dic = HashMap::new();
+    loop {
+        let page = SYNC_DIC_API.call_with_host(host(), &request).await.unwrap();
+        dic.extend(page.words);
+        match page.next {
+            Some(next) => request.cursor = Some(next),
+            None => return dic,
+        }
+    }
+}
+
+/// 某个用户对某个词最近一次提交的修改请求
+pub async fn latest_request(author: i64, wid: WordIdentity) -> (i64, State) {
+    with_db(move |db| async move {
+        let row = word_history::Entity::find()
+            .filter(word_history::Column::Author.eq(author))
+            .filter(word_history::Column::Wid.eq(wid))
+            .order_by_desc(word_history::Column::Pid)
+            .one(db)
+            .await
+            .unwrap()
+            .unwrap();
+        (row.pid, row.state)
+    })
+        .await
+}
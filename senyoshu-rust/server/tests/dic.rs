@@ -0,0 +1,151 @@
+use chrono::{Duration, Utc};
+
+use senyoshu_common::types::api::dic::{
+    GET_MY_SUBMISSIONS_API, MAX_REVIEW_COMMENT_LEN, POST_WORD_API, SET_ADOPTED_API,
+};
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::state::State;
+use senyoshu_common::types::word::word_entry::WordEntry;
+
+use crate::common::{
+    host, latest_request, login, login_content_maintainer, seed_word, sync_dic, word_define,
+};
+
+mod common;
+
+#[tokio::test]
+async fn only_author_can_withdraw() {
+    let author = login("dicwithdrawauthor").await;
+    let other = login("dicwithdrawother").await;
+    let maintainer = login_content_maintainer("dicwithdrawmaintainer").await;
+    let wid = seed_word(word_define("withdraw")).await;
+
+    let entry = WordEntry { id: wid, word_define: word_define("withdraw changed") };
+    POST_WORD_API.call_with_host(host(), &author, &entry).await.unwrap();
+    let (pid, state) = latest_request(author.uid, wid).await;
+    assert_eq!(state, State::Pending);
+
+    let result = SET_ADOPTED_API.call_with_host(host(), &other, &(pid, State::Withdraw, None)).await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+    //审核者也不能撤回别人的请求
+    let result = SET_ADOPTED_API.call_with_host(host(), &maintainer, &(pid, State::Withdraw, None)).await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+
+    let author_uid = author.uid;
+    SET_ADOPTED_API.call_with_host(host(), &author, &(pid, State::Withdraw, None)).await.unwrap();
+    assert_eq!(latest_request(author_uid, wid).await, (pid, State::Withdraw));
+}
+
+#[tokio::test]
+async fn only_content_maintainer_can_pass() {
+    let author = login("dicpassauthor").await;
+    let maintainer = login_content_maintainer("dicpassmaintainer").await;
+    let wid = seed_word(word_define("pass")).await;
+
+    let changed = word_define("pass changed");
+    let entry = WordEntry { id: wid, word_define: changed.to_owned() };
+    POST_WORD_API.call_with_host(host(), &author, &entry).await.unwrap();
+    let (pid, _) = latest_request(author.uid, wid).await;
+
+    //作者不能通过自己的请求
+    let result = SET_ADOPTED_API.call_with_host(host(), &author, &(pid, State::Pass, None)).await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+
+    let before_pass = Utc::now() - Duration::minutes(1);
+    SET_ADOPTED_API
+        .call_with_host(host(), &maintainer, &(pid, State::Pass, None))
+        .await
+        .unwrap();
+    assert_eq!(latest_request(author.uid, wid).await, (pid, State::Pass));
+
+    //已经处理过的请求不能再次处理
+    let result = SET_ADOPTED_API.call_with_host(host(), &maintainer, &(pid, State::Cancel, None)).await;
+    assert_eq!(result, Err(Error::Conflict));
+
+    let dic = sync_dic(Some(before_pass.into())).await;
+    assert_eq!(dic.get(&wid), Some(&Some(changed)));
+}
+
+#[tokio::test]
+async fn cancel_does_not_change_word() {
+    let author = login("diccancelauthor").await;
+    let maintainer = login_content_maintainer("diccancelmaintainer").await;
+    let origin = word_define("cancel");
+    let wid = seed_word(origin.to_owned()).await;
+
+    let entry = WordEntry { id: wid, word_define: word_define("cancel changed") };
+    POST_WORD_API.call_with_host(host(), &author, &entry).await.unwrap();
+    let (pid, _) = latest_request(author.uid, wid).await;
+
+    SET_ADOPTED_API.call_with_host(host(), &maintainer, &(pid, State::Cancel, None)).await.unwrap();
+    assert_eq!(latest_request(author.uid, wid).await, (pid, State::Cancel));
+
+    let dic = sync_dic(None).await;
+    assert_eq!(dic.get(&wid), Some(&Some(origin)));
+}
+
+#[tokio::test]
+async fn set_adopted_rejects_pending_and_missing_request() {
+    let maintainer = login_content_maintainer("dicadoptedmaintainer").await;
+
+    let result = SET_ADOPTED_API.call_with_host(host(), &maintainer, &(1, State::Pending, None)).await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+
+    let result = SET_ADOPTED_API.call_with_host(host(), &maintainer, &(i64::MAX, State::Pass, None)).await;
+    assert_eq!(result, Err(Error::NotFound));
+}
+
+#[tokio::test]
+async fn review_comment_is_visible_to_author() {
+    let author = login("dicreviewauthor").await;
+    let maintainer = login_content_maintainer("dicreviewmaintainer").await;
+    let wid = seed_word(word_define("review")).await;
+
+    let entry = WordEntry { id: wid, word_define: word_define("review changed") };
+    POST_WORD_API.call_with_host(host(), &author, &entry).await.unwrap();
+    let (pid, _) = latest_request(author.uid, wid).await;
+
+    let submissions = GET_MY_SUBMISSIONS_API.call_with_host(host(), &author, &()).await.unwrap();
+    assert_eq!(submissions.len(), 1);
+    assert_eq!((submissions[0].pid, submissions[0].state, submissions[0].reviewer), (pid, State::Pending, None));
+
+    let too_long = "x".repeat(MAX_REVIEW_COMMENT_LEN + 1);
+    let result = SET_ADOPTED_API
+        .call_with_host(host(), &maintainer, &(pid, State::Cancel, Some(too_long)))
+        .await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+
+    let comment = Some(String::from("  the reading is wrong  "));
+    SET_ADOPTED_API.call_with_host(host(), &maintainer, &(pid, State::Cancel, comment)).await.unwrap();
+
+    let submissions = GET_MY_SUBMISSIONS_API.call_with_host(host(), &author, &()).await.unwrap();
+    let submission = &submissions[0];
+    assert_eq!(submission.wid, wid);
+    assert_eq!(submission.state, State::Cancel);
+    assert_eq!(submission.reviewer, Some(maintainer.uid));
+    assert!(submission.review_date.is_some());
+    assert_eq!(submission.review_comment.as_deref(), Some("the reading is wrong"));
+
+    //审核者自己没有提交过
+    let submissions = GET_MY_SUBMISSIONS_API.call_with_host(host(), &maintainer, &()).await.unwrap();
+    assert!(submissions.is_empty());
+}
+
+#[tokio::test]
+async fn withdraw_has_no_reviewer() {
+    let author = login("dicwithdrawnoreviewer").await;
+    let wid = seed_word(word_define("withdraw no reviewer")).await;
+
+    let entry = WordEntry { id: wid, word_define: word_define("withdraw no reviewer changed") };
+    POST_WORD_API.call_with_host(host(), &author, &entry).await.unwrap();
+    let (pid, _) = latest_request(author.uid, wid).await;
+    SET_ADOPTED_API
+        .call_with_host(host(), &author, &(pid, State::Withdraw, Some(String::from("typo"))))
+        .await
+        .unwrap();
+
+    let submissions = GET_MY_SUBMISSIONS_API.call_with_host(host(), &author, &()).await.unwrap();
+    assert_eq!(submissions[0].state, State::Withdraw);
+    assert_eq!(submissions[0].reviewer, None);
+    assert_eq!(submissions[0].review_comment, None);
+}
@@ -0,0 +1,83 @@
+use reqwest::header::AUTHORIZATION;
+use reqwest::StatusCode;
+
+use senyoshu_common::types::api::dic::GET_WORD_BY_PID_API;
+use senyoshu_server::config::{Config, MetricsConfig, RateLimitConfig};
+
+use crate::common::{host, login, spawn_app};
+
+mod common;
+
+async fn get(url: String) -> (StatusCode, String) {
+    let response = reqwest::get(url).await.unwrap();
+    (response.status(), response.text().await.unwrap())
+}
+
+#[tokio::test]
+async fn health_and_ready() {
+    let (status, _) = get(format!("{}healthz", host())).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = get(format!("{}readyz", host())).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+}
+
+#[tokio::test]
+async fn metrics_count_requests_by_api() {
+    let metrics_host = spawn_app(Config {
+        rate_limit: RateLimitConfig {
+            enabled: false,
+            ..Default::default()
+        },
+        metrics: MetricsConfig {
+            enabled: true,
+            token: String::new(),
+        },
+        ..Default::default()
+    })
+        .await;
+    login("metricsuser").await;
+    let _ = GET_WORD_BY_PID_API.call_with_host(host(), &i64::MAX).await;
+
+    let (status, body) = get(format!("{metrics_host}metrics")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains(r#"senyoshu_http_requests_total{api="login",status="200"}"#), "{body}");
+    assert!(body.contains(r#"senyoshu_http_request_duration_seconds_count{api="get_word_by_pid"}"#), "{body}");
+    assert!(body.contains("senyoshu_active_sessions"), "{body}");
+    assert!(body.contains("senyoshu_pending_change_requests"), "{body}");
+}
+
+#[tokio::test]
+async fn metrics_require_token() {
+    let host = spawn_app(Config {
+        rate_limit: RateLimitConfig {
+            enabled: false,
+            ..Default::default()
+        },
+        metrics: MetricsConfig {
+            enabled: true,
+            token: String::from("scrape-token"),
+        },
+        ..Default::default()
+    })
+        .await;
+    let url = format!("{host}metrics");
+
+    let (status, _) = get(url.to_owned()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let response = reqwest::Client::new()
+        .get(url)
+        .header(AUTHORIZATION, "Bearer scrape-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn metrics_are_disabled_by_default() {
+    let host = spawn_app(Config::default()).await;
+    //未匹配的路径回退到静态文件
+    let (status, body) = get(format!("{host}metrics")).await;
+    assert!(!body.contains("senyoshu_http_requests_total"), "{status} {body}");
+}
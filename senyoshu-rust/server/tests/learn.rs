@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+
+use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::api::learn::{GET_RECORD_API, GetRecordRequest, POST_LEARN_RECORD_API};
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::learn::knowledge::{Knowledge, KnowledgeType};
+use senyoshu_common::types::learn::learn_knowledge_history::{LearnKnowledgeHistory, OperateRecord, OperateType};
+use senyoshu_common::types::learn::LearnHistoryMap;
+use senyoshu_common::util::time::UtcTimeStamp;
+
+use crate::common::{host, login};
+
+mod common;
+
+fn kanji(key: &str) -> Knowledge {
+    Knowledge {
+        knowledge_type: KnowledgeType::Kanji,
+        key: key.to_string(),
+    }
+}
+
+fn record(operate_type: OperateType, operate_time: i64) -> OperateRecord {
+    OperateRecord {
+        operate_type,
+        operate_time: UtcTimeStamp(operate_time),
+    }
+}
+
+fn history_map(knowledge: Knowledge, history: Vec<OperateRecord>) -> LearnHistoryMap {
+    LearnHistoryMap::new(HashMap::from([(
+        knowledge,
+        LearnKnowledgeHistory {
+            history,
+            freeze_time: None,
+        },
+    )]))
+}
+
+/// 下载到最后一页，返回全部记录和高水位
+async fn get_records(token: &Token, mut request: GetRecordRequest) -> (HashMap<Knowledge, LearnKnowledgeHistory>, DateTime<FixedOffset>) {
+    let mut records = HashMap::new();
+    loop {
+        let page = GET_RECORD_API.call_with_host(host(), token, &request).await.unwrap();
+        records.extend(page.records);
+        match page.next {
+            Some(next) => request.cursor = Some(next),
+            None => return (records, page.watermark),
+        }
+    }
+}
+
+async fn get_all_records(token: &Token) -> HashMap<Knowledge, LearnKnowledgeHistory> {
+    get_records(token, GetRecordRequest::default()).await.0
+}
+
+#[tokio::test]
+async fn post_record_merges_history() {
+    let token = login("learnmerge").await;
+    let before_post = Utc::now() - Duration::minutes(1);
+
+    let first = history_map(kanji("日"), Vec::from([record(OperateType::Seen, 1000), record(OperateType::Forget, 2000)]));
+    POST_LEARN_RECORD_API.call_with_host(host(), &token, &first).await.unwrap();
+    //重复的记录会被去重
+    let second = history_map(kanji("日"), Vec::from([record(OperateType::Forget, 2000), record(OperateType::Remember, 3000)]));
+    POST_LEARN_RECORD_API.call_with_host(host(), &token, &second).await.unwrap();
+
+    let request = GetRecordRequest {
+        since: Some(before_post.into()),
+        ..Default::default()
+    };
+    let (records, _) = get_records(&token, request).await;
+    assert_eq!(records.len(), 1);
+    assert_eq!(
+        records[&kanji("日")].history,
+        Vec::from([
+            record(OperateType::Seen, 1000),
+            record(OperateType::Forget, 2000),
+            record(OperateType::Remember, 3000),
+        ])
+    );
+}
+
+#[tokio::test]
+async fn records_are_isolated_between_users() {
+    let alice = login("learnalice").await;
+    let bob = login("learnbob").await;
+
+    let map = history_map(kanji("月"), Vec::from([record(OperateType::Seen, 1000)]));
+    POST_LEARN_RECORD_API.call_with_host(host(), &alice, &map).await.unwrap();
+
+    assert_eq!(get_all_records(&alice).await.len(), 1);
+    assert!(get_all_records(&bob).await.is_empty());
+}
+
+#[tokio::test]
+async fn invalid_token_is_not_auth() {
+    let token = login("learninvalidtoken").await;
+    let token = Token {
+        uid: token.uid,
+        token: String::from("invalid"),
+    };
+
+    let result = GET_RECORD_API.call_with_host(host(), &token, &GetRecordRequest::default()).await;
+    assert_eq!(result.map(|it| it.records.len()), Err(Error::NotAuth));
+
+    let map = history_map(kanji("火"), Vec::from([record(OperateType::Seen, 1000)]));
+    let result = POST_LEARN_RECORD_API.call_with_host(host(), &token, &map).await;
+    assert_eq!(result, Err(Error::NotAuth));
+}
+
+#[tokio::test]
+async fn post_record_upserts_large_batch() {
+    let token = login("learnbatch").await;
+    let key = |i: usize| format!("k{i}");
+
+    let first = (0..10)
+        .map(|i| (kanji(key(i).as_str()), record(OperateType::Seen, 1000)))
+        .map(|(k, op)| (k, LearnKnowledgeHistory { history: Vec::from([op]), freeze_time: None }))
+        .collect::<HashMap<_, _>>();
+    POST_LEARN_RECORD_API.call_with_host(host(), &token, &LearnHistoryMap::new(first)).await.unwrap();
+
+    //超过一批的数量，同时包含已有和新的知识点
+    let second = (0..2500)
+        .map(|i| (kanji(key(i).as_str()), record(OperateType::Remember, 2000)))
+        .map(|(k, op)| (k, LearnKnowledgeHistory { history: Vec::from([op]), freeze_time: None }))
+        .collect::<HashMap<_, _>>();
+    POST_LEARN_RECORD_API.call_with_host(host(), &token, &LearnHistoryMap::new(second)).await.unwrap();
+
+    let records = get_all_records(&token).await;
+    assert_eq!(records.len(), 2500);
+    assert_eq!(
+        records[&kanji("k3")].history,
+        Vec::from([record(OperateType::Seen, 1000), record(OperateType::Remember, 2000)])
+    );
+    assert_eq!(records[&kanji("k2499")].history, Vec::from([record(OperateType::Remember, 2000)]));
+}
+
+#[tokio::test]
+async fn later_operate_clears_freeze_time() {
+    let token = login("learnfreeze").await;
+    let frozen = |freeze_time: Option<i64>, history: Vec<OperateRecord>| {
+        LearnHistoryMap::new(HashMap::from([(
+            kanji("水"),
+            LearnKnowledgeHistory {
+                history,
+                freeze_time: freeze_time.map(|millis| chrono::DateTime::from_timestamp_millis(millis).unwrap().fixed_offset()),
+            },
+        )]))
+    };
+    let freeze_time = |records: HashMap<Knowledge, LearnKnowledgeHistory>| records[&kanji("水")].freeze_time.map(|it| it.timestamp_millis());
+
+    //新的知识点不接受冻结时间
+    POST_LEARN_RECORD_API.call_with_host(host(), &token, &frozen(Some(5000), Vec::from([record(OperateType::Seen, 1000)]))).await.unwrap();
+    let records = get_all_records(&token).await;
+    assert_eq!(freeze_time(records), None);
+
+    POST_LEARN_RECORD_API.call_with_host(host(), &token, &frozen(Some(5000), Vec::new())).await.unwrap();
+    let records = get_all_records(&token).await;
+    assert_eq!(freeze_time(records), Some(5000));
+
+    //冻结之后又有新的操作
+    POST_LEARN_RECORD_API.call_with_host(host(), &token, &frozen(Some(5000), Vec::from([record(OperateType::Forget, 6000)]))).await.unwrap();
+    let records = get_all_records(&token).await;
+    assert_eq!(freeze_time(records), None);
+}
+
+#[tokio::test]
+async fn get_record_pages_up_to_watermark() {
+    let token = login("learnpage").await;
+    let map = (0..25)
+        .map(|i| (kanji(format!("p{i}").as_str()), LearnKnowledgeHistory { history: Vec::from([record(OperateType::Seen, 1000)]), freeze_time: None }))
+        .collect::<HashMap<_, _>>();
+    POST_LEARN_RECORD_API.call_with_host(host(), &token, &LearnHistoryMap::new(map)).await.unwrap();
+
+    let mut request = GetRecordRequest { limit: 10, ..Default::default() };
+    let first = GET_RECORD_API.call_with_host(host(), &token, &request).await.unwrap();
+    assert_eq!(first.records.len(), 10);
+    let watermark = first.watermark;
+
+    //高水位之后的修改留到下一次同步
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    POST_LEARN_RECORD_API.call_with_host(host(), &token, &history_map(kanji("later"), Vec::from([record(OperateType::Seen, 2000)]))).await.unwrap();
+
+    request.cursor = first.next;
+    let (rest, rest_watermark) = get_records(&token, request).await;
+    assert_eq!(rest_watermark, watermark);
+    assert_eq!(rest.len(), 15);
+    assert!(first.records.iter().all(|(knowledge, _)| !rest.contains_key(knowledge)));
+    assert!(!rest.contains_key(&kanji("later")));
+
+    let (next_sync, _) = get_records(&token, GetRecordRequest { since: Some(watermark), ..Default::default() }).await;
+    assert!(next_sync.contains_key(&kanji("later")));
+}
+
+#[tokio::test]
+async fn get_record_filters_knowledge_type() {
+    let token = login("learnfilter").await;
+    let kana = Knowledge {
+        knowledge_type: KnowledgeType::Kana,
+        key: String::from("あ"),
+    };
+    let map = [kanji("木"), kana.to_owned()]
+        .into_iter()
+        .map(|k| (k, LearnKnowledgeHistory { history: Vec::from([record(OperateType::Seen, 1000)]), freeze_time: None }))
+        .collect::<HashMap<_, _>>();
+    POST_LEARN_RECORD_API.call_with_host(host(), &token, &LearnHistoryMap::new(map)).await.unwrap();
+
+    let request = GetRecordRequest {
+        knowledge_types: Vec::from([KnowledgeType::Kana]),
+        ..Default::default()
+    };
+    let (records, _) = get_records(&token, request).await;
+    assert_eq!(records.into_keys().collect::<Vec<_>>(), Vec::from([kana]));
+    assert_eq!(get_all_records(&token).await.len(), 2);
+}
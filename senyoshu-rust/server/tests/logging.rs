@@ -0,0 +1,89 @@
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use sea_orm::EntityTrait;
+use tracing::Level;
+
+use senyoshu_common::types::api::account::GET_SESSIONS_API;
+use senyoshu_server::config::{MailConfig, MailTransport};
+use senyoshu_server::database::account;
+use senyoshu_server::mail::{build_mailer, Mail};
+
+use crate::common::{host, login, with_db};
+
+mod common;
+
+#[tokio::test]
+async fn response_carries_request_id() {
+    let url = format!("{}healthz", host());
+    let response = reqwest::get(url.to_owned()).await.unwrap();
+    let generated = response.headers().get("x-request-id").unwrap().to_str().unwrap();
+    assert!(!generated.is_empty());
+
+    //反向代理传入的 ID 原样返回
+    let response = reqwest::Client::new()
+        .get(url)
+        .header("x-request-id", "from-proxy")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers().get("x-request-id").unwrap(), "from-proxy");
+}
+
+#[tokio::test]
+async fn debug_output_hides_secrets() {
+    let token = login("redactuser").await;
+    assert!(!format!("{token:?}").contains(token.token.as_str()));
+
+    let sessions = GET_SESSIONS_API.call_with_host(host(), &token, &()).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+
+    let uid = token.uid;
+    let model = with_db(move |db| async move {
+        account::Entity::find_by_id(uid).one(db).await.unwrap().unwrap()
+    })
+        .await;
+    let debug = format!("{model:?}");
+    assert!(!debug.contains(model.passwd_hash2.as_str()));
+    assert!(!debug.contains(token.token.as_str()));
+    assert!(debug.contains("redactuser"));
+}
+
+/// 收集日志输出
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn logged_mail_body_is_debug_only() {
+    let mailer = build_mailer(&MailConfig { transport: MailTransport::Log, ..Default::default() }).unwrap();
+    let mail = Mail {
+        to: String::from("logmail@example.com"),
+        subject: String::from("verification"),
+        body: String::from("verification code: 123456"),
+    };
+
+    for (level, contains_body) in [(Level::INFO, false), (Level::DEBUG, true)] {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(level)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+        mailer.send(mail.to_owned()).await.unwrap();
+
+        let output = String::from_utf8(captured.0.lock().unwrap().to_owned()).unwrap();
+        assert!(output.contains("logmail@example.com"));
+        assert_eq!(output.contains("123456"), contains_body);
+    }
+}
@@ -0,0 +1,174 @@
+use senyoshu_common::types::api::account::{
+    LOGIN_API, REQUEST_EMAIL_CODE_API, REQUEST_PASSWD_RESET_API, RESET_PASSWD_API, Token,
+    UPDATE_USER_STATE_API, UserState, VERIFY_EMAIL_API,
+};
+use senyoshu_common::types::error::Error;
+
+use crate::common::{host, login, mail_dir, read_mail};
+
+mod common;
+
+/// 邮件中冒号后面的验证码或 token
+fn find_secret(mail: &str, label: &str) -> String {
+    mail.lines()
+        .find_map(|line| line.split_once(label)?.1.split_whitespace().next().map(str::to_string))
+        .unwrap()
+}
+
+async fn bind_e_mail(token: &Token, e_mail: &str) {
+    REQUEST_EMAIL_CODE_API
+        .call_with_host(host(), token, &e_mail.to_string())
+        .await
+        .unwrap();
+    let code = find_secret(read_mail(e_mail).unwrap().as_str(), "verification code:");
+    VERIFY_EMAIL_API
+        .call_with_host(host(), token, &(e_mail.to_string(), code))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn e_mail_is_bound_after_verify() {
+    let token = login("mailbind").await;
+    let e_mail = "mailbind@example.com";
+
+    REQUEST_EMAIL_CODE_API
+        .call_with_host(host(), &token, &String::from("MailBind@Example.com"))
+        .await
+        .unwrap();
+    let code = find_secret(read_mail(e_mail).unwrap().as_str(), "verification code:");
+    assert_eq!(code.len(), 6);
+
+    let wrong = if code == "000000" { "000001" } else { "000000" };
+    let result = VERIFY_EMAIL_API
+        .call_with_host(host(), &token, &(e_mail.to_string(), wrong.to_string()))
+        .await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+
+    VERIFY_EMAIL_API
+        .call_with_host(host(), &token, &(e_mail.to_string(), code.to_owned()))
+        .await
+        .unwrap();
+    let Ok(UserState::Valid(user_info)) = UPDATE_USER_STATE_API.call_with_host(host(), &token, &()).await else {
+        panic!("token should be valid")
+    };
+    assert_eq!(user_info.e_mail.as_deref(), Some(e_mail));
+
+    //验证码只能使用一次
+    let result = VERIFY_EMAIL_API
+        .call_with_host(host(), &token, &(e_mail.to_string(), code))
+        .await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+}
+
+#[tokio::test]
+async fn illegal_or_bound_e_mail_is_rejected() {
+    let token = login("mailillegal").await;
+    let result = REQUEST_EMAIL_CODE_API
+        .call_with_host(host(), &token, &String::from("not an e-mail"))
+        .await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+
+    let owner = login("mailowner").await;
+    bind_e_mail(&owner, "mailowner@example.com").await;
+    let result = REQUEST_EMAIL_CODE_API
+        .call_with_host(host(), &token, &String::from("mailowner@example.com"))
+        .await;
+    assert_eq!(result, Err(Error::Conflict));
+}
+
+#[tokio::test]
+async fn recipient_can_not_escape_mail_dir() {
+    let token = login("mailescape").await;
+    let escape = format!("senyoshu-mail-escape-{}", std::process::id());
+    let e_mail = format!("../{escape}/a@example.com");
+    REQUEST_EMAIL_CODE_API.call_with_host(host(), &token, &e_mail).await.unwrap();
+
+    //邮件仍然写在目录中，文件名中的 `/` 被替换
+    assert!(read_mail(e_mail.as_str()).is_some());
+    assert!(!mail_dir().join("..").join(escape).exists());
+}
+
+#[tokio::test]
+async fn code_can_not_be_resent_immediately() {
+    let token = login("mailresend").await;
+    let e_mail = String::from("mailresend@example.com");
+    REQUEST_EMAIL_CODE_API.call_with_host(host(), &token, &e_mail).await.unwrap();
+    let result = REQUEST_EMAIL_CODE_API.call_with_host(host(), &token, &e_mail).await;
+    assert!(matches!(result, Err(Error::RateLimited { retry_after_secs }) if retry_after_secs > 0));
+}
+
+#[tokio::test]
+async fn code_is_invalid_after_too_many_attempts() {
+    let token = login("mailattempts").await;
+    let e_mail = "mailattempts@example.com";
+    REQUEST_EMAIL_CODE_API
+        .call_with_host(host(), &token, &e_mail.to_string())
+        .await
+        .unwrap();
+    let code = find_secret(read_mail(e_mail).unwrap().as_str(), "verification code:");
+    let wrong = if code == "000000" { "000001" } else { "000000" };
+
+    for _ in 0..5 {
+        let result = VERIFY_EMAIL_API
+            .call_with_host(host(), &token, &(e_mail.to_string(), wrong.to_string()))
+            .await;
+        assert!(matches!(result, Err(Error::ValidationFailed(_))));
+    }
+    let result = VERIFY_EMAIL_API
+        .call_with_host(host(), &token, &(e_mail.to_string(), code))
+        .await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+}
+
+#[tokio::test]
+async fn passwd_is_reset_by_e_mail() {
+    let token = login("mailreset").await;
+    let e_mail = "mailreset@example.com";
+    bind_e_mail(&token, e_mail).await;
+
+    REQUEST_PASSWD_RESET_API
+        .call_with_host(host(), &e_mail.to_string())
+        .await
+        .unwrap();
+    let reset_token = find_secret(read_mail(e_mail).unwrap().as_str(), "token:");
+    RESET_PASSWD_API
+        .call_with_host(host(), &(reset_token.to_owned(), String::from("new-password-hash")))
+        .await
+        .unwrap();
+
+    //所有 session 都已失效
+    let state = UPDATE_USER_STATE_API.call_with_host(host(), &token, &()).await.unwrap();
+    assert_eq!(state, UserState::TokenRevoked);
+
+    let old = (String::from("mailreset"), String::from("test-password-hash"), String::from("test"));
+    assert_eq!(LOGIN_API.call_with_host(host(), &old).await.map(|_| ()), Err(Error::NotAuth));
+    let new = (String::from("mailreset"), String::from("new-password-hash"), String::from("test"));
+    LOGIN_API.call_with_host(host(), &new).await.unwrap();
+
+    //token 只能使用一次
+    let result = RESET_PASSWD_API
+        .call_with_host(host(), &(reset_token, String::from("other-password-hash")))
+        .await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+}
+
+#[tokio::test]
+async fn passwd_reset_does_not_reveal_accounts() {
+    let result = REQUEST_PASSWD_RESET_API
+        .call_with_host(host(), &String::from("mailnobody"))
+        .await;
+    assert_eq!(result, Ok(()));
+
+    //没有绑定邮箱的账号同样返回成功
+    login("mailnoaddress").await;
+    let result = REQUEST_PASSWD_RESET_API
+        .call_with_host(host(), &String::from("mailnoaddress"))
+        .await;
+    assert_eq!(result, Ok(()));
+
+    let result = RESET_PASSWD_API
+        .call_with_host(host(), &(String::from("not-a-token"), String::from("new-password-hash")))
+        .await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use senyoshu_common::types::api::dic::{
+    DELETE_WORD_API, GET_MODERATION_LOG_API, ModerationLogQuery, POST_WORD_API, SET_ADOPTED_API,
+    UPDATE_MANY_API,
+};
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::moderation::ModerationAction;
+use senyoshu_common::types::state::State;
+use senyoshu_common::types::word::word_entry::WordEntry;
+
+use crate::common::{
+    host, latest_request, login, login_content_maintainer, seed_word, word_define,
+};
+
+mod common;
+
+#[tokio::test]
+async fn moderation_log_records_decisions() {
+    let author = login("modlogauthor").await;
+    let maintainer = login_content_maintainer("modlogmaintainer").await;
+    let wid = seed_word(word_define("moderation")).await;
+
+    let entry = WordEntry { id: wid, word_define: word_define("moderation pass") };
+    POST_WORD_API.call_with_host(host(), &author, &entry).await.unwrap();
+    let (passed, _) = latest_request(author.uid, wid).await;
+    SET_ADOPTED_API.call_with_host(host(), &maintainer, &(passed, State::Pass, Some(String::from("looks good")))).await.unwrap();
+
+    let entry = WordEntry { id: wid, word_define: word_define("moderation withdraw") };
+    POST_WORD_API.call_with_host(host(), &author, &entry).await.unwrap();
+    let (withdrawn, _) = latest_request(author.uid, wid).await;
+    SET_ADOPTED_API.call_with_host(host(), &author, &(withdrawn, State::Withdraw, None)).await.unwrap();
+
+    UPDATE_MANY_API
+        .call_with_host(host(), &maintainer, &HashMap::from([(wid, word_define("moderation bulk"))]))
+        .await
+        .unwrap();
+    DELETE_WORD_API
+        .call_with_host(host(), &maintainer, &(wid.0, Some(String::from(" duplicate "))))
+        .await
+        .unwrap();
+
+    let query = ModerationLogQuery { wid: Some(wid), ..Default::default() };
+    let logs = GET_MODERATION_LOG_API.call_with_host(host(), &maintainer, &query).await.unwrap();
+    let actions = logs.iter().map(|it| (it.action, it.actor, it.pid)).collect::<Vec<_>>();
+    let bulk_pid = logs[1].pid;
+    //最新的在前
+    assert_eq!(
+        actions,
+        Vec::from([
+            (ModerationAction::Delete, maintainer.uid, None),
+            (ModerationAction::UpdateMany, maintainer.uid, bulk_pid),
+            (ModerationAction::Withdraw, author.uid, Some(withdrawn)),
+            (ModerationAction::Pass, maintainer.uid, Some(passed)),
+        ])
+    );
+    assert!(bulk_pid.is_some());
+    assert_eq!(logs[0].actor_name, "modlogmaintainer");
+    assert_eq!(logs[2].actor_name, "modlogauthor");
+    assert_eq!(logs[0].comment.as_deref(), Some("duplicate"));
+    assert_eq!(logs[3].comment.as_deref(), Some("looks good"));
+}
+
+#[tokio::test]
+async fn moderation_log_pages_and_filters() {
+    let maintainer = login_content_maintainer("modlogpager").await;
+    let mut wids = Vec::new();
+    for index in 0..3 {
+        let wid = seed_word(word_define(format!("moderation page {index}").as_str())).await;
+        DELETE_WORD_API.call_with_host(host(), &maintainer, &(wid.0, None)).await.unwrap();
+        wids.push(wid);
+    }
+
+    let query = ModerationLogQuery {
+        actor: Some(maintainer.uid),
+        action: Some(ModerationAction::Delete),
+        limit: 2,
+        ..Default::default()
+    };
+    let first = GET_MODERATION_LOG_API.call_with_host(host(), &maintainer, &query).await.unwrap();
+    assert_eq!(first.iter().map(|it| it.wid).collect::<Vec<_>>(), Vec::from([Some(wids[2]), Some(wids[1])]));
+
+    let query = ModerationLogQuery { before: Some(first[1].id), ..query };
+    let second = GET_MODERATION_LOG_API.call_with_host(host(), &maintainer, &query).await.unwrap();
+    assert_eq!(second.iter().map(|it| it.wid).collect::<Vec<_>>(), Vec::from([Some(wids[0])]));
+}
+
+#[tokio::test]
+async fn moderation_log_requires_content_maintainer() {
+    let user = login("modlogplainuser").await;
+    let result = GET_MODERATION_LOG_API
+        .call_with_host(host(), &user, &ModerationLogQuery::default())
+        .await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+}
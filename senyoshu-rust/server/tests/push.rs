@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use tokio_tungstenite::tungstenite::Message;
+
+use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::api::dic::UPDATE_MANY_API;
+use senyoshu_common::types::api::learn::POST_LEARN_RECORD_API;
+use senyoshu_common::types::api::push::{push_url, PUSH_CLOSE_NOT_AUTH, PushEvent};
+use senyoshu_common::types::learn::knowledge::{Knowledge, KnowledgeType};
+use senyoshu_common::types::learn::learn_knowledge_history::{LearnKnowledgeHistory, OperateRecord, OperateType};
+use senyoshu_common::types::learn::LearnHistoryMap;
+use senyoshu_common::util::time::UtcTimeStamp;
+
+use crate::common::{host, login, login_again, login_content_maintainer, seed_word, word_define};
+
+mod common;
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// 连接并发送 token，不等待服务端的响应
+async fn connect(credential: String) -> Socket {
+    let (mut socket, _) = connect_async(push_url(host())).await.unwrap();
+    socket.send(Message::Text(credential)).await.unwrap();
+    socket
+}
+
+/// 连接并等到服务端开始推送
+async fn subscribe(token: &Token) -> Socket {
+    let mut socket = connect(token.credential()).await;
+    assert!(matches!(next_event(&mut socket).await, PushEvent::Resync));
+    socket
+}
+
+async fn next_event(socket: &mut Socket) -> PushEvent {
+    loop {
+        let message = timeout(Duration::from_secs(5), socket.next()).await.unwrap().unwrap().unwrap();
+        if let Message::Text(text) = message {
+            return serde_json::from_str(text.as_str()).unwrap();
+        }
+    }
+}
+
+/// 跳过其他测试产生的词典通知
+async fn next_learn_event(socket: &mut Socket) -> Vec<(Knowledge, LearnKnowledgeHistory)> {
+    loop {
+        if let PushEvent::Learn(records) = next_event(socket).await {
+            return records;
+        }
+    }
+}
+
+fn history_map(key: &str, operate_time: i64) -> LearnHistoryMap {
+    let knowledge = Knowledge {
+        knowledge_type: KnowledgeType::Kanji,
+        key: key.to_string(),
+    };
+    let history = Vec::from([OperateRecord {
+        operate_type: OperateType::Seen,
+        operate_time: UtcTimeStamp(operate_time),
+    }]);
+    LearnHistoryMap::new(HashMap::from([(knowledge, LearnKnowledgeHistory { history, freeze_time: None })]))
+}
+
+#[tokio::test]
+async fn learn_records_are_pushed_to_other_sessions() {
+    login("pushlearn").await;
+    let pc = login_again("pushlearn", "pc").await;
+    let phone = login_again("pushlearn", "phone").await;
+    let mut pc_socket = subscribe(&pc).await;
+    let mut other_socket = subscribe(&login("pushlearnother").await).await;
+
+    POST_LEARN_RECORD_API.call_with_host(host(), &phone, &history_map("金", 1000)).await.unwrap();
+    POST_LEARN_RECORD_API.call_with_host(host(), &phone, &history_map("金", 2000)).await.unwrap();
+    assert_eq!(next_learn_event(&mut pc_socket).await[0].1.history.len(), 1);
+    //推送的是合并后的记录
+    assert_eq!(next_learn_event(&mut pc_socket).await[0].1.history.len(), 2);
+
+    //上传记录的 session 收不到自己的记录
+    let mut phone_socket = subscribe(&phone).await;
+    POST_LEARN_RECORD_API.call_with_host(host(), &phone, &history_map("土", 1000)).await.unwrap();
+    POST_LEARN_RECORD_API.call_with_host(host(), &pc, &history_map("木", 1000)).await.unwrap();
+    assert_eq!(next_learn_event(&mut phone_socket).await[0].0.key, "木");
+    assert_eq!(next_learn_event(&mut pc_socket).await[0].0.key, "土");
+
+    //其他用户收不到
+    assert!(timeout(Duration::from_millis(200), next_learn_event(&mut other_socket)).await.is_err());
+}
+
+#[tokio::test]
+async fn dic_changes_are_pushed_to_everyone() {
+    let maintainer = login_content_maintainer("pushdic").await;
+    let wid = seed_word(word_define("pushed before")).await;
+    let mut socket = subscribe(&login("pushdicreader").await).await;
+
+    let update = HashMap::from([(wid, word_define("pushed after"))]);
+    UPDATE_MANY_API.call_with_host(host(), &maintainer, &update).await.unwrap();
+    loop {
+        if let PushEvent::Dic(words) = next_event(&mut socket).await {
+            if let Some((_, word)) = words.into_iter().find(|(it, _)| *it == wid) {
+                assert_eq!(word.unwrap().detailed, "pushed after");
+                break;
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn invalid_token_is_closed() {
+    let token = login("pushinvalid").await;
+    let token = Token {
+        uid: token.uid,
+        token: String::from("invalid"),
+    };
+    for credential in [token.credential(), String::from("garbage")] {
+        let mut socket = connect(credential).await;
+        let message = timeout(Duration::from_secs(5), socket.next()).await.unwrap().unwrap().unwrap();
+        match message {
+            Message::Close(Some(frame)) => assert_eq!(u16::from(frame.code), PUSH_CLOSE_NOT_AUTH),
+            message => panic!("unexpected message: {message:?}"),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use senyoshu_common::types::api::account::{
+    LOGIN_API, REGISTER_API, REQUEST_PASSWD_RESET_API, UPDATE_PASSWD_API,
+};
+use senyoshu_common::types::error::Error;
+use senyoshu_server::config::{Config, Quota, RateLimitConfig};
+
+use crate::common::{login, PASSWORD_HASH, spawn_app};
+
+mod common;
+
+async fn limited_host(rate_limit: RateLimitConfig) -> String {
+    spawn_app(Config {
+        rate_limit,
+        ..Default::default()
+    })
+        .await
+}
+
+fn login_request(username: &str, passwd_hash: &str) -> (String, String, String) {
+    (username.to_string(), passwd_hash.to_string(), String::from("test"))
+}
+
+#[tokio::test]
+async fn register_is_limited_per_ip() {
+    let host = limited_host(RateLimitConfig {
+        register_per_ip: Quota { requests: 2, per_secs: 3600 },
+        ..Default::default()
+    })
+        .await;
+
+    for username in ["ratereg1", "ratereg2"] {
+        let request = (username.to_string(), PASSWORD_HASH.to_string());
+        REGISTER_API.call_with_host(host.as_str(), &request).await.unwrap();
+    }
+    let request = (String::from("ratereg3"), PASSWORD_HASH.to_string());
+    let result = REGISTER_API.call_with_host(host.as_str(), &request).await;
+    assert!(matches!(result, Err(Error::RateLimited { retry_after_secs }) if retry_after_secs > 3500));
+}
+
+#[tokio::test]
+async fn passwd_reset_mail_is_limited_per_ip() {
+    let host = limited_host(RateLimitConfig {
+        mail_per_ip: Quota { requests: 2, per_secs: 3600 },
+        ..Default::default()
+    })
+        .await;
+
+    for name in ["ratemail1", "ratemail2"] {
+        REQUEST_PASSWD_RESET_API.call_with_host(host.as_str(), &name.to_string()).await.unwrap();
+    }
+    let result = REQUEST_PASSWD_RESET_API
+        .call_with_host(host.as_str(), &String::from("ratemail3"))
+        .await;
+    assert!(matches!(result, Err(Error::RateLimited { .. })));
+}
+
+#[tokio::test]
+async fn login_is_limited_per_username() {
+    login("ratelogin").await;
+    login("rateloginother").await;
+    let host = limited_host(RateLimitConfig {
+        login_per_username: Quota { requests: 2, per_secs: 60 },
+        ..Default::default()
+    })
+        .await;
+
+    for _ in 0..2 {
+        LOGIN_API
+            .call_with_host(host.as_str(), &login_request("ratelogin", PASSWORD_HASH))
+            .await
+            .unwrap();
+    }
+    let result = LOGIN_API
+        .call_with_host(host.as_str(), &login_request("ratelogin", PASSWORD_HASH))
+        .await;
+    assert!(matches!(result, Err(Error::RateLimited { .. })));
+
+    //其他用户名不受影响
+    LOGIN_API
+        .call_with_host(host.as_str(), &login_request("rateloginother", PASSWORD_HASH))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn failed_logins_lock_username_exponentially() {
+    login("ratelock").await;
+    let host = limited_host(RateLimitConfig {
+        lockout_threshold: 2,
+        lockout_base_secs: 1,
+        ..Default::default()
+    })
+        .await;
+    let wrong = login_request("ratelock", "wrong");
+
+    let result = LOGIN_API.call_with_host(host.as_str(), &wrong).await;
+    assert_eq!(result, Err(Error::NotAuth));
+    let result = LOGIN_API.call_with_host(host.as_str(), &wrong).await;
+    assert_eq!(result, Err(Error::NotAuth));
+
+    //锁定期间正确的密码也会被拒绝
+    let result = LOGIN_API
+        .call_with_host(host.as_str(), &login_request("ratelock", PASSWORD_HASH))
+        .await;
+    assert_eq!(result, Err(Error::RateLimited { retry_after_secs: 1 }));
+
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    let result = LOGIN_API.call_with_host(host.as_str(), &wrong).await;
+    assert_eq!(result, Err(Error::NotAuth));
+    let result = LOGIN_API.call_with_host(host.as_str(), &wrong).await;
+    assert_eq!(result, Err(Error::RateLimited { retry_after_secs: 2 }));
+
+    //成功登录后清除失败记录
+    tokio::time::sleep(Duration::from_millis(2100)).await;
+    LOGIN_API
+        .call_with_host(host.as_str(), &login_request("ratelock", PASSWORD_HASH))
+        .await
+        .unwrap();
+    let result = LOGIN_API.call_with_host(host.as_str(), &wrong).await;
+    assert_eq!(result, Err(Error::NotAuth));
+}
+
+#[tokio::test]
+async fn failed_passwd_updates_share_login_lockout() {
+    login("ratepasswd").await;
+    let host = limited_host(RateLimitConfig {
+        lockout_threshold: 2,
+        lockout_base_secs: 60,
+        ..Default::default()
+    })
+        .await;
+    let update = |old: &str| (String::from("ratepasswd"), String::from("new"), old.to_string());
+
+    let result = UPDATE_PASSWD_API.call_with_host(host.as_str(), &update("wrong")).await;
+    assert_eq!(result, Err(Error::NotAuth));
+    let result = LOGIN_API
+        .call_with_host(host.as_str(), &login_request("ratepasswd", "wrong"))
+        .await;
+    assert_eq!(result, Err(Error::NotAuth));
+
+    //两个接口的失败一起计数
+    let result = UPDATE_PASSWD_API.call_with_host(host.as_str(), &update(PASSWORD_HASH)).await;
+    assert!(matches!(result, Err(Error::RateLimited { .. })));
+    let result = LOGIN_API
+        .call_with_host(host.as_str(), &login_request("ratepasswd", PASSWORD_HASH))
+        .await;
+    assert!(matches!(result, Err(Error::RateLimited { .. })));
+}
@@ -0,0 +1,135 @@
+use chrono::{Duration, Local};
+use futures_util::future::join_all;
+use sea_orm::{EntityTrait, IntoActiveModel, ActiveModelTrait, Set};
+
+use senyoshu_common::types::api::account::{GET_SESSIONS_API, REVOKE_OTHER_SESSIONS_API, REVOKE_SESSION_API, UPDATE_USER_STATE_API, UserState};
+use senyoshu_common::types::api::learn::{GET_RECORD_API, GetRecordRequest};
+use senyoshu_common::types::api::session::SessionVec;
+use senyoshu_common::types::error::Error;
+use senyoshu_server::database::account;
+
+use crate::common::{host, login, login_again, with_db};
+
+mod common;
+
+#[tokio::test]
+async fn sessions_are_listed_without_token() {
+    let phone = login("sessionlist").await;
+    let pc = login_again("sessionlist", "pc").await;
+
+    let sessions = GET_SESSIONS_API.call_with_host(host(), &pc, &()).await.unwrap();
+    assert_eq!(sessions.len(), 2);
+    let current = sessions.iter().find(|session| session.current).unwrap();
+    assert_eq!(current.device, "pc");
+    assert!(current.last_used.is_some());
+    assert!(sessions.iter().all(|session| session.id != phone.token && session.id != pc.token));
+}
+
+#[tokio::test]
+async fn revoked_session_can_not_be_used() {
+    let phone = login("sessionrevoke").await;
+    let pc = login_again("sessionrevoke", "pc").await;
+
+    let sessions = GET_SESSIONS_API.call_with_host(host(), &pc, &()).await.unwrap();
+    let phone_id = sessions.iter().find(|session| !session.current).unwrap().id.to_owned();
+    REVOKE_SESSION_API.call_with_host(host(), &pc, &phone_id.to_owned()).await.unwrap();
+
+    let state = UPDATE_USER_STATE_API.call_with_host(host(), &phone, &()).await.unwrap();
+    assert_eq!(state, UserState::TokenRevoked);
+    let result = GET_RECORD_API.call_with_host(host(), &phone, &GetRecordRequest::default()).await;
+    assert_eq!(result.map(|it| it.records.len()), Err(Error::NotAuth));
+
+    let result = REVOKE_SESSION_API.call_with_host(host(), &pc, &phone_id).await;
+    assert_eq!(result, Err(Error::NotFound));
+    assert!(matches!(
+        UPDATE_USER_STATE_API.call_with_host(host(), &pc, &()).await,
+        Ok(UserState::Valid(_))
+    ));
+}
+
+#[tokio::test]
+async fn revoke_other_sessions_keeps_current() {
+    let phone = login("sessionothers").await;
+    let tablet = login_again("sessionothers", "tablet").await;
+    let pc = login_again("sessionothers", "pc").await;
+
+    REVOKE_OTHER_SESSIONS_API.call_with_host(host(), &pc, &()).await.unwrap();
+
+    for token in [phone, tablet] {
+        let state = UPDATE_USER_STATE_API.call_with_host(host(), &token, &()).await.unwrap();
+        assert_eq!(state, UserState::TokenRevoked);
+    }
+    let sessions = GET_SESSIONS_API.call_with_host(host(), &pc, &()).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert!(sessions[0].current);
+}
+
+#[tokio::test]
+async fn expired_session_is_revoked() {
+    let token = login("sessionexpire").await;
+    let uid = token.uid;
+
+    with_db(move |db| async move {
+        let row = account::Entity::find_by_id(uid).one(db).await.unwrap().unwrap();
+        let mut sessions = row.sessions.to_owned().unwrap().0;
+        for session in sessions.iter_mut() {
+            session.expire_time = Some((Local::now() - Duration::minutes(1)).fixed_offset());
+        }
+        let mut row = row.into_active_model();
+        row.sessions = Set(Some(SessionVec(sessions)));
+        row.update(db).await.unwrap();
+    })
+        .await;
+
+    let state = UPDATE_USER_STATE_API.call_with_host(host(), &token, &()).await.unwrap();
+    assert_eq!(state, UserState::TokenRevoked);
+}
+
+#[tokio::test]
+async fn least_recently_used_session_is_evicted() {
+    let first = login("sessionevict").await;
+    for i in 0..8 {
+        login_again("sessionevict", format!("device{i}").as_str()).await;
+    }
+
+    let state = UPDATE_USER_STATE_API.call_with_host(host(), &first, &()).await.unwrap();
+    assert_eq!(state, UserState::TokenRevoked);
+}
+
+/// 让所有 session 都需要续期
+async fn age_sessions(uid: i64) {
+    with_db(move |db| async move {
+        let row = account::Entity::find_by_id(uid).one(db).await.unwrap().unwrap();
+        let mut sessions = row.sessions.to_owned().unwrap().0;
+        for session in sessions.iter_mut() {
+            session.last_used = Some((Local::now() - Duration::hours(2)).fixed_offset());
+        }
+        let mut row = row.into_active_model();
+        row.sessions = Set(Some(SessionVec(sessions)));
+        row.update(db).await.unwrap();
+    })
+        .await;
+}
+
+#[tokio::test]
+async fn refresh_does_not_restore_revoked_session() {
+    let pc = login("sessionrace").await;
+    for i in 0..10 {
+        let phone = login_again("sessionrace", format!("phone{i}").as_str()).await;
+        let sessions = GET_SESSIONS_API.call_with_host(host(), &pc, &()).await.unwrap();
+        let phone_id = sessions.iter().find(|session| !session.current).unwrap().id.to_owned();
+        age_sessions(pc.uid).await;
+
+        //多个续期与撤销同时进行
+        let refreshes = (0..8).map(|_| UPDATE_USER_STATE_API.call_with_host(host(), &pc, &()));
+        let (states, revoked) = tokio::join!(
+            join_all(refreshes),
+            REVOKE_SESSION_API.call_with_host(host(), &pc, &phone_id),
+        );
+        assert!(states.into_iter().all(|state| matches!(state, Ok(UserState::Valid(_)))));
+        revoked.unwrap();
+
+        let state = UPDATE_USER_STATE_API.call_with_host(host(), &phone, &()).await.unwrap();
+        assert_eq!(state, UserState::TokenRevoked);
+    }
+}
@@ -0,0 +1,93 @@
+use std::path::{Path, PathBuf};
+
+use reqwest::header::CACHE_CONTROL;
+use reqwest::StatusCode;
+
+use senyoshu_common::types::api::dic::{
+    DIC_SNAPSHOT_MANIFEST, DIC_SNAPSHOT_PATH, DicSnapshotManifest, get_dic_snapshot_manifest_with_host,
+    get_dic_snapshot_with_host,
+};
+use senyoshu_server::config::{Config, RateLimitConfig, SnapshotConfig};
+use senyoshu_server::snapshot::SnapshotJob;
+
+use crate::common::{seed_word, spawn_app, sync_dic, with_db, word_define};
+
+mod common;
+
+fn snapshot_config(dir: PathBuf) -> SnapshotConfig {
+    SnapshotConfig {
+        enabled: true,
+        dir,
+        keep: 2,
+        ..Default::default()
+    }
+}
+
+async fn spawn_snapshot_app(dir: PathBuf) -> String {
+    spawn_app(Config {
+        rate_limit: RateLimitConfig {
+            enabled: false,
+            ..Default::default()
+        },
+        snapshot: snapshot_config(dir),
+        ..Default::default()
+    })
+        .await
+}
+
+async fn build_snapshot(dir: PathBuf) -> DicSnapshotManifest {
+    let job = SnapshotJob::new(&snapshot_config(dir));
+    with_db(move |db| async move { job.run_once(db).await.unwrap() }).await
+}
+
+fn snapshot_files(dir: &Path) -> usize {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.ends_with(".cbor.lz4"))
+        .count()
+}
+
+#[tokio::test]
+async fn snapshot_is_served_with_manifest() {
+    let dir = std::env::temp_dir().join(format!("senyoshu-test-snapshot-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let host = spawn_snapshot_app(dir.to_owned()).await;
+    let url = |path: &str| format!("{}{DIC_SNAPSHOT_PATH}/{path}", host.trim_end_matches('/'));
+
+    //还没有生成快照
+    assert_eq!(get_dic_snapshot_manifest_with_host(host.as_str()).await.unwrap(), None);
+
+    let wid = seed_word(word_define("snapshot")).await;
+    let manifest = build_snapshot(dir.to_owned()).await;
+    assert_eq!(get_dic_snapshot_manifest_with_host(host.as_str()).await.unwrap(), Some(manifest.to_owned()));
+    let dic = get_dic_snapshot_with_host(host.as_str(), &manifest).await.unwrap();
+    assert_eq!(dic.len() as u64, manifest.words);
+    assert_eq!(dic.get(&wid), Some(&word_define("snapshot")));
+
+    let client = reqwest::Client::new();
+    let response = client.get(url(DIC_SNAPSHOT_MANIFEST)).send().await.unwrap();
+    assert_eq!(response.headers()[CACHE_CONTROL], "no-cache");
+    let response = client.get(url(manifest.file.as_str())).send().await.unwrap();
+    assert_eq!(response.headers()[CACHE_CONTROL], "public, max-age=31536000, immutable");
+    let response = client.get(url("dic-missing.cbor.lz4")).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(response.headers().get(CACHE_CONTROL).is_none());
+
+    //内容不变时文件也不变
+    let again = build_snapshot(dir.to_owned()).await;
+    assert_eq!(again.file, manifest.file);
+
+    //快照之后的修改通过同步词典获取
+    let later = seed_word(word_define("after snapshot")).await;
+    let delta = sync_dic(Some(manifest.watermark)).await;
+    assert!(delta.contains_key(&later));
+
+    //只保留最近的快照
+    build_snapshot(dir.to_owned()).await;
+    seed_word(word_define("third snapshot")).await;
+    let latest = build_snapshot(dir.to_owned()).await;
+    assert_eq!(snapshot_files(&dir), 2);
+    assert!(dir.join(latest.file).exists());
+    assert!(!dir.join(manifest.file).exists());
+}
@@ -0,0 +1,156 @@
+use reqwest::header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use reqwest::StatusCode;
+
+use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::api::sound::{
+    get_sound_with_host, REVIEW_SOUND_API, SEARCH_SOUNDS_API, sound_path, SoundKey, SoundQuery,
+    SoundUpload, UPLOAD_SOUND_API,
+};
+use senyoshu_common::types::error::Error;
+use senyoshu_common::types::sound::SoundMeta;
+
+use crate::common::{host, login, login_content_maintainer};
+
+mod common;
+
+/// 所有测试共用同一个数据库，每个测试使用不同的读音
+fn key(katakana: &str) -> SoundKey {
+    SoundKey {
+        katakana: katakana.to_string(),
+        tone: 1,
+        role: String::from("female"),
+    }
+}
+
+async fn upload(token: &Token, key: SoundKey, sound: &[u8]) -> Result<SoundMeta, Error> {
+    let upload = SoundUpload {
+        key,
+        content_type: String::from("audio/mpeg"),
+        sound: sound.to_vec(),
+    };
+    UPLOAD_SOUND_API.call_with_host(host(), token, &upload).await
+}
+
+fn url(path: &str) -> String {
+    format!("{}{path}", host().trim_end_matches('/'))
+}
+
+#[tokio::test]
+async fn sound_is_visible_after_review() {
+    let author = login_content_maintainer("soundauthor").await;
+    let reviewer = login_content_maintainer("soundreviewer").await;
+    let key = key("サクラ");
+
+    let meta = upload(&author, key.to_owned(), b"sakura").await.unwrap();
+    assert_eq!(meta.checked, None);
+    assert_eq!(meta.author, author.uid);
+
+    //审核之前其他人看不到
+    let any = get_sound_with_host(host(), sound_path("サクラ", 1).as_str()).await.unwrap();
+    assert_eq!(any, None);
+    let exact = get_sound_with_host(host(), key.path().as_str()).await.unwrap();
+    assert_eq!(exact, None);
+
+    //审核者可以试听，但不能被缓存
+    let response = reqwest::Client::new()
+        .get(url(key.path().as_str()))
+        .bearer_auth(reviewer.credential())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CACHE_CONTROL], "private, no-store");
+    assert_eq!(response.bytes().await.unwrap().as_ref(), b"sakura");
+
+    //作者不能审核自己的音频
+    let result = REVIEW_SOUND_API.call_with_host(host(), &author, &(key.to_owned(), true)).await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+    let meta = REVIEW_SOUND_API
+        .call_with_host(host(), &reviewer, &(key.to_owned(), true))
+        .await
+        .unwrap();
+    assert_eq!(meta.checked, Some(true));
+
+    let any = get_sound_with_host(host(), sound_path("サクラ", 1).as_str()).await.unwrap();
+    assert_eq!(any.as_deref(), Some(b"sakura".as_slice()));
+
+    //覆盖后需要重新审核
+    let meta = upload(&author, key.to_owned(), b"sakura2").await.unwrap();
+    assert_eq!(meta.checked, None);
+    let exact = get_sound_with_host(host(), key.path().as_str()).await.unwrap();
+    assert_eq!(exact, None);
+}
+
+#[tokio::test]
+async fn reviewed_sound_is_cacheable() {
+    let author = login_content_maintainer("soundcacheauthor").await;
+    let reviewer = login_content_maintainer("soundcachereviewer").await;
+    let key = key("ツキ");
+    upload(&author, key.to_owned(), b"tsuki").await.unwrap();
+    REVIEW_SOUND_API.call_with_host(host(), &reviewer, &(key.to_owned(), true)).await.unwrap();
+
+    let client = reqwest::Client::new();
+    let response = client.get(url(key.path().as_str())).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "audio/mpeg");
+    assert_eq!(response.headers()[CACHE_CONTROL], "public, max-age=86400");
+    let etag = response.headers()[ETAG].to_owned();
+
+    let response = client
+        .get(url(key.path().as_str()))
+        .header(IF_NONE_MATCH, etag.to_owned())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert!(response.bytes().await.unwrap().is_empty());
+
+    //音频变化后 ETag 也会变化
+    upload(&author, key.to_owned(), b"tsuki!").await.unwrap();
+    REVIEW_SOUND_API.call_with_host(host(), &reviewer, &(key.to_owned(), true)).await.unwrap();
+    let response = client
+        .get(url(key.path().as_str()))
+        .header(IF_NONE_MATCH, etag)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.bytes().await.unwrap().as_ref(), b"tsuki!");
+}
+
+#[tokio::test]
+async fn only_maintainer_can_upload_and_search() {
+    let user = login("soundnormaluser").await;
+    let maintainer = login_content_maintainer("soundsearcher").await;
+
+    let result = upload(&user, key("ハナ"), b"hana").await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+    let result = upload(&maintainer, key("ハナ"), &[]).await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+    let mut bad_role = key("ハナ");
+    bad_role.role = String::from("../female");
+    let result = upload(&maintainer, bad_role, b"hana").await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+    //不能作为响应头的类型
+    let bad_type = SoundUpload {
+        key: key("ハナ"),
+        content_type: String::from("audio/x\n"),
+        sound: b"hana".to_vec(),
+    };
+    let result = UPLOAD_SOUND_API.call_with_host(host(), &maintainer, &bad_type).await;
+    assert!(matches!(result, Err(Error::ValidationFailed(_))));
+
+    upload(&maintainer, key("ハナ"), b"hana").await.unwrap();
+    let query = SoundQuery {
+        katakana: Some(String::from("ハナ")),
+        unchecked_only: true,
+        offset: 0,
+        limit: 10,
+    };
+    let result = SEARCH_SOUNDS_API.call_with_host(host(), &user, &query).await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+    let sounds = SEARCH_SOUNDS_API.call_with_host(host(), &maintainer, &query).await.unwrap();
+    assert_eq!(sounds.len(), 1);
+    assert_eq!(sounds[0].role, "female");
+    assert_eq!(sounds[0].checked, None);
+}
@@ -0,0 +1,154 @@
+use futures_util::future::join_all;
+
+use senyoshu_common::types::api::account::Token;
+use senyoshu_common::types::api::admin::{
+    GET_SURF_CREDENTIALS_API, GET_SURF_SERVER_CONFIGS_API, ROTATE_SURF_CREDENTIALS_API,
+    SAVE_SURF_SERVER_API, SET_VIP_API, SurfServerConfig, VipChange,
+};
+use senyoshu_common::types::api::api::{GET_SURF_SERVERS_API, SurfServer};
+use senyoshu_common::types::error::Error;
+
+use crate::common::{host, login, login_admin};
+
+mod common;
+
+fn server_config(name: &str, capacity: u32) -> SurfServerConfig {
+    SurfServerConfig {
+        id: None,
+        name: name.to_string(),
+        region: String::from("jp"),
+        server: String::from("127.0.0.1"),
+        server_port: 8388,
+        method: String::from("aes-256-gcm"),
+        capacity,
+        enabled: true,
+        active_users: 0,
+    }
+}
+
+async fn login_vip(admin: &Token, username: &str) -> Token {
+    let token = login(username).await;
+    SET_VIP_API
+        .call_with_host(host(), admin, &(token.uid, VipChange::ExtendDays(30)))
+        .await
+        .unwrap();
+    token
+}
+
+/// 所有测试共用同一个数据库，只看本测试添加的服务器
+async fn servers_named(token: &Token, name: &str) -> Vec<SurfServer> {
+    GET_SURF_SERVERS_API
+        .call_with_host(host(), token, &())
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|it| it.name == name)
+        .collect()
+}
+
+#[tokio::test]
+async fn surf_servers_require_vip() {
+    let user = login("surfnotvip").await;
+    let result = GET_SURF_SERVERS_API.call_with_host(host(), &user, &()).await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+
+    let anonymous = Token { uid: user.uid, token: String::from("wrong") };
+    let result = GET_SURF_SERVERS_API.call_with_host(host(), &anonymous, &()).await;
+    assert_eq!(result, Err(Error::NotAuth));
+
+    let result = SAVE_SURF_SERVER_API.call_with_host(host(), &user, &server_config("surfdenied", 0)).await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+}
+
+#[tokio::test]
+async fn each_vip_gets_own_credential() {
+    let admin = login_admin("surfadmin").await;
+    let first = login_vip(&admin, "surffirst").await;
+    let second = login_vip(&admin, "surfsecond").await;
+    let third = login_vip(&admin, "surfthird").await;
+
+    //容量为 2，第三个用户拿不到这台服务器
+    let id = SAVE_SURF_SERVER_API
+        .call_with_host(host(), &admin, &server_config("surfshared", 2))
+        .await
+        .unwrap();
+    let first_servers = servers_named(&first, "surfshared").await;
+    assert_eq!(first_servers.len(), 1);
+    assert_eq!(first_servers[0].region, "jp");
+    assert!(first_servers[0].expire_time.is_some());
+    assert_eq!(servers_named(&first, "surfshared").await, first_servers);
+    let second_servers = servers_named(&second, "surfshared").await;
+    assert_ne!(second_servers[0].password, first_servers[0].password);
+    assert!(servers_named(&third, "surfshared").await.is_empty());
+
+    let credentials = GET_SURF_CREDENTIALS_API.call_with_host(host(), &admin, &id).await.unwrap();
+    assert_eq!(credentials.iter().map(|it| it.uid).collect::<Vec<_>>(), [first.uid, second.uid]);
+    let configs = GET_SURF_SERVER_CONFIGS_API.call_with_host(host(), &admin, &()).await.unwrap();
+    let config = configs.into_iter().find(|it| it.id == Some(id)).unwrap();
+    assert_eq!(config.active_users, 2);
+
+    //撤销会员后密码作废，空出的位置可以分配给其他人
+    SET_VIP_API
+        .call_with_host(host(), &admin, &(second.uid, VipChange::Revoke))
+        .await
+        .unwrap();
+    let result = GET_SURF_SERVERS_API.call_with_host(host(), &second, &()).await;
+    assert_eq!(result, Err(Error::PermissionDenied));
+    assert_eq!(servers_named(&third, "surfshared").await.len(), 1);
+
+    //更换密码后重新生成
+    let revoked = ROTATE_SURF_CREDENTIALS_API.call_with_host(host(), &admin, &id).await.unwrap();
+    assert_eq!(revoked, 2);
+    let rotated = servers_named(&first, "surfshared").await;
+    assert_ne!(rotated[0].password, first_servers[0].password);
+
+    //停用的服务器不再返回
+    let disabled = SurfServerConfig {
+        id: Some(id),
+        enabled: false,
+        ..server_config("surfshared", 2)
+    };
+    SAVE_SURF_SERVER_API.call_with_host(host(), &admin, &disabled).await.unwrap();
+    assert!(servers_named(&first, "surfshared").await.is_empty());
+}
+
+#[tokio::test]
+async fn concurrent_requests_share_one_credential() {
+    let admin = login_admin("surfraceadmin").await;
+    let user = login_vip(&admin, "surfrace").await;
+    let id = SAVE_SURF_SERVER_API
+        .call_with_host(host(), &admin, &server_config("surfrace", 0))
+        .await
+        .unwrap();
+
+    let requests = (0..4).map(|_| servers_named(&user, "surfrace"));
+    let passwords = join_all(requests)
+        .await
+        .into_iter()
+        .map(|servers| servers[0].password.to_owned())
+        .collect::<Vec<_>>();
+    assert!(passwords.iter().all(|it| *it == passwords[0]));
+
+    let credentials = GET_SURF_CREDENTIALS_API.call_with_host(host(), &admin, &id).await.unwrap();
+    assert_eq!(credentials.len(), 1);
+}
+
+#[tokio::test]
+async fn concurrent_requests_respect_capacity() {
+    let admin = login_admin("surfcapadmin").await;
+    let mut users = Vec::new();
+    for index in 0..4 {
+        users.push(login_vip(&admin, format!("surfcap{index}").as_str()).await);
+    }
+    let id = SAVE_SURF_SERVER_API
+        .call_with_host(host(), &admin, &server_config("surfcap", 2))
+        .await
+        .unwrap();
+
+    let requests = users.iter().map(|user| servers_named(user, "surfcap"));
+    let served = join_all(requests).await.into_iter().filter(|it| !it.is_empty()).count();
+    assert_eq!(served, 2);
+
+    let credentials = GET_SURF_CREDENTIALS_API.call_with_host(host(), &admin, &id).await.unwrap();
+    assert_eq!(credentials.len(), 2);
+}
@@ -0,0 +1,106 @@
+use std::collections::HashSet;
+
+use chrono::Duration;
+use sea_orm::{ActiveModelTrait, Set};
+
+use senyoshu_common::types::api::dic::{DELETE_WORD_API, SYNC_DIC_API, SyncDicRequest};
+use senyoshu_common::types::word::wid::WordIdentity;
+use senyoshu_server::database::dic::words;
+
+use crate::common::{host, login_content_maintainer, seed_word, sync_dic, with_db, word_define};
+
+mod common;
+
+#[tokio::test]
+async fn pages_cover_every_word_once() {
+    let mut seeded = HashSet::new();
+    for i in 0..5 {
+        seeded.insert(seed_word(word_define(format!("page {i}").as_str())).await);
+    }
+
+    let mut request = SyncDicRequest { limit: 2, ..Default::default() };
+    let mut synced = Vec::new();
+    let mut watermark = None;
+    loop {
+        let page = SYNC_DIC_API.call_with_host(host(), &request).await.unwrap();
+        assert!(page.words.len() <= 2);
+        //同一次同步的高水位不变
+        assert_eq!(*watermark.get_or_insert(page.watermark), page.watermark);
+        synced.extend(page.words.into_iter().map(|(wid, _)| wid));
+        match page.next {
+            Some(next) => request.cursor = Some(next),
+            None => break,
+        }
+    }
+
+    let unique = synced.iter().cloned().collect::<HashSet<WordIdentity>>();
+    assert_eq!(unique.len(), synced.len());
+    assert!(seeded.is_subset(&unique));
+}
+
+#[tokio::test]
+async fn compressed_page_matches_json() {
+    for i in 0..3 {
+        seed_word(word_define(format!("compressed {i}").as_str())).await;
+    }
+
+    let first = SyncDicRequest { limit: 1, ..Default::default() };
+    let page = SYNC_DIC_API.call_compressed_with_host(host(), &first).await.unwrap();
+    assert_eq!(page.words.len(), 1);
+
+    //继续同一次同步时高水位相同，两种编码的结果也相同
+    let second = SyncDicRequest { cursor: page.next, limit: 2, ..Default::default() };
+    let json = SYNC_DIC_API.call_with_host(host(), &second).await.unwrap();
+    let compressed = SYNC_DIC_API.call_compressed_with_host(host(), &second).await.unwrap();
+    assert_eq!(json, compressed);
+    assert_eq!(compressed.words.len(), 2);
+}
+
+#[tokio::test]
+async fn incremental_sync_returns_deleted_word() {
+    let maintainer = login_content_maintainer("syncdeleter").await;
+    let wid = seed_word(word_define("deleted")).await;
+
+    let page = SYNC_DIC_API.call_with_host(host(), &SyncDicRequest::default()).await.unwrap();
+    DELETE_WORD_API.call_with_host(host(), &maintainer, &(wid.0, None)).await.unwrap();
+
+    let dic = sync_dic(Some(page.watermark)).await;
+    assert_eq!(dic.get(&wid), Some(&None));
+    //全量同步时不返回已删除的词
+    let dic = sync_dic(None).await;
+    assert_eq!(dic.get(&wid), None);
+}
+
+#[tokio::test]
+async fn word_after_watermark_waits_for_next_sync() {
+    let first = SyncDicRequest { limit: 1, ..Default::default() };
+    let page = SYNC_DIC_API.call_with_host(host(), &first).await.unwrap();
+
+    //同步过程中写入的词，更新时间晚于高水位，不在本次同步中
+    let update_date = page.watermark + Duration::milliseconds(1);
+    let wid = with_db(move |db| async move {
+        words::ActiveModel {
+            word_define: Set(Some(word_define("later"))),
+            update_date: Set(update_date),
+            ..Default::default()
+        }
+            .insert(db)
+            .await
+            .unwrap()
+            .wid
+    })
+        .await;
+
+    let mut request = SyncDicRequest { cursor: page.next, limit: 1, ..Default::default() };
+    let mut watermark = page.watermark;
+    while request.cursor.is_some() {
+        let page = SYNC_DIC_API.call_with_host(host(), &request).await.unwrap();
+        assert!(page.words.iter().all(|(it, _)| *it != wid));
+        watermark = page.watermark;
+        request.cursor = page.next;
+    }
+
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    let dic = sync_dic(Some(watermark)).await;
+    assert!(dic.contains_key(&wid));
+}
@@ -0,0 +1,214 @@
+use std::sync::Arc;
+
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use tokio::sync::{Mutex, MutexGuard};
+
+use senyoshu_common::types::api::sound::{SoundKey, SoundUpload, UPLOAD_SOUND_API};
+use senyoshu_common::types::word::tones::Tones;
+use senyoshu_common::types::word::word::{Word, WordElement};
+use senyoshu_common::types::word::word_entry::WordDefine;
+use senyoshu_server::config::{TtsCommandConfig, TtsConfig};
+use senyoshu_server::database::dic::sounds;
+use senyoshu_server::tts::command::CommandEngine;
+use senyoshu_server::tts::job::{TTS_AUTHOR, TtsJob, TtsReport};
+use senyoshu_server::tts::test_engine::TestEngine;
+use senyoshu_server::tts::TtsEngine;
+
+use crate::common::{host, login_content_maintainer, seed_word, with_db};
+
+mod common;
+
+/// 任务扫描共用的整个词典，已有任意说话人音频的读音不再生成，同时运行的测试会互相影响
+static JOB_LOCK: Mutex<()> = Mutex::const_new(());
+
+async fn exclusive() -> MutexGuard<'static, ()> {
+    JOB_LOCK.lock().await
+}
+
+/// 每个测试使用不同的说话人，区分各自生成的音频
+fn config(role: &str) -> TtsConfig {
+    TtsConfig {
+        role: role.to_string(),
+        ..Default::default()
+    }
+}
+
+async fn seed(txt: &str, ruby: &str, tones: &[usize]) {
+    let mut tone_flags = [false; 6];
+    for tone in tones {
+        tone_flags[*tone] = true;
+    }
+    seed_word(WordDefine {
+        word: Word {
+            elements: Vec::from([WordElement {
+                txt: txt.to_string(),
+                ruby: ruby.to_string(),
+                proto: String::new(),
+            }]),
+            tones: Tones(tone_flags),
+        },
+        ..WordDefine::template()
+    })
+        .await;
+}
+
+async fn run(engine: Arc<dyn TtsEngine>, role: &str) -> TtsReport {
+    let job = TtsJob::new(engine, &config(role));
+    with_db(move |db| async move { job.run_once(db).await.unwrap() }).await
+}
+
+async fn find_sound(katakana: &str, tone: i16, role: &str) -> Option<sounds::Model> {
+    let key = (katakana.to_string(), tone, role.to_string());
+    with_db(move |db| async move { sounds::Entity::find_by_id(key).one(db).await.unwrap() }).await
+}
+
+#[tokio::test]
+async fn missing_sounds_are_generated_as_unchecked() {
+    let _guard = exclusive().await;
+    seed("桜", "さくら", &[0]).await;
+    seed("雨", "あめ", &[1]).await;
+    //审核者已经上传的音频不会被覆盖
+    let maintainer = login_content_maintainer("ttsmaintainer").await;
+    let upload = SoundUpload {
+        key: SoundKey {
+            katakana: String::from("アメ"),
+            tone: 1,
+            role: String::from("ttsdemo"),
+        },
+        content_type: String::from("audio/mpeg"),
+        sound: b"ame".to_vec(),
+    };
+    UPLOAD_SOUND_API.call_with_host(host(), &maintainer, &upload).await.unwrap();
+
+    let report = run(Arc::new(TestEngine), "ttsdemo").await;
+    assert!(report.generated >= 1);
+    assert_eq!(report.failed, 0);
+
+    let sakura = find_sound("サクラ", 0, "ttsdemo").await.unwrap();
+    assert_eq!(sakura.checked, None);
+    assert_eq!(sakura.author, TTS_AUTHOR);
+    assert_eq!(sakura.content_type, "audio/wav");
+    assert!(sakura.sound.starts_with(b"RIFF"));
+    let ame = find_sound("アメ", 1, "ttsdemo").await.unwrap();
+    assert_eq!(ame.author, maintainer.uid);
+    assert_eq!(ame.sound, b"ame");
+
+    //已经生成的不会重复生成，换了说话人也一样
+    run(Arc::new(TestEngine), "ttsdemo").await;
+    assert_eq!(find_sound("サクラ", 0, "ttsdemo").await.unwrap(), sakura);
+    run(Arc::new(TestEngine), "ttsagain").await;
+    assert_eq!(find_sound("サクラ", 0, "ttsagain").await, None);
+}
+
+#[tokio::test]
+async fn recording_of_other_role_is_not_generated() {
+    let _guard = exclusive().await;
+    seed("雀", "すずめ", &[0]).await;
+    let maintainer = login_content_maintainer("ttsotherrole").await;
+    let upload = SoundUpload {
+        key: SoundKey {
+            katakana: String::from("スズメ"),
+            tone: 0,
+            role: String::from("ttshuman"),
+        },
+        content_type: String::from("audio/mpeg"),
+        sound: b"suzume".to_vec(),
+    };
+    UPLOAD_SOUND_API.call_with_host(host(), &maintainer, &upload).await.unwrap();
+
+    run(Arc::new(TestEngine), "ttsother").await;
+    assert_eq!(find_sound("スズメ", 0, "ttsother").await, None);
+    assert_eq!(find_sound("スズメ", 0, "ttshuman").await.unwrap().sound, b"suzume");
+}
+
+#[tokio::test]
+async fn command_engine_reads_audio_from_stdout() {
+    let _guard = exclusive().await;
+    seed("燕", "つばめ", &[0]).await;
+    let engine = CommandEngine::new(&TtsCommandConfig {
+        program: String::from("sh"),
+        args: Vec::from([
+            String::from("-c"),
+            String::from("printf '%s/%s/%s' \"$0\" \"$1\" \"$2\""),
+            String::from("{katakana}"),
+            String::from("{tone}"),
+            String::from("{role}"),
+        ]),
+        ..Default::default()
+    })
+        .unwrap();
+
+    run(Arc::new(engine), "ttscommand").await;
+    let sound = find_sound("ツバメ", 0, "ttscommand").await.unwrap();
+    assert_eq!(sound.sound, "ツバメ/0/ttscommand".as_bytes());
+    assert_eq!(sound.checked, None);
+}
+
+#[tokio::test]
+async fn failed_command_stores_nothing() {
+    let _guard = exclusive().await;
+    seed("鳩", "はと", &[1]).await;
+    let engine = CommandEngine::new(&TtsCommandConfig {
+        program: String::from("sh"),
+        args: Vec::from([String::from("-c"), String::from("echo broken >&2; exit 3")]),
+        ..Default::default()
+    })
+        .unwrap();
+
+    let report = run(Arc::new(engine), "ttsbroken").await;
+    assert_eq!(report.generated, 0);
+    assert!(report.failed >= 1);
+    let rows = with_db(|db| async move {
+        sounds::Entity::find()
+            .filter(sounds::Column::Role.eq("ttsbroken"))
+            .all(db)
+            .await
+            .unwrap()
+    })
+        .await;
+    assert!(rows.is_empty());
+}
+
+#[test]
+fn command_engine_rejects_invalid_content_type() {
+    for content_type in ["text/plain", "audio/x\n"] {
+        let result = CommandEngine::new(&TtsCommandConfig {
+            program: String::from("sh"),
+            content_type: content_type.to_string(),
+            ..Default::default()
+        });
+        assert!(result.is_err(), "{content_type:?}");
+    }
+}
+
+#[tokio::test]
+async fn scan_continues_from_cursor() {
+    let _guard = exclusive().await;
+    seed("鶴", "つる", &[0, 2]).await;
+    let job = TtsJob::new(
+        Arc::new(TestEngine),
+        &TtsConfig {
+            batch_size: 1,
+            ..config("ttscursor")
+        },
+    );
+    let reports = with_db(move |db| async move {
+        let first = job.run_once(db).await.unwrap();
+        let second = job.run_once(db).await.unwrap();
+        (first, second)
+    })
+        .await;
+    assert_eq!(reports.0.generated, 1);
+    assert!(reports.0.remaining >= 1);
+    assert_eq!(reports.1.generated, 1);
+
+    let rows = with_db(|db| async move {
+        sounds::Entity::find()
+            .filter(sounds::Column::Role.eq("ttscursor"))
+            .all(db)
+            .await
+            .unwrap()
+    })
+        .await;
+    assert_eq!(rows.len(), 2);
+}